@@ -15,6 +15,7 @@ use crate::gastly::player_state::{PlayerEvolutionState, EvolutionStage};
 use crate::gastly::visuals::{update_body_and_unique_parts_visibility, set_active_eye_mesh};
 use crate::gastly::icon_management::deactivate_all_pos_sensitive_icons;
 use crate::gastly::constants::*;
+use crate::gastly::mesh_visibility::set_mesh_visibility;
 
 // Initialize all WorkModule flags and timers for Gastly evolution system
 unsafe fn initialize_work_module_flags_and_timers(boma: &mut smash::app::BattleObjectModuleAccessor) {
@@ -74,7 +75,7 @@ unsafe fn hide_all_evolution_meshes(boma: &mut smash::app::BattleObjectModuleAcc
     ];
 
     for &mesh in &meshes_to_hide {
-        ModelModule::set_mesh_visibility(boma, mesh, false);
+        set_mesh_visibility(boma, mesh, false);
     }
 }
 
@@ -110,6 +111,9 @@ pub unsafe extern "C" fn agent_reset_gastly_evolution(fighter: &mut L2CFighterCo
         let mut states_map_writer = FIGHTER_STATES.write();
         let player_state = states_map_writer.entry(instance_key).or_insert_with(PlayerEvolutionState::new);
 
+        // Model was (re)loaded - the cached mesh visibility no longer matches the engine
+        crate::gastly::mesh_visibility::invalidate_mesh_cache(instance_key);
+
         // Clean up dark effects and flash macros on reset
         crate::gastly::darkfx::cleanup_dark_effects_on_death(entry_id_u32);
         macros::COL_NORMAL(fighter);
@@ -129,6 +133,10 @@ pub unsafe extern "C" fn agent_reset_gastly_evolution(fighter: &mut L2CFighterCo
         
         player_state.reset_evo_readiness_icons();
 
+        // Shiny odds roll at match entry (frame callback); keep this match's roll, results screen included
+        player_state.shiny_roll = crate::gastly::shiny_odds::shiny_roll_for(boma);
+        player_state.is_shiny = crate::gastly::shiny_odds::is_shiny_this_match(boma);
+
         // Force Gastly stage for marked costumes
         force_gastly_reset_for_marked_costume(boma, player_state, color_id);
 
@@ -154,10 +162,9 @@ pub unsafe extern "C" fn agent_reset_gastly_evolution(fighter: &mut L2CFighterCo
 unsafe fn cancel_evolution_for_entry(player_state: &mut PlayerEvolutionState, check_boma: *mut smash::app::BattleObjectModuleAccessor) {
     player_state.is_evolving = false;
     player_state.linking_cord_active = false;
-    player_state.linking_cord_evo_attempt_icon_timer = 0;
-    player_state.linking_cord_evo_attempt_icon_is_pos_sensitive = false;
+    player_state.icons.cancel(crate::gastly::icon_manager::OverheadIcon::LinkingCordEvolving);
     player_state.evolution_timer = 0;
-    player_state.down_taunt_cancel_press_count = 0;
+    player_state.command_matcher.reset_command(crate::gastly::input_sequence::EvolutionCommand::CancelEvolution);
     player_state.last_evolution_confirmation_frame = -1;
     player_state.reset_evo_readiness_icons();
     player_state.evolution_just_cancelled_this_frame = true;
@@ -173,6 +180,9 @@ unsafe extern "C" fn training_mode_reset_handler(fighter: &mut L2CFighterCommon)
     
     let entry_id_val = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID);
     let entry_id_u32 = entry_id_val as u32;
+
+    // Remember this entry's real portrait before any evolution UI is applied
+    crate::gastly::ui_management::capture_original_ui_chara(boma);
     
     // Force clear flash effects for ALL characters during training reset
     crate::gastly::darkfx::cleanup_dark_effects_on_death(entry_id_u32);
@@ -202,7 +212,7 @@ unsafe extern "C" fn training_mode_reset_handler(fighter: &mut L2CFighterCommon)
     let instance_keys: Vec<u32> = states_map_writer.keys().copied().collect();
     
     for instance_key in instance_keys {
-        let check_entry_id = (instance_key / 32) as u32; // Convert instance_key back to entry_id
+        let check_entry_id = crate::gastly::entry_id_from_instance_key(instance_key);
         let check_boma = smash::app::sv_battle_object::module_accessor(check_entry_id);
         if !check_boma.is_null() && utility::get_kind(&mut *check_boma) == *FIGHTER_KIND_PURIN {
             let check_color_id = WorkModule::get_int(check_boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR) as usize;
@@ -238,6 +248,7 @@ unsafe extern "C" fn training_mode_reset_handler(fighter: &mut L2CFighterCommon)
                     player_state.giga_gengar_form_active = false;
                     
                     // Force visual update
+                    crate::gastly::mesh_visibility::invalidate_mesh_cache(instance_key);
                     crate::gastly::visuals::update_body_and_unique_parts_visibility(check_boma, crate::gastly::player_state::EvolutionStage::Gastly);
                     crate::gastly::visuals::set_active_eye_mesh(check_boma, player_state, None);
                 }
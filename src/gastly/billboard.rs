@@ -0,0 +1,252 @@
+// src/gastly/billboard.rs
+
+// Runtime billboarding for the overhead icons (`billboard` joint) and the down-taunt ghost.
+//
+// The model's own billboard flag breaks when facing left: flip.prc mirrors the model across X
+// instead of turning it, which mirrors the billboarded mesh and reverses the joint's yaw. Instead,
+// while an icon is up (or the ghost is out) the joint is counter-rotated so it ends up facing the
+// camera, and mirrored back when PostureModule::lr is negative. Otherwise the joints are left to
+// the animation. The battle camera pans and zooms but looks straight down -Z, so "facing the
+// camera" defaults to world yaw 0; it hasn't been checked against every stage camera, so it can be
+// tuned without a rebuild:
+//
+//   [billboard]
+//   camera_yaw = 0.0     # degrees
+//   icon_parent = top    # joint the `billboard` joint hangs from
+//   ghost_parent = top   # joint the `ghost` joint hangs from
+//
+// The yaw to undo is the parent joint's global yaw (radians, converted to degrees, the unit
+// set_joint_rotate takes), so rotation on any joint between `top` and the icon/ghost is corrected
+// too. The icon's rise/shrink animation is added on top of the joint's own bind translation, which
+// is captured (relative to the parent) on the frame the override starts.
+
+use smash::app::lua_bind::{ModelModule, MotionModule, PostureModule};
+use smash::app::{BattleObjectModuleAccessor, MotionNodeRotateCompose, MotionNodeRotateOrder};
+use smash::lib::lua_const::*;
+use smash::phx::{Hash40, Vector3f};
+use parking_lot::RwLock;
+
+use crate::gastly::config::IniDocument;
+use crate::gastly::constants::*;
+use crate::gastly::icon_manager::IconPose;
+use crate::gastly::player_state::PlayerEvolutionState;
+
+// Yaw of the camera's view direction, reversed (the way a mesh must face to be seen head-on)
+pub const CAMERA_FACING_YAW: f32 = 0.0;
+
+pub const DEFAULT_PARENT_JOINT: &str = "top";
+
+pub static BILLBOARD_CAMERA_YAW: RwLock<f32> = parking_lot::const_rwlock(CAMERA_FACING_YAW);
+
+// (icon parent, ghost parent) joint hashes; 0 until the config is loaded (falls back to `top`)
+static BILLBOARD_PARENTS: RwLock<(u64, u64)> = parking_lot::const_rwlock((0, 0));
+
+pub fn camera_yaw_from_config(config: &IniDocument) -> f32 {
+    config.section("billboard")
+        .and_then(|section| section.get_f32("camera_yaw"))
+        .filter(|yaw| yaw.is_finite())
+        .unwrap_or(CAMERA_FACING_YAW)
+}
+
+pub fn parent_joint_from_config<'a>(config: &'a IniDocument, key: &str) -> &'a str {
+    config.section("billboard")
+        .and_then(|section| section.get(key))
+        .filter(|joint| !joint.is_empty())
+        .unwrap_or(DEFAULT_PARENT_JOINT)
+}
+
+pub fn load_billboard_config() {
+    let config = crate::gastly::config::GASTLY_CONFIG.read();
+    *BILLBOARD_CAMERA_YAW.write() = camera_yaw_from_config(&config);
+    *BILLBOARD_PARENTS.write() = (
+        smash::hash40(parent_joint_from_config(&config, "icon_parent")),
+        smash::hash40(parent_joint_from_config(&config, "ghost_parent")),
+    );
+}
+
+fn parent_joints() -> (Hash40, Hash40) {
+    let (icon, ghost) = *BILLBOARD_PARENTS.read();
+    let or_top = |hash: u64| if hash == 0 { *TOP_BONE } else { Hash40 { hash } };
+    (or_top(icon), or_top(ghost))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BillboardCorrection {
+    pub rotate_y: f32, // Local yaw to add to the joint, degrees
+    pub scale_x: f32,  // -1 undoes the facing-left mirror
+}
+
+impl BillboardCorrection {
+    pub const NONE: Self = Self { rotate_y: 0.0, scale_x: 1.0 };
+}
+
+fn wrap_degrees(degrees: f32) -> f32 {
+    let wrapped = (degrees + 180.0).rem_euclid(360.0) - 180.0;
+    if wrapped == -180.0 { 180.0 } else { wrapped }
+}
+
+// `body_yaw`: world yaw of the fighter (degrees), `lr`: PostureModule::lr
+pub fn billboard_correction(body_yaw: f32, lr: f32, camera_yaw: f32) -> BillboardCorrection {
+    let mirrored = lr < 0.0;
+    let yaw = wrap_degrees(camera_yaw - body_yaw);
+    BillboardCorrection {
+        // A mirrored parent reverses the direction of local yaw
+        rotate_y: if mirrored { -yaw } else { yaw },
+        scale_x: if mirrored { -1.0 } else { 1.0 },
+    }
+}
+
+// A world-space offset from the parent joint, brought into the parent's space: undo the
+// facing-left mirror, then the parent's yaw (degrees)
+pub fn parent_local_offset(offset: [f32; 3], parent_yaw: f32, lr: f32) -> [f32; 3] {
+    let x = if lr < 0.0 { -offset[0] } else { offset[0] };
+    let (sin, cos) = (-parent_yaw.to_radians()).sin_cos();
+    [x * cos + offset[2] * sin, offset[1], offset[2] * cos - x * sin]
+}
+
+// Whether the joint override is on (instance key)
+static mut ICON_BILLBOARDED: [bool; 256] = [false; 256];
+static mut GHOST_BILLBOARDED: [bool; 256] = [false; 256];
+
+// The icon joint's bind translation, captured when its override starts (instance key)
+static mut ICON_BIND_TRANSLATE: [[f32; 3]; 256] = [[0.0; 3]; 256];
+
+unsafe fn set_joint_pose(
+    boma: *mut BattleObjectModuleAccessor,
+    joint: Hash40,
+    correction: BillboardCorrection,
+    scale: f32,
+) {
+    let rotate = Vector3f { x: 0.0, y: correction.rotate_y, z: 0.0 };
+    let scale = Vector3f { x: scale * correction.scale_x, y: scale, z: scale };
+    ModelModule::set_joint_rotate(
+        boma,
+        joint,
+        &rotate,
+        MotionNodeRotateCompose { _address: *MOTION_NODE_ROTATE_COMPOSE_AFTER as u8 },
+        MotionNodeRotateOrder { _address: *MOTION_NODE_ROTATE_ORDER_XYZ as u8 },
+    );
+    ModelModule::set_joint_scale(boma, joint, &scale);
+}
+
+unsafe fn icon_joint_pose(boma: *mut BattleObjectModuleAccessor, correction: BillboardCorrection, pose: IconPose, bind: [f32; 3]) {
+    let translate = Vector3f { x: bind[0], y: bind[1] + pose.y_offset, z: bind[2] };
+    ModelModule::set_joint_translate(boma, *BILLBOARD_BONE, &translate, false, false);
+    set_joint_pose(boma, *BILLBOARD_BONE, correction, pose.scale);
+}
+
+unsafe fn joint_global_yaw(boma: *mut BattleObjectModuleAccessor, joint: Hash40) -> f32 {
+    let mut rotation = Vector3f { x: 0.0, y: 0.0, z: 0.0 };
+    ModelModule::joint_global_rotation(boma, joint, &mut rotation, false);
+    rotation.y.to_degrees()
+}
+
+// Only valid while the animation still drives the joint
+unsafe fn bind_translation(boma: *mut BattleObjectModuleAccessor, joint: Hash40, parent: Hash40) -> [f32; 3] {
+    let mut joint_pos = Vector3f { x: 0.0, y: 0.0, z: 0.0 };
+    let mut parent_pos = Vector3f { x: 0.0, y: 0.0, z: 0.0 };
+    ModelModule::joint_global_position(boma, joint, &mut joint_pos, true);
+    ModelModule::joint_global_position(boma, parent, &mut parent_pos, true);
+    let offset = [joint_pos.x - parent_pos.x, joint_pos.y - parent_pos.y, joint_pos.z - parent_pos.z];
+    parent_local_offset(offset, joint_global_yaw(boma, parent), PostureModule::lr(boma))
+}
+
+unsafe fn parent_correction(boma: *mut BattleObjectModuleAccessor, parent: Hash40) -> BillboardCorrection {
+    billboard_correction(joint_global_yaw(boma, parent), PostureModule::lr(boma), *BILLBOARD_CAMERA_YAW.read())
+}
+
+// Called every frame for marked Purin, after the icons have been updated
+pub unsafe fn update_billboards(boma: *mut BattleObjectModuleAccessor, player_state: &PlayerEvolutionState, instance_key: usize) {
+    if instance_key >= 256 {
+        return;
+    }
+    let icon_shown = player_state.icons.any_visible();
+    let motion = MotionModule::motion_kind(boma);
+    let ghost_motion = motion == APPEAL_LW_L_MOTION.hash || motion == APPEAL_LW_R_MOTION.hash;
+    if !icon_shown && !ghost_motion && !ICON_BILLBOARDED[instance_key] && !GHOST_BILLBOARDED[instance_key] {
+        return;
+    }
+    let (icon_parent, ghost_parent) = parent_joints();
+
+    if icon_shown {
+        if !ICON_BILLBOARDED[instance_key] {
+            ICON_BIND_TRANSLATE[instance_key] = bind_translation(boma, *BILLBOARD_BONE, icon_parent);
+        }
+        let correction = parent_correction(boma, icon_parent);
+        icon_joint_pose(boma, correction, player_state.icons.current_pose(), ICON_BIND_TRANSLATE[instance_key]);
+        ICON_BILLBOARDED[instance_key] = true;
+    } else if ICON_BILLBOARDED[instance_key] {
+        // Hand the joint back to the animation
+        ModelModule::clear_joint_srt(boma, *BILLBOARD_BONE);
+        ICON_BILLBOARDED[instance_key] = false;
+    }
+
+    if ghost_motion {
+        set_joint_pose(boma, *GHOST_BONE, parent_correction(boma, ghost_parent), 1.0);
+        GHOST_BILLBOARDED[instance_key] = true;
+    } else if GHOST_BILLBOARDED[instance_key] {
+        ModelModule::clear_joint_srt(boma, *GHOST_BONE);
+        GHOST_BILLBOARDED[instance_key] = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-4, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn facing_right_counter_rotates_body_yaw() {
+        let correction = billboard_correction(30.0, 1.0, CAMERA_FACING_YAW);
+        assert_close(correction.rotate_y, -30.0);
+        assert_close(correction.scale_x, 1.0);
+    }
+
+    #[test]
+    fn facing_left_reverses_yaw_and_mirrors_back() {
+        let correction = billboard_correction(30.0, -1.0, CAMERA_FACING_YAW);
+        assert_close(correction.rotate_y, 30.0);
+        assert_close(correction.scale_x, -1.0);
+    }
+
+    #[test]
+    fn facing_camera_needs_no_rotation() {
+        assert_eq!(billboard_correction(0.0, 1.0, 0.0), BillboardCorrection::NONE);
+        assert_close(billboard_correction(15.0, 1.0, 15.0).rotate_y, 0.0);
+    }
+
+    #[test]
+    fn offsets_are_brought_into_the_parent_space() {
+        let local = parent_local_offset([0.0, 8.0, 0.0], 45.0, 1.0);
+        assert_close(local[0], 0.0);
+        assert_close(local[1], 8.0);
+        assert_close(local[2], 0.0);
+
+        // Parent turned a quarter: its local +X points along world -Z
+        let local = parent_local_offset([0.0, 1.0, -2.0], 90.0, 1.0);
+        assert_close(local[0], 2.0);
+        assert_close(local[2], 0.0);
+
+        // Facing left, the mirror is undone first
+        let local = parent_local_offset([-3.0, 1.0, 0.0], 0.0, -1.0);
+        assert_close(local[0], 3.0);
+    }
+
+    #[test]
+    fn parent_joints_default_to_top() {
+        let config = crate::gastly::config::parse_ini("[billboard]\nghost_parent = rot\nicon_parent =\n");
+        assert_eq!(parent_joint_from_config(&config, "icon_parent"), DEFAULT_PARENT_JOINT);
+        assert_eq!(parent_joint_from_config(&config, "ghost_parent"), "rot");
+    }
+
+    #[test]
+    fn yaw_wraps_to_half_turn() {
+        assert_close(billboard_correction(-170.0, 1.0, 20.0).rotate_y, -170.0);
+        assert_close(billboard_correction(180.0, 1.0, 0.0).rotate_y, 180.0);
+        assert_close(billboard_correction(-180.0, 1.0, 0.0).rotate_y, 180.0);
+        assert_close(billboard_correction(725.0, 1.0, 0.0).rotate_y, -5.0);
+    }
+}
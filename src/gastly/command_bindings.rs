@@ -0,0 +1,301 @@
+// src/gastly/command_bindings.rs
+
+// Remappable button sequences for the evolution commands (see input_sequence.rs).
+//
+//   [bindings]                         # everyone
+//   everstone = special, special
+//   linking_cord = up_taunt, up_taunt
+//   cancel_evolution = down_taunt, down_taunt
+//   cancel_evolution_context = anywhere   # guard_ground | guard_ground_or_air | anywhere
+//   cancel_evolution_window = 45          # max frames between presses
+//
+//   [bindings.slot3]                   # costume slot c03
+//   gengarite = up_taunt, side_taunt_l
+//
+//   [bindings.player2]                 # port 2 (most specific, wins over slot and global)
+//   dynamax = special, side_taunt_r
+//
+// Commands: linking_cord, everstone, cancel_evolution, gengarite, dynamax.
+// Buttons: special, up_taunt, down_taunt, side_taunt_l, side_taunt_r (appeal_hi/lw/s_l/s_r also work).
+//
+// Two commands conflict when both can be entered in the same state and one sequence is a prefix of
+// (or equal to) the other. Cancel evolution also can't start with the Everstone button: while
+// evolving, that button on its own shows the Everstone X icon. Conflicting sets are dropped at load
+// time; a player whose merged bindings conflict falls back to the next less specific set
+// (player+slot -> player -> slot -> global). Every dropped set is listed in BINDINGS_REPORT_PATH.
+
+use std::collections::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+use crate::gastly::config::{IniDocument, IniSection};
+use crate::gastly::input_sequence::{default_patterns, CommandButton, CommandPattern, EvolutionCommand, InputContext};
+
+pub const MAX_BINDING_LEN: usize = 4;
+pub const BINDINGS_REPORT_PATH: &str = "sd:/ultimate/gastly/bindings_report.txt";
+
+pub fn command_key(command: EvolutionCommand) -> &'static str {
+    match command {
+        EvolutionCommand::LinkingCord => "linking_cord",
+        EvolutionCommand::Everstone => "everstone",
+        EvolutionCommand::CancelEvolution => "cancel_evolution",
+        EvolutionCommand::Gengarite => "gengarite",
+        EvolutionCommand::Dynamax => "dynamax",
+    }
+}
+
+pub fn parse_button(value: &str) -> Option<CommandButton> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "special" | "b" => Some(CommandButton::Special),
+        "up_taunt" | "appeal_hi" => Some(CommandButton::AppealHi),
+        "down_taunt" | "appeal_lw" => Some(CommandButton::AppealLw),
+        "side_taunt_l" | "appeal_s_l" => Some(CommandButton::AppealSL),
+        "side_taunt_r" | "appeal_s_r" => Some(CommandButton::AppealSR),
+        _ => None,
+    }
+}
+
+pub fn parse_context(value: &str) -> Option<InputContext> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "guard_ground" => Some(InputContext::GuardOnGround),
+        "guard_ground_or_air" => Some(InputContext::GuardOnGroundOrAir),
+        "anywhere" => Some(InputContext::Anywhere),
+        _ => None,
+    }
+}
+
+// Pairs that can be entered in the same state, so their sequences mustn't share a prefix.
+// Every other pair is never armed at once and may share a sequence.
+fn commands_can_overlap(a: EvolutionCommand, b: EvolutionCommand) -> bool {
+    use EvolutionCommand::*;
+    matches!((a, b), (LinkingCord, Everstone) | (Everstone, LinkingCord) | (Gengarite, Dynamax) | (Dynamax, Gengarite))
+}
+
+pub fn find_conflicts(patterns: &[CommandPattern]) -> Vec<(EvolutionCommand, EvolutionCommand)> {
+    let mut conflicts = Vec::new();
+    for (index, a) in patterns.iter().enumerate() {
+        for b in patterns.iter().skip(index + 1) {
+            if !commands_can_overlap(a.command, b.command) {
+                continue;
+            }
+            let shared = a.buttons.len().min(b.buttons.len());
+            if a.buttons[..shared] == b.buttons[..shared] {
+                conflicts.push((a.command, b.command));
+            }
+        }
+    }
+
+    // Single Everstone press while evolving (icon_management.rs) against the cancel sequence
+    let first_button = |command: EvolutionCommand| {
+        patterns.iter().find(|pattern| pattern.command == command).and_then(|pattern| pattern.buttons.first().copied())
+    };
+    let everstone = first_button(EvolutionCommand::Everstone);
+    if everstone.is_some() && everstone == first_button(EvolutionCommand::CancelEvolution) {
+        conflicts.push((EvolutionCommand::Everstone, EvolutionCommand::CancelEvolution));
+    }
+
+    conflicts
+}
+
+// One command's settings from a [bindings*] section; unset parts keep the less specific value
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingOverride {
+    pub command: EvolutionCommand,
+    pub buttons: Option<Vec<CommandButton>>,
+    pub context: Option<InputContext>,
+    pub window: Option<i32>,
+}
+
+pub fn parse_binding_section(section: &IniSection) -> Result<Vec<BindingOverride>, String> {
+    let mut overrides = Vec::new();
+
+    for command in EvolutionCommand::ALL {
+        let key = command_key(command);
+        let buttons = match section.get(key) {
+            Some(value) => {
+                let buttons: Option<Vec<CommandButton>> = value.split(',').map(parse_button).collect();
+                match buttons {
+                    Some(buttons) if !buttons.is_empty() && buttons.len() <= MAX_BINDING_LEN => Some(buttons),
+                    _ => return Err(format!("{}: bad button sequence '{}'", key, value)),
+                }
+            }
+            None => None,
+        };
+        let context = match section.get(&format!("{}_context", key)) {
+            Some(value) => Some(parse_context(value).ok_or_else(|| format!("{}_context: unknown context '{}'", key, value))?),
+            None => None,
+        };
+        let window = section.get_i32(&format!("{}_window", key)).filter(|window| *window > 0);
+
+        if buttons.is_some() || context.is_some() || window.is_some() {
+            overrides.push(BindingOverride { command, buttons, context, window });
+        }
+    }
+
+    Ok(overrides)
+}
+
+pub fn merge_overrides(base: &[CommandPattern], overrides: &[BindingOverride]) -> Vec<CommandPattern> {
+    let mut merged = base.to_vec();
+    for pattern in merged.iter_mut() {
+        if let Some(over) = overrides.iter().find(|over| over.command == pattern.command) {
+            if let Some(buttons) = &over.buttons {
+                pattern.buttons = buttons.clone();
+            }
+            pattern.context = over.context.unwrap_or(pattern.context);
+            pattern.window = over.window.unwrap_or(pattern.window);
+        }
+    }
+    merged
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BindingTable {
+    pub global: Vec<CommandPattern>,
+    pub slots: HashMap<u32, Vec<BindingOverride>>,   // Costume slot -> overrides
+    pub players: HashMap<u32, Vec<BindingOverride>>, // Entry id -> overrides
+}
+
+impl BindingTable {
+    pub fn defaults() -> Self {
+        Self { global: default_patterns(), slots: HashMap::new(), players: HashMap::new() }
+    }
+
+    // Most specific conflict-free binding set for this player
+    pub fn patterns_for(&self, entry_id: u32, color_id: u32) -> Vec<CommandPattern> {
+        let slot = self.slots.get(&color_id);
+        let player = self.players.get(&entry_id);
+
+        let mut candidates = Vec::new();
+        if let (Some(slot_over), Some(player_over)) = (slot, player) {
+            candidates.push(merge_overrides(&merge_overrides(&self.global, slot_over), player_over));
+        }
+        if let Some(player_over) = player {
+            candidates.push(merge_overrides(&self.global, player_over));
+        }
+        if let Some(slot_over) = slot {
+            candidates.push(merge_overrides(&self.global, slot_over));
+        }
+
+        candidates.into_iter()
+            .find(|patterns| find_conflicts(patterns).is_empty())
+            .unwrap_or_else(|| self.global.clone())
+    }
+}
+
+fn describe_conflicts(conflicts: &[(EvolutionCommand, EvolutionCommand)]) -> String {
+    conflicts.iter()
+        .map(|(a, b)| format!("{} / {}", command_key(*a), command_key(*b)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Build the table and collect every problem found (bad values, conflicts) for reporting
+pub fn build_binding_table(config: &IniDocument) -> (BindingTable, Vec<String>) {
+    let mut table = BindingTable::defaults();
+    let mut problems = Vec::new();
+
+    if let Some(section) = config.section("bindings") {
+        match parse_binding_section(section) {
+            Ok(overrides) => {
+                let merged = merge_overrides(&table.global, &overrides);
+                let conflicts = find_conflicts(&merged);
+                if conflicts.is_empty() {
+                    table.global = merged;
+                } else {
+                    problems.push(format!("[bindings] conflicts ({}), using defaults", describe_conflicts(&conflicts)));
+                }
+            }
+            Err(error) => problems.push(format!("[bindings] {}, using defaults", error)),
+        }
+    }
+
+    for (suffix, section) in config.sections_with_prefix("bindings") {
+        let (target, id) = if let Some(slot) = suffix.strip_prefix("slot") {
+            (&mut table.slots, slot.parse::<u32>().ok())
+        } else if let Some(port) = suffix.strip_prefix("player") {
+            // Ports are 1-based, entry ids 0-based
+            (&mut table.players, port.parse::<u32>().ok().filter(|port| *port >= 1).map(|port| port - 1))
+        } else {
+            problems.push(format!("[bindings.{}] unknown section (use slotN or playerN)", suffix));
+            continue;
+        };
+        let id = match id {
+            Some(id) => id,
+            None => {
+                problems.push(format!("[bindings.{}] bad slot/player number", suffix));
+                continue;
+            }
+        };
+
+        match parse_binding_section(section) {
+            Ok(overrides) => {
+                let conflicts = find_conflicts(&merge_overrides(&table.global, &overrides));
+                if conflicts.is_empty() {
+                    target.insert(id, overrides);
+                } else {
+                    problems.push(format!("[bindings.{}] conflicts ({}), ignored", suffix, describe_conflicts(&conflicts)));
+                }
+            }
+            Err(error) => problems.push(format!("[bindings.{}] {}, ignored", suffix, error)),
+        }
+    }
+
+    // Slot + player combinations are only resolved per match; report the ones that clash now
+    for (color_id, slot_over) in table.slots.iter() {
+        for (entry_id, player_over) in table.players.iter() {
+            let conflicts = find_conflicts(&merge_overrides(&merge_overrides(&table.global, slot_over), player_over));
+            if !conflicts.is_empty() {
+                problems.push(format!(
+                    "[bindings.player{}] on slot {} conflicts ({}), player bindings used alone",
+                    entry_id + 1, color_id, describe_conflicts(&conflicts)
+                ));
+            }
+        }
+    }
+
+    (table, problems)
+}
+
+static BINDING_TABLE: Lazy<RwLock<BindingTable>> = Lazy::new(|| RwLock::new(BindingTable::defaults()));
+
+pub fn load_command_bindings() {
+    let config = crate::gastly::config::GASTLY_CONFIG.read();
+    let (table, problems) = build_binding_table(&config);
+    *BINDING_TABLE.write() = table;
+    crate::gastly::config::write_report(BINDINGS_REPORT_PATH, &problems);
+}
+
+pub fn patterns_for(entry_id: u32, color_id: u32) -> Vec<CommandPattern> {
+    BINDING_TABLE.read().patterns_for(entry_id, color_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gastly::config::parse_ini;
+
+    fn problems(text: &str) -> Vec<String> {
+        build_binding_table(&parse_ini(text)).1
+    }
+
+    #[test]
+    fn default_bindings_have_no_conflicts() {
+        assert!(find_conflicts(&default_patterns()).is_empty());
+    }
+
+    #[test]
+    fn shared_prefix_conflicts_only_for_overlapping_commands() {
+        assert_eq!(problems("[bindings]\neverstone = up_taunt, up_taunt\n").len(), 1);
+        // Gengarite is only armed as Gengar, when Everstone isn't
+        assert!(problems("[bindings]\ngengarite = special, special\n").is_empty());
+    }
+
+    #[test]
+    fn cancel_evolution_cant_start_with_the_everstone_button() {
+        let (table, problems) = build_binding_table(&parse_ini("[bindings]\ncancel_evolution = special, down_taunt\n"));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(table.global, default_patterns());
+        assert!(build_binding_table(&parse_ini("[bindings]\ncancel_evolution = down_taunt, special\n")).1.is_empty());
+    }
+}
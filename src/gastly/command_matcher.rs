@@ -0,0 +1,287 @@
+// src/gastly/command_matcher.rs
+
+// Button-sequence matching for the evolution commands, free of game dependencies.
+//
+// Each command is a CommandPattern: the buttons to press in order, the guard/situation the presses
+// must happen in, and the frame window allowed between presses. InputSequenceMatcher is fed one
+// InputFrame per game frame and reports Started / Matched / Expired events. The stock patterns and
+// the pad sampling are in input_sequence.rs.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommandButton {
+    Special,
+    AppealHi,
+    AppealLw,
+    AppealSL,
+    AppealSR,
+}
+
+impl CommandButton {
+    // Sampling priority when several buttons are pressed on the same frame
+    pub const ALL: [CommandButton; 5] = [
+        CommandButton::AppealSL,
+        CommandButton::AppealSR,
+        CommandButton::AppealHi,
+        CommandButton::Special,
+        CommandButton::AppealLw,
+    ];
+
+    pub fn bit(self) -> u8 {
+        match self {
+            CommandButton::Special => 1 << 0,
+            CommandButton::AppealHi => 1 << 1,
+            CommandButton::AppealLw => 1 << 2,
+            CommandButton::AppealSL => 1 << 3,
+            CommandButton::AppealSR => 1 << 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvolutionCommand {
+    LinkingCord,     // Manual Haunter -> Gengar
+    Everstone,       // Toggle evolution lock
+    CancelEvolution,
+    Gengarite,       // Mega Gengar final smash
+    Dynamax,         // Gigantamax Gengar final smash
+}
+
+impl EvolutionCommand {
+    pub const ALL: [EvolutionCommand; 5] = [
+        EvolutionCommand::LinkingCord,
+        EvolutionCommand::Everstone,
+        EvolutionCommand::CancelEvolution,
+        EvolutionCommand::Gengarite,
+        EvolutionCommand::Dynamax,
+    ];
+}
+
+// Where the presses of a pattern count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputContext {
+    GuardOnGround,
+    GuardOnGroundOrAir, // Guarding on the ground, or anything in the air
+    Anywhere,
+}
+
+impl InputContext {
+    pub fn allows(self, input: &InputFrame) -> bool {
+        match self {
+            InputContext::GuardOnGround => input.guarding && input.on_ground,
+            InputContext::GuardOnGroundOrAir => (input.guarding && input.on_ground) || !input.on_ground,
+            InputContext::Anywhere => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandPattern {
+    pub command: EvolutionCommand,
+    pub buttons: Vec<CommandButton>,
+    pub context: InputContext,
+    pub window: i32, // Max frames between consecutive presses
+}
+
+impl CommandPattern {
+    pub fn new(command: EvolutionCommand, buttons: &[CommandButton], context: InputContext, window: i32) -> Self {
+        Self { command, buttons: buttons.to_vec(), context, window }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct InputFrame {
+    pub frame: i32,
+    pub pressed: Option<CommandButton>, // Button that triggered this frame
+    pub guarding: bool,
+    pub on_ground: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandEvent {
+    Started(EvolutionCommand), // First press of a multi-press command
+    Matched(EvolutionCommand),
+    Expired(EvolutionCommand), // A started command timed out
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct SequenceProgress {
+    step: usize,
+    last_frame: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct InputSequenceMatcher {
+    patterns: Vec<CommandPattern>,
+    progress: Vec<SequenceProgress>,
+}
+
+impl InputSequenceMatcher {
+    pub fn new(patterns: Vec<CommandPattern>) -> Self {
+        let progress = vec![SequenceProgress::default(); patterns.len()];
+        Self { patterns, progress }
+    }
+
+    pub fn patterns(&self) -> &[CommandPattern] {
+        &self.patterns
+    }
+
+    pub fn pattern(&self, command: EvolutionCommand) -> Option<&CommandPattern> {
+        self.patterns.iter().find(|pattern| pattern.command == command)
+    }
+
+    // Presses matched so far for a command (0 = idle)
+    pub fn step(&self, command: EvolutionCommand) -> usize {
+        self.patterns.iter()
+            .position(|pattern| pattern.command == command)
+            .map_or(0, |index| self.progress[index].step)
+    }
+
+    pub fn reset(&mut self) {
+        for progress in self.progress.iter_mut() {
+            *progress = SequenceProgress::default();
+        }
+    }
+
+    pub fn reset_command(&mut self, command: EvolutionCommand) {
+        for (pattern, progress) in self.patterns.iter().zip(self.progress.iter_mut()) {
+            if pattern.command == command {
+                *progress = SequenceProgress::default();
+            }
+        }
+    }
+
+    // Whether feeding this press would advance some armed command
+    pub fn accepts(&self, input: &InputFrame, armed: impl Fn(EvolutionCommand) -> bool) -> bool {
+        let button = match input.pressed {
+            Some(button) => button,
+            None => return false,
+        };
+        self.patterns.iter().zip(self.progress.iter()).any(|(pattern, progress)| {
+            !pattern.buttons.is_empty()
+                && armed(pattern.command)
+                && pattern.context.allows(input)
+                && (button == pattern.buttons[progress.step] || button == pattern.buttons[0])
+        })
+    }
+
+    // Advance every pattern by one frame. `armed` says whether a command can currently be entered
+    // (stage, evolving...); presses for unarmed commands or outside the pattern's context are ignored
+    // without resetting progress, but progress still times out.
+    pub fn feed(&mut self, input: &InputFrame, armed: impl Fn(EvolutionCommand) -> bool) -> Vec<CommandEvent> {
+        let mut events = Vec::new();
+
+        for (pattern, progress) in self.patterns.iter().zip(self.progress.iter_mut()) {
+            if pattern.buttons.is_empty() {
+                continue;
+            }
+
+            if progress.step > 0 && input.frame - progress.last_frame > pattern.window {
+                *progress = SequenceProgress::default();
+                events.push(CommandEvent::Expired(pattern.command));
+            }
+
+            let button = match input.pressed {
+                Some(button) => button,
+                None => continue,
+            };
+            if !armed(pattern.command) || !pattern.context.allows(input) {
+                continue;
+            }
+
+            if button == pattern.buttons[progress.step] {
+                progress.step += 1;
+            } else if button == pattern.buttons[0] {
+                progress.step = 1; // Wrong next button, but it starts the sequence over
+            } else {
+                continue;
+            }
+            progress.last_frame = input.frame;
+
+            if progress.step >= pattern.buttons.len() {
+                *progress = SequenceProgress::default();
+                events.push(CommandEvent::Matched(pattern.command));
+            } else if progress.step == 1 {
+                events.push(CommandEvent::Started(pattern.command));
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CommandButton::*;
+
+    fn matcher() -> InputSequenceMatcher {
+        InputSequenceMatcher::new(vec![
+            CommandPattern::new(EvolutionCommand::Everstone, &[Special, Special], InputContext::GuardOnGround, 30),
+            CommandPattern::new(EvolutionCommand::CancelEvolution, &[AppealLw, AppealLw], InputContext::GuardOnGroundOrAir, 60),
+        ])
+    }
+
+    fn shield(frame: i32, pressed: Option<CommandButton>) -> InputFrame {
+        InputFrame { frame, pressed, guarding: true, on_ground: true }
+    }
+
+    fn idle(frame: i32, pressed: Option<CommandButton>) -> InputFrame {
+        InputFrame { frame, pressed, guarding: false, on_ground: true }
+    }
+
+    // Feeds one InputFrame per entry and collects every event
+    fn run(matcher: &mut InputSequenceMatcher, trace: &[InputFrame], armed: impl Fn(EvolutionCommand) -> bool + Copy) -> Vec<CommandEvent> {
+        trace.iter().flat_map(|input| matcher.feed(input, armed)).collect()
+    }
+
+    #[test]
+    fn double_press_inside_window_matches() {
+        let mut matcher = matcher();
+        let events = run(&mut matcher, &[shield(1, Some(Special)), shield(2, None), shield(20, Some(Special))], |_| true);
+        assert_eq!(events, vec![
+            CommandEvent::Started(EvolutionCommand::Everstone),
+            CommandEvent::Matched(EvolutionCommand::Everstone),
+        ]);
+        assert_eq!(matcher.step(EvolutionCommand::Everstone), 0);
+    }
+
+    #[test]
+    fn second_press_after_window_expires_and_restarts() {
+        let mut matcher = matcher();
+        let events = run(&mut matcher, &[shield(1, Some(Special)), shield(40, Some(Special))], |_| true);
+        assert_eq!(events, vec![
+            CommandEvent::Started(EvolutionCommand::Everstone),
+            CommandEvent::Expired(EvolutionCommand::Everstone),
+            CommandEvent::Started(EvolutionCommand::Everstone),
+        ]);
+        assert_eq!(matcher.step(EvolutionCommand::Everstone), 1);
+    }
+
+    #[test]
+    fn presses_outside_context_are_ignored() {
+        let mut matcher = matcher();
+        let events = run(&mut matcher, &[idle(1, Some(Special)), idle(5, Some(Special))], |_| true);
+        assert!(events.is_empty());
+
+        // Down taunt also counts in the air
+        let air = |frame| InputFrame { frame, pressed: Some(AppealLw), guarding: false, on_ground: false };
+        let events = run(&mut matcher, &[air(1), air(10)], |_| true);
+        assert_eq!(events.last(), Some(&CommandEvent::Matched(EvolutionCommand::CancelEvolution)));
+    }
+
+    #[test]
+    fn unarmed_commands_ignore_presses() {
+        let mut matcher = matcher();
+        let armed = |command| command != EvolutionCommand::Everstone;
+        let events = run(&mut matcher, &[shield(1, Some(Special)), shield(2, Some(Special))], armed);
+        assert!(events.is_empty());
+        assert!(!matcher.accepts(&shield(3, Some(Special)), armed));
+    }
+
+    #[test]
+    fn wrong_button_keeps_sequence_and_first_button_restarts() {
+        let mut matcher = matcher();
+        let events = run(&mut matcher, &[shield(1, Some(Special)), shield(3, Some(AppealHi)), shield(5, Some(Special))], |_| true);
+        assert_eq!(events.last(), Some(&CommandEvent::Matched(EvolutionCommand::Everstone)));
+    }
+}
@@ -0,0 +1,193 @@
+// src/gastly/config.rs
+
+// Tiny INI-style config reader shared by the data-driven subsystems.
+//
+// Format:
+//   # comment / ; comment
+//   [section.name]
+//   key = value
+//
+// Parsing is pure (no smash/skyline calls) so it can be exercised on any host.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+pub const GASTLY_CONFIG_PATH: &str = "sd:/ultimate/gastly/config.ini";
+
+#[derive(Debug, Clone, Default)]
+pub struct IniSection {
+    pub name: String,
+    pub entries: Vec<(String, String)>,
+}
+
+impl IniSection {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter()
+            .rev() // Last assignment wins
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn get_f32(&self, key: &str) -> Option<f32> {
+        self.get(key).and_then(|v| v.parse::<f32>().ok())
+    }
+
+    pub fn get_i32(&self, key: &str) -> Option<i32> {
+        self.get(key).and_then(parse_int)
+    }
+
+    pub fn get_u32(&self, key: &str) -> Option<u32> {
+        self.get_i32(key).filter(|v| *v >= 0).map(|v| v as u32)
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(|v| match v.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => Some(true),
+            "0" | "false" | "no" | "off" => Some(false),
+            _ => None,
+        })
+    }
+
+    // Comma separated floats, e.g. "0.2, 0.05, 0.3"
+    pub fn get_f32_list(&self, key: &str) -> Option<Vec<f32>> {
+        let value = self.get(key)?;
+        let parsed: Result<Vec<f32>, _> = value.split(',').map(|v| v.trim().parse::<f32>()).collect();
+        parsed.ok()
+    }
+
+    // Comma separated words, lowercased and trimmed
+    pub fn get_list(&self, key: &str) -> Option<Vec<String>> {
+        self.get(key).map(|v| {
+            v.split(',')
+                .map(|item| item.trim().to_ascii_lowercase())
+                .filter(|item| !item.is_empty())
+                .collect()
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IniDocument {
+    pub sections: Vec<IniSection>,
+}
+
+impl IniDocument {
+    pub fn section(&self, name: &str) -> Option<&IniSection> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    // All sections named "<prefix>.<something>", returned with the suffix
+    pub fn sections_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a str, &'a IniSection)> + 'a {
+        self.sections.iter().filter_map(move |s| {
+            s.name.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .map(|suffix| (suffix, s))
+        })
+    }
+}
+
+// Accepts decimal and 0x-prefixed hex (status kinds are usually written in hex)
+pub fn parse_int(value: &str) -> Option<i32> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        i32::from_str_radix(hex, 16).ok()
+    } else {
+        value.parse::<i32>().ok()
+    }
+}
+
+// `#` / `;` start a comment at the start of a line or after whitespace, so `a#b` stays a value
+fn strip_comment(line: &str) -> &str {
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        if (c == '#' || c == ';') && previous.is_whitespace() {
+            return &line[..index];
+        }
+        previous = c;
+    }
+    line
+}
+
+pub fn parse_ini(text: &str) -> IniDocument {
+    let mut document = IniDocument::default();
+    // Keys before the first header go into an unnamed section
+    let mut current = IniSection::default();
+
+    for raw_line in text.lines() {
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            if !current.name.is_empty() || !current.entries.is_empty() {
+                document.sections.push(current);
+            }
+            current = IniSection {
+                name: line[1..line.len() - 1].trim().to_ascii_lowercase(),
+                entries: Vec::new(),
+            };
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            current.entries.push((key.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    if !current.name.is_empty() || !current.entries.is_empty() {
+        document.sections.push(current);
+    }
+
+    document
+}
+
+pub static GASTLY_CONFIG: Lazy<RwLock<IniDocument>> = Lazy::new(|| RwLock::new(IniDocument::default()));
+
+// Read the optional config file. A missing file leaves every subsystem on its built-in defaults.
+pub fn load_config() {
+    if let Ok(text) = std::fs::read_to_string(GASTLY_CONFIG_PATH) {
+        *GASTLY_CONFIG.write() = parse_ini(&text);
+    }
+}
+
+pub const REPORT_DIR: &str = "sd:/ultimate/gastly";
+
+// Load-time problems go to a report file next to config.ini; a clean load removes the old report
+pub fn write_report(path: &str, lines: &[String]) {
+    if lines.is_empty() {
+        let _ = std::fs::remove_file(path);
+        return;
+    }
+    let _ = std::fs::create_dir_all(REPORT_DIR);
+    let _ = std::fs::write(path, lines.join("\n") + "\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailing_comments_are_not_part_of_the_value() {
+        let document = parse_ini(
+            "[dark_move.down_air]  # lick\n\
+             stages = gastly, haunter, gengar   # let Gengar's down air lick too\n\
+             flash = simple ; none | simple | enhanced\n\
+             # full line\n\
+             ; full line\n\
+             name = a#b\n",
+        );
+        let section = document.section("dark_move.down_air").expect("section header with a comment");
+        assert_eq!(section.get("stages"), Some("gastly, haunter, gengar"));
+        assert_eq!(section.get("flash"), Some("simple"));
+        assert_eq!(section.get("name"), Some("a#b"));
+        assert_eq!(section.entries.len(), 3);
+    }
+
+    #[test]
+    fn float_lists_parse_with_a_trailing_comment() {
+        let document = parse_ini("[palette.shadow]\naura_color = 0.35, 0.05, 0.45         # Gastly aura\n");
+        let section = document.section("palette.shadow").unwrap();
+        assert_eq!(section.get_f32_list("aura_color"), Some(vec![0.35, 0.05, 0.45]));
+    }
+}
@@ -13,7 +13,7 @@ pub const FIGHTER_PURIN_INSTANCE_WORK_ID_FLAG_HAUNTER_CUTIN_READY: i32 = 0x20000
 pub const FIGHTER_PURIN_INSTANCE_WORK_ID_FLAG_GENGAR_CUTIN_READY: i32 = 0x200000F1;
 
 // CUTIN SYSTEM TOGGLE
-pub const ENABLE_EVOLUTION_CUTINS: bool = false;  // Set to true to enable cutins
+pub const ENABLE_EVOLUTION_CUTINS: bool = false;  // Screen-effect cutins (cutins.rs); off until the gastly_cutin_* effects ship
 
 
 // Gastly evolving meshes
@@ -75,6 +75,8 @@ pub static EVERSTONE_X_ICON: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("
 pub static GENGARITE_ICON: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengarite") });
 pub static DYNAMAX_ICON: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("dynamax") });
 pub static BILLBOARD_BONE: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("billboard") }); 
+pub static GHOST_BONE: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("ghost") }); // Joint the down-taunt ghost is skinned to
+pub static TOP_BONE: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("top") });
 
 // --- Evolution Readiness Indicator Icons ---
 pub static STG1_DMG_T_ICON: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("stg1_dmg_t") });
@@ -95,6 +97,9 @@ pub static GASTLY_EYE_CAPTURE: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40
 pub static GASTLY_EYE_OUCH: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gastly_eye_ouch") });
 pub static GASTLY_EYE_DOWN: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gastly_eye_down") });
 pub static GASTLY_EYE_HEAVYATTACK: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gastly_eye_heavyattack") });
+pub static GASTLY_EYE_SQUINT: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gastly_eye_squint") });
+pub static GASTLY_EYE_LOOK_F: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gastly_eye_look_f") });
+pub static GASTLY_EYE_LOOK_B: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gastly_eye_look_b") });
 
 pub static PURIN_VANILLA_EYE_N: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("purin_eyen") });
 pub static PURIN_VANILLA_EYE_BLINK: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("purin_eye_blink") });
@@ -116,10 +121,18 @@ pub static HAUNTER_EYE_CAPTURE: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash4
 pub static HAUNTER_EYE_OUCH: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("haunter_eye_ouch") });
 pub static HAUNTER_EYE_DOWN: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("haunter_eye_down") });
 pub static HAUNTER_EYE_HEAVYATTACK: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("haunter_eye_heavyattack") });
+pub static HAUNTER_EYE_SQUINT: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("haunter_eye_squint") });
+pub static HAUNTER_EYE_LOOK_F: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("haunter_eye_look_f") });
+pub static HAUNTER_EYE_LOOK_B: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("haunter_eye_look_b") });
 
 pub static GENGAR_BODY: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_body") });
 pub static GENGAR_TONGUE_LONG: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_tongue_long") });
 pub static GENGAR_TONGUE_NORMAL: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_tongue_normal") });
+// Facing-left (mirrored) tongue copies, see tongue.rs
+pub static GASTLY_TONGUE_L: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gastly_tongue_l") });
+pub static GENGAR_TONGUE_LONG_L: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_tongue_long_l") });
+pub static GENGAR_TONGUE_NORMAL_L: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_tongue_normal_l") });
+pub static MIRRORED_TONGUES: Lazy<[Hash40; 3]> = Lazy::new(|| [ *GASTLY_TONGUE_L, *GENGAR_TONGUE_LONG_L, *GENGAR_TONGUE_NORMAL_L ]);
 pub static GENGAR_IRIS: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_eyes") });
 pub static GENGAR_EYE_N: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_eyen") });
 pub static GENGAR_EYE_BLINK: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_eye_blink") });
@@ -129,13 +142,16 @@ pub static GENGAR_EYE_CAPTURE: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40
 pub static GENGAR_EYE_OUCH: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_eye_ouch") });
 pub static GENGAR_EYE_DOWN: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_eye_down") });
 pub static GENGAR_EYE_HEAVYATTACK: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_eye_heavyattack") });
+pub static GENGAR_EYE_SQUINT: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_eye_squint") });
+pub static GENGAR_EYE_LOOK_F: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_eye_look_f") });
+pub static GENGAR_EYE_LOOK_B: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("gengar_eye_look_b") });
 
 pub static MEGA_GENGAR_BODY: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("mega_gengar") });
 pub static GIGA_GENGAR_BODY: Lazy<Hash40> = Lazy::new(|| Hash40 { hash: hash40("giga_gengar") });
 
-pub static GASTLY_EYE_EXPRESSIONS: Lazy<[Hash40; 8]> = Lazy::new(|| [ *GASTLY_EYE_N, *GASTLY_EYE_BLINK, *GASTLY_EYE_HALFBLINK1, *GASTLY_EYE_ATTACK, *GASTLY_EYE_CAPTURE, *GASTLY_EYE_OUCH, *GASTLY_EYE_DOWN, *GASTLY_EYE_HEAVYATTACK ]);
-pub static HAUNTER_EYELID_EXPRESSIONS: Lazy<[Hash40; 8]> = Lazy::new(|| [ *HAUNTER_EYE_N, *HAUNTER_EYE_BLINK, *HAUNTER_EYE_HALFBLINK1, *HAUNTER_EYE_ATTACK, *HAUNTER_EYE_CAPTURE, *HAUNTER_EYE_OUCH, *HAUNTER_EYE_DOWN, *HAUNTER_EYE_HEAVYATTACK ]);
-pub static GENGAR_EYELID_EXPRESSIONS: Lazy<[Hash40; 8]> = Lazy::new(|| [ *GENGAR_EYE_N, *GENGAR_EYE_BLINK, *GENGAR_EYE_HALFBLINK1, *GENGAR_EYE_ATTACK, *GENGAR_EYE_CAPTURE, *GENGAR_EYE_OUCH, *GENGAR_EYE_DOWN, *GENGAR_EYE_HEAVYATTACK ]);
+pub static GASTLY_EYE_EXPRESSIONS: Lazy<[Hash40; 11]> = Lazy::new(|| [ *GASTLY_EYE_N, *GASTLY_EYE_BLINK, *GASTLY_EYE_HALFBLINK1, *GASTLY_EYE_ATTACK, *GASTLY_EYE_CAPTURE, *GASTLY_EYE_OUCH, *GASTLY_EYE_DOWN, *GASTLY_EYE_HEAVYATTACK, *GASTLY_EYE_SQUINT, *GASTLY_EYE_LOOK_F, *GASTLY_EYE_LOOK_B ]);
+pub static HAUNTER_EYELID_EXPRESSIONS: Lazy<[Hash40; 11]> = Lazy::new(|| [ *HAUNTER_EYE_N, *HAUNTER_EYE_BLINK, *HAUNTER_EYE_HALFBLINK1, *HAUNTER_EYE_ATTACK, *HAUNTER_EYE_CAPTURE, *HAUNTER_EYE_OUCH, *HAUNTER_EYE_DOWN, *HAUNTER_EYE_HEAVYATTACK, *HAUNTER_EYE_SQUINT, *HAUNTER_EYE_LOOK_F, *HAUNTER_EYE_LOOK_B ]);
+pub static GENGAR_EYELID_EXPRESSIONS: Lazy<[Hash40; 11]> = Lazy::new(|| [ *GENGAR_EYE_N, *GENGAR_EYE_BLINK, *GENGAR_EYE_HALFBLINK1, *GENGAR_EYE_ATTACK, *GENGAR_EYE_CAPTURE, *GENGAR_EYE_OUCH, *GENGAR_EYE_DOWN, *GENGAR_EYE_HEAVYATTACK, *GENGAR_EYE_SQUINT, *GENGAR_EYE_LOOK_F, *GENGAR_EYE_LOOK_B ]);
 pub static PURIN_VANILLA_EYES_TO_HIDE: Lazy<[Hash40; 8]> = Lazy::new(|| [ *PURIN_VANILLA_EYE_N, *PURIN_VANILLA_EYE_BLINK, *PURIN_VANILLA_EYE_HALFBLINK1, *PURIN_VANILLA_EYE_ATTACK, *PURIN_VANILLA_EYE_CAPTURE, *PURIN_VANILLA_EYE_OUCH, *PURIN_VANILLA_EYE_DOWN, *PURIN_VANILLA_EYE_HEAVYATTACK ]);
 
 // Shadow Floor meshes (squat_wait animation)
@@ -208,7 +224,6 @@ pub const HAUNTER_EVO_HITS_THRESHOLD: i32 = 15; // 15 hits to evolve Haunter →
 
 pub const EVOLUTION_ANIMATION_TOTAL_FRAMES: i32 = 240;
 pub const EVO_CANCEL_PENALTY_PERCENT: f32 = 50.0; 
-pub const MAX_TAUNT_SEQUENCE_LEN: usize = 2;
 pub const DEBUG_TAUNT_COOLDOWN: i32 = 30;
 pub const DOUBLE_PRESS_WINDOW: i32 = 30; 
 pub const ICON_ANIMATION_DURATION: i32 = 40; 
@@ -0,0 +1,155 @@
+// src/gastly/cutins.rs
+
+// Battle cutins drawn as screen effects (EffectModule::req_screen) instead of swapping the entry's
+// chara_6 UI through The CSK Collection (which only ever showed a black portrait).
+//
+// Cutins are queued: only one plays at a time, so two players evolving on the same frame see their
+// cutins back to back instead of on top of each other. A player can't queue the same cutin twice.
+// The effects (CutinKind::effect_name) belong in the mod's ef_purin.eff but aren't authored yet,
+// so ENABLE_EVOLUTION_CUTINS stays off until they ship.
+
+use std::collections::VecDeque;
+use parking_lot::Mutex;
+use smash::app::lua_bind::EffectModule;
+use smash::phx::Hash40;
+
+pub const CUTIN_DURATION_FRAMES: u32 = 70;
+pub const CUTIN_GAP_FRAMES: u32 = 10;      // Pause between queued cutins
+pub const CUTIN_MAX_QUEUE_DELAY: u32 = 240; // Drop cutins that waited longer than this (moment has passed)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CutinKind {
+    Haunter,
+    Gengar,
+    MegaGengar,
+    GigaGengar,
+}
+
+impl CutinKind {
+    pub fn effect_name(self) -> &'static str {
+        match self {
+            CutinKind::Haunter => "gastly_cutin_haunter",
+            CutinKind::Gengar => "gastly_cutin_gengar",
+            CutinKind::MegaGengar => "gastly_cutin_mega_gengar",
+            CutinKind::GigaGengar => "gastly_cutin_giga_gengar",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CutinRequest {
+    pub entry_id: u32,
+    pub kind: CutinKind,
+    pub queued_frame: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActiveCutin {
+    pub request: CutinRequest,
+    pub started_frame: u32,
+    pub effect_handle: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CutinEvent {
+    Start(CutinRequest),
+    Finish(ActiveCutin),
+}
+
+pub struct CutinQueue {
+    pending: VecDeque<CutinRequest>,
+    active: Option<ActiveCutin>,
+    last_finish_frame: Option<u32>,
+    last_tick_frame: Option<u32>,
+}
+
+impl CutinQueue {
+    pub const fn new() -> Self {
+        Self { pending: VecDeque::new(), active: None, last_finish_frame: None, last_tick_frame: None }
+    }
+
+    // Returns false if this player already has this cutin queued or playing
+    pub fn enqueue(&mut self, entry_id: u32, kind: CutinKind, frame: u32) -> bool {
+        let same = |request: &CutinRequest| request.entry_id == entry_id && request.kind == kind;
+        if self.pending.iter().any(same) || self.active.map_or(false, |active| same(&active.request)) {
+            return false;
+        }
+        self.pending.push_back(CutinRequest { entry_id, kind, queued_frame: frame });
+        true
+    }
+
+    // Advance once per frame (repeat calls on the same frame do nothing). The caller draws/clears the effects.
+    pub fn tick(&mut self, frame: u32) -> Vec<CutinEvent> {
+        let mut events = Vec::new();
+        if self.last_tick_frame == Some(frame) {
+            return events;
+        }
+        self.last_tick_frame = Some(frame);
+
+        if let Some(active) = self.active {
+            if frame.wrapping_sub(active.started_frame) < CUTIN_DURATION_FRAMES {
+                return events;
+            }
+            self.active = None;
+            self.last_finish_frame = Some(frame);
+            events.push(CutinEvent::Finish(active));
+        }
+
+        if self.last_finish_frame.map_or(false, |finished| frame.wrapping_sub(finished) < CUTIN_GAP_FRAMES) {
+            return events;
+        }
+
+        while let Some(request) = self.pending.pop_front() {
+            if frame.wrapping_sub(request.queued_frame) <= CUTIN_MAX_QUEUE_DELAY {
+                events.push(CutinEvent::Start(request));
+                break;
+            }
+        }
+        events
+    }
+
+    pub fn set_active(&mut self, request: CutinRequest, frame: u32, effect_handle: u32) {
+        self.active = Some(ActiveCutin { request, started_frame: frame, effect_handle });
+    }
+
+    // Drop everything queued by a player (death, reset); a playing cutin finishes normally
+    pub fn clear_entry(&mut self, entry_id: u32) {
+        self.pending.retain(|request| request.entry_id != entry_id);
+    }
+}
+
+static CUTIN_QUEUE: Mutex<CutinQueue> = parking_lot::const_mutex(CutinQueue::new());
+
+pub fn queue_cutin(entry_id: u32, kind: CutinKind) -> bool {
+    CUTIN_QUEUE.lock().enqueue(entry_id, kind, crate::gastly::hit_records::global_frame())
+}
+
+pub fn clear_cutins_for_entry(entry_id: u32) {
+    CUTIN_QUEUE.lock().clear_entry(entry_id);
+}
+
+// Start/stop cutin effects. Called every frame from UI management; only the first call per frame does work.
+pub unsafe fn update_cutins() {
+    let frame = crate::gastly::hit_records::global_frame();
+    let mut queue = CUTIN_QUEUE.lock();
+
+    for event in queue.tick(frame) {
+        match event {
+            CutinEvent::Finish(active) => {
+                let boma = smash::app::sv_battle_object::module_accessor(active.request.entry_id);
+                if !boma.is_null() && active.effect_handle != 0 {
+                    EffectModule::kill(boma, active.effect_handle, false, true);
+                }
+            }
+            CutinEvent::Start(request) => {
+                let boma = smash::app::sv_battle_object::module_accessor(request.entry_id);
+                if boma.is_null() {
+                    continue;
+                }
+                let handle = EffectModule::req_screen(boma, Hash40::new(request.kind.effect_name()), false, false, false) as u32;
+                let handle = if handle == u64::MAX as u32 { 0 } else { handle };
+                queue.set_active(request, frame, handle);
+            }
+        }
+    }
+}
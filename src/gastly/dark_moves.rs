@@ -0,0 +1,547 @@
+// src/gastly/dark_moves.rs
+
+// Data-driven table of Gastly's dark moves: which status/motion counts as a dark move,
+// which evolution stages may use it, what the victim gets (effect, flash, status effect)
+// and how long the per-victim cooldown is.
+//
+// Built-in defaults match the original hardcoded behaviour; any entry can be overridden
+// (or new ones added) from `[dark_move.<name>]` sections of the Gastly config file.
+// Everything here is plain data so it can be checked on the host.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+use crate::gastly::config::{IniDocument, IniSection};
+
+// Dark move type constants
+pub const DARK_MOVE_DOWN_TILT: u32 = 2;
+pub const DARK_MOVE_DOWN_SMASH: u32 = 7;
+pub const DARK_MOVE_NEUTRAL_AIR: u32 = 8;
+pub const DARK_MOVE_FORWARD_AIR: u32 = 9;
+pub const DARK_MOVE_DOWN_AIR: u32 = 10; //  Down Air with bomber sweat (Gastly/Haunter only)
+pub const DARK_MOVE_DOWN_SPECIAL: u32 = 12; // Down Special with poison effect
+pub const DARK_MOVE_NEUTRAL_SPECIAL_ROLLOUT: u32 = 13; // For rollout flash effect
+pub const DARK_MOVE_FORWARD_SPECIAL: u32 = 14;
+pub const DARK_MOVE_PUMMEL: u32 = 16; //  Pummel with bomber sweat (All stages)
+pub const DARK_MOVE_UP_SPECIAL_HYPNOSIS: u32 = 17; // Up Special (Hypnosis) - sleep / reversed controls
+
+// Every built-in move type - each must have a table entry with a flash profile
+pub const ALL_DARK_MOVES: [u32; 10] = [
+    DARK_MOVE_DOWN_TILT,
+    DARK_MOVE_DOWN_SMASH,
+    DARK_MOVE_NEUTRAL_AIR,
+    DARK_MOVE_FORWARD_AIR,
+    DARK_MOVE_DOWN_AIR,
+    DARK_MOVE_DOWN_SPECIAL,
+    DARK_MOVE_NEUTRAL_SPECIAL_ROLLOUT,
+    DARK_MOVE_FORWARD_SPECIAL,
+    DARK_MOVE_PUMMEL,
+    DARK_MOVE_UP_SPECIAL_HYPNOSIS,
+];
+
+// Config-added moves get ids from here upwards
+const CUSTOM_DARK_MOVE_BASE: u32 = 100;
+
+pub const DEFAULT_DARK_EFFECT_COOLDOWN_FRAMES: u32 = 30;
+
+// Status a dark move is performed in. Named variants are resolved to lua consts at runtime.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DarkMoveStatus {
+    AttackLw3,
+    AttackLw4,
+    AttackAir,
+    SpecialS,
+    SpecialHi,
+    SpecialLw,
+    CatchAttack,
+    Raw(i32), // Custom status kinds, e.g. 0x1E7 (SPECIAL_N_HIT_END)
+}
+
+impl DarkMoveStatus {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "attack_lw3" => Some(Self::AttackLw3),
+            "attack_lw4" => Some(Self::AttackLw4),
+            "attack_air" => Some(Self::AttackAir),
+            "special_s" => Some(Self::SpecialS),
+            "special_hi" => Some(Self::SpecialHi),
+            "special_lw" => Some(Self::SpecialLw),
+            "catch_attack" => Some(Self::CatchAttack),
+            other => crate::gastly::config::parse_int(other).map(Self::Raw),
+        }
+    }
+}
+
+// How the victim is tinted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlashKind {
+    None,           // No tint (lick/bomber sweat moves)
+    Simple,         // 3 blinks + fade
+    Enhanced(i32),  // 3 blinks + fade, restarted on status change until the duration elapses
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlashProfile {
+    pub kind: FlashKind,
+    pub color: [f32; 4], // r, g, b, a - FLASH macro backend
+    pub tint: [f32; 3],  // Model colour multiplier at full strength - material backend
+}
+
+impl FlashProfile {
+    pub const DARK_PURPLE: [f32; 4] = [0.2, 0.05, 0.3, 0.8];
+    pub const DARK_PURPLE_TINT: [f32; 3] = [0.7, 0.45, 0.85];
+
+    pub const fn none() -> Self {
+        Self { kind: FlashKind::None, color: Self::DARK_PURPLE, tint: Self::DARK_PURPLE_TINT }
+    }
+
+    pub const fn simple() -> Self {
+        Self { kind: FlashKind::Simple, color: Self::DARK_PURPLE, tint: Self::DARK_PURPLE_TINT }
+    }
+
+    pub const fn enhanced(duration: i32) -> Self {
+        Self { kind: FlashKind::Enhanced(duration), color: Self::DARK_PURPLE, tint: Self::DARK_PURPLE_TINT }
+    }
+}
+
+// How the dark-hit shade is drawn on the victim
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DarkTintBackend {
+    Flash,    // FLASH macro - gets more opaque as the camera zooms out (original behaviour)
+    Material, // Model colour multiply - same strength at any camera depth
+}
+
+impl DarkTintBackend {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "flash" => Some(Self::Flash),
+            "material" => Some(Self::Material),
+            _ => None,
+        }
+    }
+}
+
+pub const DEFAULT_DARK_TINT_BACKEND: DarkTintBackend = DarkTintBackend::Material;
+
+// What a Hypnosis hit does to the defender
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HypnosisMode {
+    Sleep,           // Vanilla sleep, cut short after the hypnosis duration
+    ReversedControl, // Wake immediately but with horizontal controls reversed
+}
+
+impl HypnosisMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "sleep" => Some(Self::Sleep),
+            "reversed" | "reversed_control" => Some(Self::ReversedControl),
+            _ => None,
+        }
+    }
+}
+
+pub const DEFAULT_HYPNOSIS_MODE: HypnosisMode = HypnosisMode::ReversedControl;
+
+// Lasting status applied to the victim on hit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DarkStatusEffect {
+    None,
+    Poison,
+    Hypnosis,
+}
+
+// Effect spawned on the victim
+#[derive(Debug, Clone, PartialEq)]
+pub struct DarkHitEffect {
+    pub name: String,
+    pub bone: String,
+    pub rot: [f32; 3],
+    pub scale: f32,
+    pub scale_by_model: bool,        // Multiply scale by the victim's model scale
+    pub scale_xyz: Option<[f32; 3]>, // Per-axis scale applied after spawning
+    pub rgb: Option<[f32; 3]>,
+    pub rate: f32,
+}
+
+impl DarkHitEffect {
+    fn darkness(rate: f32) -> Self {
+        Self {
+            name: "ganon_attack_purple".to_string(),
+            bone: "hip".to_string(),
+            rot: [0.0, 0.0, 0.0],
+            scale: 1.75,
+            scale_by_model: true,
+            scale_xyz: None,
+            rgb: Some([2.0, 1.0, 1.0]),
+            rate,
+        }
+    }
+
+    fn poison() -> Self {
+        Self {
+            name: "sys_hit_poison".to_string(),
+            rate: 0.25,
+            ..Self::darkness(0.25)
+        }
+    }
+
+    fn bomber_sweat() -> Self {
+        Self {
+            name: "sys_bomber_sweat".to_string(),
+            bone: "hip".to_string(),
+            rot: [0.0, 90.0, 0.0], // Emit upward
+            scale: 1.0,
+            scale_by_model: false,
+            scale_xyz: Some([1.0, 2.0, 1.0]),
+            rgb: None,
+            rate: 1.5, // 1.5x speed = shorter duration
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DarkMoveEntry {
+    pub move_type: u32,
+    pub name: String,
+    pub enabled: bool,
+    pub statuses: Vec<DarkMoveStatus>, // Any of these statuses
+    pub motion: Option<u64>,         // Required motion hash (None = any motion in the status)
+    pub allowed_stages: [bool; 3],   // Gastly, Haunter, Gengar
+    pub priority: u8,                // Highest priority attacker decides the victim's effect
+    pub effect: Option<DarkHitEffect>,
+    pub flash: FlashProfile,
+    pub status_effect: DarkStatusEffect,
+    pub cooldown_frames: u32,
+}
+
+impl DarkMoveEntry {
+    fn new(move_type: u32, name: &str, status: DarkMoveStatus, motion: Option<&str>) -> Self {
+        Self {
+            move_type,
+            name: name.to_string(),
+            enabled: true,
+            statuses: vec![status],
+            motion: motion.map(smash_hash40),
+            allowed_stages: [true; 3],
+            priority: 10,
+            effect: Some(DarkHitEffect::darkness(0.3)),
+            flash: FlashProfile::simple(),
+            status_effect: DarkStatusEffect::None,
+            cooldown_frames: DEFAULT_DARK_EFFECT_COOLDOWN_FRAMES,
+        }
+    }
+
+    pub fn allows_stage(&self, evolution_stage: u32) -> bool {
+        self.enabled && (evolution_stage as usize) < 3 && self.allowed_stages[evolution_stage as usize]
+    }
+}
+
+fn smash_hash40(name: &str) -> u64 {
+    smash::hash40(name)
+}
+
+// Original hardcoded behaviour.
+// Priority order: Down Special > Down Air > Neutral Special Rollout > Pummel > Regular Dark Moves
+pub fn default_dark_move_table() -> Vec<DarkMoveEntry> {
+    vec![
+        DarkMoveEntry::new(DARK_MOVE_DOWN_TILT, "down_tilt", DarkMoveStatus::AttackLw3, None),
+        DarkMoveEntry::new(DARK_MOVE_DOWN_SMASH, "down_smash", DarkMoveStatus::AttackLw4, None),
+        DarkMoveEntry::new(DARK_MOVE_NEUTRAL_AIR, "neutral_air", DarkMoveStatus::AttackAir, Some("attack_air_n")),
+        DarkMoveEntry::new(DARK_MOVE_FORWARD_AIR, "forward_air", DarkMoveStatus::AttackAir, Some("attack_air_f")),
+        DarkMoveEntry {
+            allowed_stages: [true, true, false], // Gengar omitted from down air effect
+            priority: 40,
+            effect: Some(DarkHitEffect::bomber_sweat()),
+            flash: FlashProfile::none(),
+            ..DarkMoveEntry::new(DARK_MOVE_DOWN_AIR, "down_air", DarkMoveStatus::AttackAir, Some("attack_air_lw"))
+        },
+        DarkMoveEntry::new(DARK_MOVE_FORWARD_SPECIAL, "forward_special", DarkMoveStatus::SpecialS, None),
+        DarkMoveEntry {
+            priority: 50,
+            effect: Some(DarkHitEffect::poison()),
+            flash: FlashProfile::enhanced(120), // 3 blinks + fade, held for 120 frames
+            status_effect: DarkStatusEffect::Poison,
+            ..DarkMoveEntry::new(DARK_MOVE_DOWN_SPECIAL, "down_special", DarkMoveStatus::SpecialLw, None)
+        },
+        DarkMoveEntry {
+            // Hits land while rolling; 0x1E7 (SPECIAL_N_HIT_END) follows the bomb
+            statuses: vec![DarkMoveStatus::Raw(0x1E3), DarkMoveStatus::Raw(0x1E4), DarkMoveStatus::Raw(0x1E7)],
+            priority: 30,
+            ..DarkMoveEntry::new(DARK_MOVE_NEUTRAL_SPECIAL_ROLLOUT, "neutral_special_rollout", DarkMoveStatus::Raw(0x1E7), None)
+        },
+        DarkMoveEntry {
+            priority: 20,
+            effect: Some(DarkHitEffect::bomber_sweat()),
+            flash: FlashProfile::none(),
+            ..DarkMoveEntry::new(DARK_MOVE_PUMMEL, "pummel", DarkMoveStatus::CatchAttack, None)
+        },
+        DarkMoveEntry {
+            priority: 0, // Handled through sleep detection, never drives the hit effect
+            effect: None,
+            flash: FlashProfile::simple(),
+            status_effect: DarkStatusEffect::Hypnosis,
+            ..DarkMoveEntry::new(DARK_MOVE_UP_SPECIAL_HYPNOSIS, "hypnosis", DarkMoveStatus::SpecialHi, None)
+        },
+    ]
+}
+
+fn parse_stages(values: &[String]) -> [bool; 3] {
+    let mut stages = [false; 3];
+    for value in values {
+        match value.as_str() {
+            "gastly" | "0" => stages[0] = true,
+            "haunter" | "1" => stages[1] = true,
+            "gengar" | "2" => stages[2] = true,
+            "all" => stages = [true; 3],
+            _ => {}
+        }
+    }
+    stages
+}
+
+// `status = attack_air` or a comma separated list (`status = 0x1E3, 0x1E4`)
+fn parse_statuses(section: &IniSection) -> Option<Vec<DarkMoveStatus>> {
+    let statuses: Vec<DarkMoveStatus> = section.get_list("status")?
+        .iter()
+        .filter_map(|status| DarkMoveStatus::parse(status))
+        .collect();
+    if statuses.is_empty() { None } else { Some(statuses) }
+}
+
+fn apply_section_overrides(entry: &mut DarkMoveEntry, section: &IniSection) {
+    if let Some(enabled) = section.get_bool("enabled") {
+        entry.enabled = enabled;
+    }
+    if let Some(statuses) = parse_statuses(section) {
+        entry.statuses = statuses;
+    }
+    if let Some(motion) = section.get("motion") {
+        entry.motion = if motion.is_empty() || motion == "any" { None } else { Some(smash_hash40(motion)) };
+    }
+    if let Some(stages) = section.get_list("stages") {
+        entry.allowed_stages = parse_stages(&stages);
+    }
+    if let Some(priority) = section.get_u32("priority") {
+        entry.priority = priority.min(u8::MAX as u32) as u8;
+    }
+    if let Some(cooldown) = section.get_u32("cooldown") {
+        entry.cooldown_frames = cooldown;
+    }
+
+    // Hit effect
+    if let Some(effect_name) = section.get("effect") {
+        if effect_name.is_empty() || effect_name == "none" {
+            entry.effect = None;
+        } else {
+            let effect = entry.effect.get_or_insert_with(|| DarkHitEffect::darkness(0.3));
+            effect.name = effect_name.to_string();
+        }
+    }
+    if let Some(effect) = entry.effect.as_mut() {
+        if let Some(bone) = section.get("effect_bone") {
+            effect.bone = bone.to_string();
+        }
+        if let Some(rgb) = section.get_f32_list("effect_rgb").filter(|v| v.len() == 3) {
+            effect.rgb = Some([rgb[0], rgb[1], rgb[2]]);
+        }
+        if let Some(rate) = section.get_f32("effect_rate") {
+            effect.rate = rate;
+        }
+        if let Some(scale) = section.get_f32("effect_scale") {
+            effect.scale = scale;
+        }
+    }
+
+    // Flash
+    if let Some(kind) = section.get("flash") {
+        let duration = section.get_i32("flash_duration").unwrap_or(120);
+        entry.flash.kind = match kind.to_ascii_lowercase().as_str() {
+            "none" => FlashKind::None,
+            "enhanced" => FlashKind::Enhanced(duration),
+            _ => FlashKind::Simple,
+        };
+    }
+    if let Some(color) = section.get_f32_list("flash_color") {
+        if color.len() == 4 {
+            entry.flash.color = [color[0], color[1], color[2], color[3]];
+        } else if color.len() == 3 {
+            entry.flash.color = [color[0], color[1], color[2], entry.flash.color[3]];
+        }
+    }
+
+    if let Some(tint) = section.get_f32_list("tint_color").filter(|v| v.len() == 3) {
+        entry.flash.tint = [tint[0], tint[1], tint[2]];
+    }
+
+    if let Some(status_effect) = section.get("status_effect") {
+        entry.status_effect = match status_effect.to_ascii_lowercase().as_str() {
+            "poison" => DarkStatusEffect::Poison,
+            "hypnosis" => DarkStatusEffect::Hypnosis,
+            _ => DarkStatusEffect::None,
+        };
+    }
+}
+
+// Defaults, then `[dark_move.<name>]` overrides. Unknown names add new moves (a status is required).
+pub fn build_dark_move_table(config: &IniDocument) -> Vec<DarkMoveEntry> {
+    let mut table = default_dark_move_table();
+    let mut next_custom_id = CUSTOM_DARK_MOVE_BASE;
+
+    for (name, section) in config.sections_with_prefix("dark_move") {
+        if let Some(entry) = table.iter_mut().find(|entry| entry.name == name) {
+            apply_section_overrides(entry, section);
+        } else if let Some(statuses) = parse_statuses(section) {
+            let mut entry = DarkMoveEntry::new(next_custom_id, name, statuses[0], None);
+            next_custom_id += 1;
+            apply_section_overrides(&mut entry, section);
+            table.push(entry);
+        }
+    }
+
+    table
+}
+
+// Every built-in DARK_MOVE_* must have exactly one entry (disabled moves keep theirs),
+// and every entry must carry a flash profile with a usable colour/duration.
+pub fn validate_dark_move_table(table: &[DarkMoveEntry]) -> Result<(), String> {
+    for move_type in ALL_DARK_MOVES.iter() {
+        match table.iter().filter(|entry| entry.move_type == *move_type).count() {
+            0 => return Err(format!("dark move {} has no table entry / flash profile", move_type)),
+            1 => {}
+            _ => return Err(format!("dark move {} has more than one table entry", move_type)),
+        }
+    }
+
+    for entry in table.iter().filter(|entry| entry.enabled) {
+        if entry.flash.color.iter().any(|c| !c.is_finite() || *c < 0.0) {
+            return Err(format!("dark move '{}' has an invalid flash color", entry.name));
+        }
+        if entry.flash.tint.iter().any(|c| !c.is_finite() || *c < 0.0) {
+            return Err(format!("dark move '{}' has an invalid tint color", entry.name));
+        }
+        if let FlashKind::Enhanced(duration) = entry.flash.kind {
+            if duration <= 0 {
+                return Err(format!("dark move '{}' has a non-positive flash duration", entry.name));
+            }
+        }
+        if !entry.allowed_stages.iter().any(|allowed| *allowed) {
+            return Err(format!("dark move '{}' is not allowed for any stage", entry.name));
+        }
+    }
+
+    Ok(())
+}
+
+pub static DARK_MOVE_TABLE: Lazy<RwLock<Vec<DarkMoveEntry>>> = Lazy::new(|| RwLock::new(default_dark_move_table()));
+pub static DARK_TINT_BACKEND: RwLock<DarkTintBackend> = parking_lot::const_rwlock(DEFAULT_DARK_TINT_BACKEND);
+
+// `[dark_tint] backend = material | flash`
+pub fn dark_tint_backend_from_config(config: &IniDocument) -> DarkTintBackend {
+    config.section("dark_tint")
+        .and_then(|section| section.get("backend"))
+        .and_then(DarkTintBackend::parse)
+        .unwrap_or(DEFAULT_DARK_TINT_BACKEND)
+}
+
+pub static HYPNOSIS_MODE: RwLock<HypnosisMode> = parking_lot::const_rwlock(DEFAULT_HYPNOSIS_MODE);
+
+// `[hypnosis] mode = reversed | sleep`
+pub fn hypnosis_mode_from_config(config: &IniDocument) -> HypnosisMode {
+    config.section("hypnosis")
+        .and_then(|section| section.get("mode"))
+        .and_then(HypnosisMode::parse)
+        .unwrap_or(DEFAULT_HYPNOSIS_MODE)
+}
+
+// The merged table, or the defaults if the merge produced an invalid table
+pub fn dark_move_table_from_config(config: &IniDocument) -> Vec<DarkMoveEntry> {
+    let table = build_dark_move_table(config);
+    if validate_dark_move_table(&table).is_ok() { table } else { default_dark_move_table() }
+}
+
+// Rebuild the table from the loaded config
+pub fn load_dark_move_table() {
+    let config = crate::gastly::config::GASTLY_CONFIG.read();
+    *DARK_TINT_BACKEND.write() = dark_tint_backend_from_config(&config);
+    *HYPNOSIS_MODE.write() = hypnosis_mode_from_config(&config);
+    *DARK_MOVE_TABLE.write() = dark_move_table_from_config(&config);
+}
+
+pub fn dark_move_entry(move_type: u32) -> Option<DarkMoveEntry> {
+    DARK_MOVE_TABLE.read().iter().find(|entry| entry.move_type == move_type).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(table: &[DarkMoveEntry], move_type: u32) -> &DarkMoveEntry {
+        table.iter().find(|entry| entry.move_type == move_type).unwrap()
+    }
+
+    #[test]
+    fn override_changes_stages_and_cooldown() {
+        let config = crate::gastly::config::parse_ini(
+            "[dark_move.down_smash]\nstages = gengar\ncooldown = 45\npriority = 70\n",
+        );
+        let table = dark_move_table_from_config(&config);
+        let down_smash = entry(&table, DARK_MOVE_DOWN_SMASH);
+        assert_eq!(down_smash.allowed_stages, [false, false, true]);
+        assert_eq!(down_smash.cooldown_frames, 45);
+        assert_eq!(down_smash.priority, 70);
+        assert!(!down_smash.allows_stage(0));
+        assert!(down_smash.allows_stage(2));
+
+        // Untouched moves keep their defaults
+        let defaults = default_dark_move_table();
+        assert_eq!(entry(&table, DARK_MOVE_DOWN_TILT), entry(&defaults, DARK_MOVE_DOWN_TILT));
+    }
+
+    #[test]
+    fn unknown_section_with_a_status_adds_a_custom_move() {
+        let config = crate::gastly::config::parse_ini("[dark_move.up_tilt]\nstatus = 0x1E5\ncooldown = 10\n");
+        let table = dark_move_table_from_config(&config);
+        assert_eq!(table.len(), default_dark_move_table().len() + 1);
+        let up_tilt = entry(&table, CUSTOM_DARK_MOVE_BASE);
+        assert_eq!(up_tilt.statuses, vec![DarkMoveStatus::Raw(0x1E5)]);
+        assert_eq!(up_tilt.name, "up_tilt");
+        assert_eq!(up_tilt.cooldown_frames, 10);
+
+        let without_status = crate::gastly::config::parse_ini("[dark_move.up_tilt]\ncooldown = 10\n");
+        assert_eq!(dark_move_table_from_config(&without_status), default_dark_move_table());
+    }
+
+    #[test]
+    fn invalid_section_falls_back_to_defaults() {
+        for text in [
+            "[dark_move.down_smash]\nstages = mega_gengar\ncooldown = 45\n",
+            "[dark_move.down_special]\nflash = enhanced\nflash_duration = 0\n",
+            "[dark_move.pummel]\nflash_color = 1.0, -0.5, 1.0\n",
+        ] {
+            let config = crate::gastly::config::parse_ini(text);
+            assert!(validate_dark_move_table(&build_dark_move_table(&config)).is_err(), "{}", text);
+            assert_eq!(dark_move_table_from_config(&config), default_dark_move_table(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn hypnosis_mode_from_config() {
+        let mode = |text: &str| super::hypnosis_mode_from_config(&crate::gastly::config::parse_ini(text));
+        assert_eq!(mode(""), DEFAULT_HYPNOSIS_MODE);
+        assert_eq!(mode("[hypnosis]\nmode = sleep\n"), HypnosisMode::Sleep);
+        assert_eq!(mode("[hypnosis]\nmode = Reversed # mirror the stick\n"), HypnosisMode::ReversedControl);
+        assert_eq!(mode("[hypnosis]\nmode = confused\n"), DEFAULT_HYPNOSIS_MODE);
+    }
+
+    #[test]
+    fn config_with_trailing_comments_keeps_every_stage() {
+        let config = crate::gastly::config::parse_ini(
+            "[dark_move.down_air]\nstages = gastly, haunter, gengar   # let Gengar's down air lick too\nflash = simple # none | simple | enhanced\n",
+        );
+        let table = build_dark_move_table(&config);
+        assert_eq!(validate_dark_move_table(&table), Ok(()));
+        let down_air = table.iter().find(|entry| entry.move_type == DARK_MOVE_DOWN_AIR).unwrap();
+        assert_eq!(down_air.allowed_stages, [true, true, true]);
+        assert_eq!(down_air.flash.kind, FlashKind::Simple);
+    }
+}
@@ -2,9 +2,9 @@
 
 use smash::app::lua_bind::{
     StatusModule, MotionModule, WorkModule, ModelModule, EffectModule, 
-    AttackModule, DamageModule, StopModule, SoundModule
+    AttackModule, DamageModule, StopModule, SoundModule, ControlModule
 };
-use smash::app::BattleObjectModuleAccessor;
+use smash::app::{BattleObjectModuleAccessor, MODEL_COLOR_TYPE};
 use smash::phx::{Hash40, Vector3f};
 use smash::lib::lua_const::*;
 use smash::lua2cpp::L2CFighterCommon;
@@ -19,62 +19,69 @@ use std::sync::Mutex;
 // Import for evolution stage detection
 use crate::gastly::{FIGHTER_STATES, player_state::EvolutionStage};
 
-// --- DARK EFFECT CONFIGURATION ---
-const DARK_EFFECT_NAME: &str = "ganon_attack_purple";
-const DARK_EFFECT_COOLDOWN_FRAMES: u32 = 30;
-
-// Dark move type constants - UPDATED FOR NEW EFFECTS
-const DARK_MOVE_DOWN_TILT: u32 = 2;
-const DARK_MOVE_DOWN_SMASH: u32 = 7;
-const DARK_MOVE_NEUTRAL_AIR: u32 = 8;
-const DARK_MOVE_FORWARD_AIR: u32 = 9;
-const DARK_MOVE_DOWN_AIR: u32 = 10; //  Down Air with bomber sweat (Gastly/Haunter only)
-const DARK_MOVE_DOWN_SPECIAL: u32 = 12; // Down Special with poison effect
-const DARK_MOVE_NEUTRAL_SPECIAL_ROLLOUT: u32 = 13; // For rollout flash effect
-const DARK_MOVE_FORWARD_SPECIAL: u32 = 14;
-const DARK_MOVE_PUMMEL: u32 = 16; //  Pummel with bomber sweat (All stages)
-
-// Global frame counter
-static mut PSEUDO_GLOBAL_FRAME_COUNT: u32 = 0;
-
-#[derive(Clone)]
-struct AttackerInfo {
-    entry_id: u32,
-    move_type: u32,
-    evolution_stage: u32, // 0=Gastly, 1=Haunter, 2=Gengar
-}
+use crate::gastly::hit_records;
+
+// Dark move definitions (status/motion/stage rules, hit effect, flash, cooldown) live in dark_moves.rs
+use crate::gastly::dark_moves::{
+    DarkMoveEntry, DarkMoveStatus, DarkStatusEffect, DarkTintBackend, FlashKind, FlashProfile, HypnosisMode,
+    DARK_MOVE_TABLE, DARK_TINT_BACKEND, DEFAULT_HYPNOSIS_MODE, HYPNOSIS_MODE, dark_move_entry
+};
+
+// --- STATUS EFFECT CONFIGURATION ---
+// Poison (Down Special): damage over time tracked per defender
+const POISON_DURATION_FRAMES: i32 = 300; // 5 seconds of poison
+const POISON_TICK_INTERVAL_FRAMES: i32 = 60; // Damage applied once per second
+const POISON_DAMAGE_PER_TICK: f32 = 1.0; // 5% total over the full duration
+
+// Hypnosis (Up Special): how long it lasts; what it does is `[hypnosis] mode` (HYPNOSIS_MODE in dark_moves.rs)
+const HYPNOSIS_DURATION_FRAMES: i32 = 90;
 
 #[derive(Debug, Clone, Copy)]
 struct DarkEffectData {
     last_effect_frame: u32,
+    last_hit_record_sequence: u64, // Cursor into hit_records - hits after this haven't been applied yet
     flash_stage: u8,
     flash_timer: i32,
     // Enhanced flash tracking for status interruption handling
     flash_total_planned_duration: i32,
     flash_elapsed_duration: i32,
     flash_last_status: i32,
+    flash_color: [f32; 4],
+    flash_tint: [f32; 3],
+    // Poison status (Down Special)
+    poison_frames_remaining: i32,
+    poison_tick_timer: i32,
+    poison_source_entry_id: u32,
+    // Hypnosis status (Up Special)
+    hypnosis_frames_remaining: i32,
+    hypnosis_mode: HypnosisMode,
 }
 
 impl Default for DarkEffectData {
     fn default() -> Self {
         Self {
             last_effect_frame: 0,
+            last_hit_record_sequence: 0,
             flash_stage: 0,
             flash_timer: 0,
             flash_total_planned_duration: 0,
             flash_elapsed_duration: 0,
             flash_last_status: -1,
+            flash_color: FlashProfile::DARK_PURPLE,
+            flash_tint: FlashProfile::DARK_PURPLE_TINT,
+            poison_frames_remaining: 0,
+            poison_tick_timer: 0,
+            poison_source_entry_id: 0,
+            hypnosis_frames_remaining: 0,
+            hypnosis_mode: DEFAULT_HYPNOSIS_MODE,
         }
     }
 }
 
+// Keyed by entry id: victims can be any fighter, not just marked Gastly slots (see get_instance_key)
 static DARK_EFFECT_DATA: Lazy<Mutex<HashMap<u32, DarkEffectData>>> = 
     Lazy::new(|| Mutex::new(HashMap::new()));
 
-// Store which fighters are Gastly performers of dark moves this frame with move info
-static GASTLY_ATTACKERS_THIS_FRAME: Lazy<Mutex<Vec<AttackerInfo>>> = 
-    Lazy::new(|| Mutex::new(Vec::new()));
-
 // Get evolution stage from player state
 unsafe fn get_evolution_stage(boma: *mut BattleObjectModuleAccessor) -> u32 {
     let instance_key = crate::gastly::get_instance_key(boma);
@@ -93,7 +100,26 @@ unsafe fn get_evolution_stage(boma: *mut BattleObjectModuleAccessor) -> u32 {
     0
 }
 
-unsafe fn check_for_dark_move(boma: *mut BattleObjectModuleAccessor) -> Option<(u32, u32)> {
+// Resolve a table status to the game's status kind
+unsafe fn resolve_dark_move_status(status: DarkMoveStatus) -> i32 {
+    match status {
+        DarkMoveStatus::AttackLw3 => *FIGHTER_STATUS_KIND_ATTACK_LW3,
+        DarkMoveStatus::AttackLw4 => *FIGHTER_STATUS_KIND_ATTACK_LW4,
+        DarkMoveStatus::AttackAir => *FIGHTER_STATUS_KIND_ATTACK_AIR,
+        DarkMoveStatus::SpecialS => *FIGHTER_STATUS_KIND_SPECIAL_S,
+        DarkMoveStatus::SpecialHi => *FIGHTER_STATUS_KIND_SPECIAL_HI,
+        DarkMoveStatus::SpecialLw => *FIGHTER_STATUS_KIND_SPECIAL_LW,
+        DarkMoveStatus::CatchAttack => *FIGHTER_STATUS_KIND_CATCH_ATTACK,
+        DarkMoveStatus::Raw(status_kind) => status_kind,
+    }
+}
+
+// Dark move type the fighter is currently performing, if any (called from hit_tracking_hook)
+pub unsafe fn classify_dark_move(boma: *mut BattleObjectModuleAccessor) -> Option<u32> {
+    check_for_dark_move(boma).map(|(entry, _)| entry.move_type)
+}
+
+unsafe fn check_for_dark_move(boma: *mut BattleObjectModuleAccessor) -> Option<(DarkMoveEntry, u32)> {
     // FIRST: Must be a Gastly (Purin)
     let fighter_kind = utility::get_kind(&mut *boma);
     if fighter_kind != *FIGHTER_KIND_PURIN {
@@ -105,48 +131,37 @@ unsafe fn check_for_dark_move(boma: *mut BattleObjectModuleAccessor) -> Option<(
         return None;
     }
 
+    // Slot marker can disable dark moves
+    if !crate::gastly::markers::marker_for(boma).subsystems.dark_moves {
+        return None;
+    }
+
     let evolution_stage = get_evolution_stage(boma);
     let current_status = StatusModule::status_kind(boma);
     let current_motion = MotionModule::motion_kind(boma);
-    let motion_hash = Hash40 { hash: current_motion };
-    
-    let move_type = match current_status {
-        // ONLY THESE MOVES CAUSE DARK EFFECTS:
-        s if s == *FIGHTER_STATUS_KIND_ATTACK_LW3 => Some(DARK_MOVE_DOWN_TILT), // Down Tilt
-        s if s == *FIGHTER_STATUS_KIND_ATTACK_LW4 => Some(DARK_MOVE_DOWN_SMASH), // Down Smash
-        s if s == *FIGHTER_STATUS_KIND_ATTACK_AIR => {
-            if motion_hash.hash == smash::hash40("attack_air_n") {
-                Some(DARK_MOVE_NEUTRAL_AIR) // Neutral Air
-            } else if motion_hash.hash == smash::hash40("attack_air_f") {
-                Some(DARK_MOVE_FORWARD_AIR) // Forward Air
-            } else if motion_hash.hash == smash::hash40("attack_air_lw") {
-                //  Down Air with bomber sweat (Gastly/Haunter only)
-                if evolution_stage == 0 || evolution_stage == 1 { // Gastly or Haunter
-                    Some(DARK_MOVE_DOWN_AIR)
-                } else {
-                    None // Gengar omitted from down air effect
-                }
-            } else {
-                None
-            }
-        },
-        s if s == *FIGHTER_STATUS_KIND_SPECIAL_S => Some(DARK_MOVE_FORWARD_SPECIAL), // Forward Special
-        s if s == *FIGHTER_STATUS_KIND_SPECIAL_LW => Some(DARK_MOVE_DOWN_SPECIAL), // Down Special
-        //  Neutral Special hit status (when shadowball bomb occurs)
-        s if s == 0x1E7 => { // SPECIAL_N_HIT_END status
-            Some(DARK_MOVE_NEUTRAL_SPECIAL_ROLLOUT)
-        },
-        s if s == *FIGHTER_STATUS_KIND_CATCH_ATTACK => Some(DARK_MOVE_PUMMEL), //  Pummel with bomber sweat (All stages)
-        _ => None,
-    };
-    
-    move_type.map(|mt| (mt, evolution_stage))
+
+    // First entry whose status (and motion, if any) matches decides the move.
+    // Stage restrictions don't fall through to other entries (e.g. Gengar down air = no effect).
+    let table = DARK_MOVE_TABLE.read();
+    let entry = table.iter().find(|entry| {
+        entry.enabled &&
+        entry.statuses.iter().any(|status| resolve_dark_move_status(*status) == current_status) &&
+        entry.motion.map_or(true, |motion| motion == current_motion)
+    })?;
+
+    if !entry.allows_stage(evolution_stage) {
+        return None;
+    }
+
+    Some((entry.clone(), evolution_stage))
 }
 
 // Begin enhanced purple flash with status change detection
-unsafe fn begin_enhanced_purple_flash(entry_id: u32, total_duration: i32) {
+unsafe fn begin_enhanced_purple_flash(entry_id: u32, total_duration: i32, profile: &FlashProfile) {
     if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
         let data = data_map.entry(entry_id).or_insert_with(DarkEffectData::default);
+        data.flash_color = profile.color;
+        data.flash_tint = profile.tint;
         data.flash_stage = 1;
         data.flash_timer = 8; // Start with first blink
         data.flash_total_planned_duration = total_duration;
@@ -155,6 +170,40 @@ unsafe fn begin_enhanced_purple_flash(entry_id: u32, total_duration: i32) {
     }
 }
 
+// set_color_rgb takes the colour type as the opaque binding struct, not the lua const
+fn color_blend() -> MODEL_COLOR_TYPE {
+    MODEL_COLOR_TYPE { _address: *MODEL_COLOR_TYPE_COLOR_BLEND as u8 }
+}
+
+// Draw the dark-hit shade at the given strength (0.0 - 1.0) using the configured backend
+unsafe fn dark_tint_on(fighter: &mut L2CFighterCommon, flash_color: [f32; 4], flash_tint: [f32; 3], strength: f32) {
+    match *DARK_TINT_BACKEND.read() {
+        DarkTintBackend::Flash => {
+            let [r, g, b, a] = flash_color;
+            macros::FLASH(fighter, r, g, b, a * strength);
+        },
+        DarkTintBackend::Material => {
+            // Multiply the model colour towards the tint - independent of camera depth
+            let [r, g, b] = flash_tint.map(|c| 1.0 + (c - 1.0) * strength);
+            ModelModule::set_color_rgb(fighter.module_accessor, r, g, b, color_blend());
+        },
+    }
+}
+
+// Gap between blinks: the flash backend blinks, the material backend holds a steady shade
+unsafe fn dark_tint_gap(fighter: &mut L2CFighterCommon, flash_color: [f32; 4], flash_tint: [f32; 3]) {
+    match *DARK_TINT_BACKEND.read() {
+        DarkTintBackend::Flash => macros::COL_NORMAL(fighter),
+        DarkTintBackend::Material => dark_tint_on(fighter, flash_color, flash_tint, 1.0),
+    }
+}
+
+// Remove the shade (both backends, so switching backends never leaves a tint behind)
+unsafe fn dark_tint_off(fighter: &mut L2CFighterCommon) {
+    macros::COL_NORMAL(fighter);
+    ModelModule::set_color_rgb(fighter.module_accessor, 1.0, 1.0, 1.0, color_blend());
+}
+
 // Enhanced flash effects with status change detection
 unsafe fn update_purple_flash_effects_with_status_detection(fighter: &mut L2CFighterCommon, entry_id: u32, current_status: i32) {
     if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
@@ -182,46 +231,47 @@ unsafe fn update_purple_flash_effects_with_status_detection(fighter: &mut L2CFig
                     data.flash_stage = 0;
                     data.flash_total_planned_duration = 0;
                     data.flash_elapsed_duration = 0;
-                    macros::COL_NORMAL(fighter);
+                    dark_tint_off(fighter);
                     return;
                 }
             }
             
             // Regular flash processing (enhanced version)
+            let (flash_color, flash_tint) = (data.flash_color, data.flash_tint);
             if data.flash_stage > 0 {
                 data.flash_timer -= 1;
                 
                 match data.flash_stage {
                     1 => { // First blink
-                        macros::FLASH(fighter, 0.2, 0.05, 0.3, 0.8); // Darker purple for regular dark moves
+                        dark_tint_on(fighter, flash_color, flash_tint, 1.0);
                         if data.flash_timer <= 0 {
                             data.flash_stage = 2;
                             data.flash_timer = 6;
                         }
                     },
                     2 => { // Gap between first and second blink
-                        macros::COL_NORMAL(fighter);
+                        dark_tint_gap(fighter, flash_color, flash_tint);
                         if data.flash_timer <= 0 {
                             data.flash_stage = 3;
                             data.flash_timer = 8;
                         }
                     },
                     3 => { // Second blink
-                        macros::FLASH(fighter, 0.2, 0.05, 0.3, 0.8); // Darker purple for regular dark moves
+                        dark_tint_on(fighter, flash_color, flash_tint, 1.0);
                         if data.flash_timer <= 0 {
                             data.flash_stage = 4;
                             data.flash_timer = 6;
                         }
                     },
                     4 => { // Gap between second and third blink
-                        macros::COL_NORMAL(fighter);
+                        dark_tint_gap(fighter, flash_color, flash_tint);
                         if data.flash_timer <= 0 {
                             data.flash_stage = 5;
                             data.flash_timer = 8;
                         }
                     },
                     5 => { // Third blink
-                        macros::FLASH(fighter, 0.2, 0.05, 0.3, 0.8); // Darker purple for regular dark moves
+                        dark_tint_on(fighter, flash_color, flash_tint, 1.0);
                         if data.flash_timer <= 0 {
                             data.flash_stage = 6;
                             data.flash_timer = 30; // 30 frame fade
@@ -229,12 +279,12 @@ unsafe fn update_purple_flash_effects_with_status_detection(fighter: &mut L2CFig
                     },
                     6 => { // Fade out over 30 frames
                         let fade_progress = 1.0 - (data.flash_timer as f32 / 30.0);
-                        let fade_alpha = 0.8 * (1.0 - fade_progress);
+                        let fade_strength = 1.0 - fade_progress;
                         
-                        if fade_alpha > 0.0 {
-                            macros::FLASH(fighter, 0.2, 0.05, 0.3, fade_alpha); // Darker purple fade
+                        if fade_strength > 0.0 {
+                            dark_tint_on(fighter, flash_color, flash_tint, fade_strength);
                         } else {
-                            macros::COL_NORMAL(fighter);
+                            dark_tint_off(fighter);
                         }
                         
                         if data.flash_timer <= 0 {
@@ -244,7 +294,7 @@ unsafe fn update_purple_flash_effects_with_status_detection(fighter: &mut L2CFig
                                 data.flash_stage = 0;
                                 data.flash_total_planned_duration = 0;
                                 data.flash_elapsed_duration = 0;
-                                macros::COL_NORMAL(fighter);
+                                dark_tint_off(fighter);
                             } else {
                                 // Restart the sequence if we haven't hit the planned duration
                                 data.flash_stage = 1;
@@ -256,7 +306,7 @@ unsafe fn update_purple_flash_effects_with_status_detection(fighter: &mut L2CFig
                         data.flash_stage = 0;
                         data.flash_total_planned_duration = 0;
                         data.flash_elapsed_duration = 0;
-                        macros::COL_NORMAL(fighter);
+                        dark_tint_off(fighter);
                     }
                 }
             }
@@ -265,13 +315,14 @@ unsafe fn update_purple_flash_effects_with_status_detection(fighter: &mut L2CFig
 }
 
 // Start flash effect for attacker (simple version - darker purple)
-unsafe fn begin_purple_flash(entry_id: u32) {
+unsafe fn begin_purple_flash(entry_id: u32, profile: &FlashProfile) {
     if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
         let data = data_map.entry(entry_id).or_insert_with(DarkEffectData::default);
         if data.flash_stage == 0 {
+            data.flash_color = profile.color;
+            data.flash_tint = profile.tint;
             data.flash_stage = 1;
             data.flash_timer = 8;
-            // Note: This uses the darker purple flash (0.2, 0.05, 0.3)
         }
     }
 }
@@ -289,6 +340,196 @@ unsafe fn cleanup_flash_effects_on_death(entry_id: u32) {
     }
 }
 
+// Sleep statuses that Hypnosis (vanilla Sing hitbox) puts the defender into
+unsafe fn is_sleep_status(status: i32) -> bool {
+    status == *FIGHTER_STATUS_KIND_SLEEP_START ||
+    status == *FIGHTER_STATUS_KIND_SLEEP ||
+    status == *FIGHTER_STATUS_KIND_SLEEP_FALL
+}
+
+// Apply (or refresh) poison on a defender - refreshing keeps the current tick timer
+unsafe fn apply_poison_status(defender_entry_id: u32, source_entry_id: u32) {
+    if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
+        let data = data_map.entry(defender_entry_id).or_insert_with(DarkEffectData::default);
+        if data.poison_frames_remaining <= 0 {
+            data.poison_tick_timer = POISON_TICK_INTERVAL_FRAMES;
+        }
+        data.poison_frames_remaining = POISON_DURATION_FRAMES;
+        data.poison_source_entry_id = source_entry_id;
+    }
+}
+
+// Apply (or refresh) hypnosis on a defender
+unsafe fn apply_hypnosis_status(defender_entry_id: u32) {
+    if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
+        let data = data_map.entry(defender_entry_id).or_insert_with(DarkEffectData::default);
+        data.hypnosis_frames_remaining = HYPNOSIS_DURATION_FRAMES;
+        data.hypnosis_mode = *HYPNOSIS_MODE.read();
+    }
+}
+
+// Spawn the small poison bubble effect shown on every poison tick
+unsafe fn spawn_poison_tick_effect(boma: *mut BattleObjectModuleAccessor) {
+    let character_model_scale = ModelModule::scale(boma);
+    let effect_handle = EffectModule::req_follow(
+        boma,
+        Hash40::new("sys_hit_poison"),
+        Hash40::new("hip"),
+        &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
+        &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
+        0.8 * character_model_scale.max(0.1),
+        true, 0x40000, 0, -1, 0, 0, false, false
+    );
+
+    if effect_handle != u64::MAX && effect_handle != 0u64 {
+        let handle_u32 = effect_handle as u32;
+        EffectModule::set_rgb(boma, handle_u32, 2.0, 1.0, 1.0);
+        EffectModule::set_rate(boma, handle_u32, 0.5);
+    }
+}
+
+// Reverse horizontal control: mirror the main stick once per frame, before the control energy reads it.
+// The controller overwrites the stick every frame, so this never double-flips.
+unsafe fn apply_reversed_control(boma: *mut BattleObjectModuleAccessor) {
+    let stick_x = ControlModule::get_stick_x(boma);
+    ControlModule::set_main_stick_x(boma, -stick_x);
+}
+
+// Tick poison and hypnosis for this fighter (runs once per frame from the dark effects handler)
+unsafe fn update_status_effects(boma: *mut BattleObjectModuleAccessor, entry_id: u32, current_status: i32) {
+    let mut poison_tick = false;
+    let mut hypnosis_active = false;
+    let mut hypnosis_expired = false;
+    let mut hypnosis_mode = DEFAULT_HYPNOSIS_MODE;
+    let mut poison_flash_profile = FlashProfile::simple();
+
+    if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
+        if let Some(data) = data_map.get_mut(&entry_id) {
+            poison_flash_profile.color = data.flash_color;
+            poison_flash_profile.tint = data.flash_tint;
+            if data.poison_frames_remaining > 0 {
+                data.poison_frames_remaining -= 1;
+                data.poison_tick_timer -= 1;
+                if data.poison_tick_timer <= 0 {
+                    poison_tick = true;
+                    data.poison_tick_timer = POISON_TICK_INTERVAL_FRAMES;
+                }
+            }
+
+            if data.hypnosis_frames_remaining > 0 {
+                data.hypnosis_frames_remaining -= 1;
+                hypnosis_active = true;
+                hypnosis_expired = data.hypnosis_frames_remaining == 0;
+                hypnosis_mode = data.hypnosis_mode;
+            }
+        }
+    }
+
+    if poison_tick {
+        DamageModule::add_damage(boma, POISON_DAMAGE_PER_TICK, 0);
+        spawn_poison_tick_effect(boma);
+        begin_purple_flash(entry_id, &poison_flash_profile);
+    }
+
+    if hypnosis_active {
+        match hypnosis_mode {
+            HypnosisMode::Sleep => {
+                // Cut vanilla sleep short once the hypnosis window runs out
+                if hypnosis_expired && is_sleep_status(current_status) {
+                    StatusModule::change_status_request_from_script(boma, *FIGHTER_STATUS_KIND_SLEEP_END, false);
+                }
+            },
+            HypnosisMode::ReversedControl => {
+                // Skip the sleep entirely - the defender is confused instead
+                if is_sleep_status(current_status) {
+                    StatusModule::change_status_request_from_script(boma, *FIGHTER_STATUS_KIND_SLEEP_END, false);
+                } else {
+                    apply_reversed_control(boma);
+                }
+            },
+        }
+    }
+}
+
+// Clear poison/hypnosis on death/respawn, and end any poison this fighter inflicted
+unsafe fn cleanup_status_effects_on_death(entry_id: u32) {
+    if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
+        if let Some(data) = data_map.get_mut(&entry_id) {
+            data.hypnosis_frames_remaining = 0;
+        }
+        for (victim_entry_id, data) in data_map.iter_mut() {
+            if *victim_entry_id == entry_id || (data.poison_frames_remaining > 0 && data.poison_source_entry_id == entry_id) {
+                data.poison_frames_remaining = 0;
+                data.poison_tick_timer = 0;
+            }
+        }
+    }
+}
+
+// Spawn the table-defined hit effect, start the flash and apply any lasting status on the victim
+unsafe fn apply_dark_move_to_victim(
+    boma: *mut BattleObjectModuleAccessor,
+    victim_entry_id: u32,
+    attacker_entry_id: u32,
+    dark_move: &DarkMoveEntry
+) {
+    if let Some(effect) = dark_move.effect.as_ref() {
+        // Get character model scale for proper effect sizing
+        let effect_scale = if effect.scale_by_model {
+            effect.scale * ModelModule::scale(boma).max(0.1)
+        } else {
+            effect.scale
+        };
+
+        let effect_handle = EffectModule::req_follow(
+            boma,
+            Hash40::new(&effect.name),
+            Hash40::new(&effect.bone),
+            &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
+            &Vector3f { x: effect.rot[0], y: effect.rot[1], z: effect.rot[2] },
+            effect_scale,
+            true, 0x40000, 0, -1, 0, 0, false, false
+        );
+
+        if effect_handle != u64::MAX && effect_handle != 0u64 {
+            let handle_u32 = effect_handle as u32;
+            if let Some([r, g, b]) = effect.rgb {
+                EffectModule::set_rgb(boma, handle_u32, r, g, b);
+            }
+            if let Some([x, y, z]) = effect.scale_xyz {
+                EffectModule::set_scale(boma, handle_u32, &Vector3f { x, y, z });
+            }
+            EffectModule::set_rate(boma, handle_u32, effect.rate);
+        }
+    }
+
+    match dark_move.flash.kind {
+        FlashKind::None => {}
+        FlashKind::Simple => begin_purple_flash(victim_entry_id, &dark_move.flash),
+        FlashKind::Enhanced(duration) => begin_enhanced_purple_flash(victim_entry_id, duration, &dark_move.flash),
+    }
+
+    match dark_move.status_effect {
+        // Damage over time is ticked in update_status_effects
+        DarkStatusEffect::Poison => apply_poison_status(victim_entry_id, attacker_entry_id),
+        DarkStatusEffect::Hypnosis => apply_hypnosis_status(victim_entry_id),
+        DarkStatusEffect::None => {}
+    }
+}
+
+// The attacker's palette recolours the dark-hit flash/tint it inflicts
+fn with_attacker_palette(mut entry: DarkMoveEntry, attacker_color: u32) -> DarkMoveEntry {
+    if let Some(palette) = crate::gastly::palettes::slot_palette(attacker_color as usize) {
+        if let Some(color) = palette.dark_flash_color {
+            entry.flash.color = color;
+        }
+        if let Some(tint) = palette.dark_tint_color {
+            entry.flash.tint = tint;
+        }
+    }
+    entry
+}
+
 //  Main dark effects handler called from mod.rs
 unsafe extern "C" fn gastly_dark_effects_handler(fighter: &mut L2CFighterCommon) {
     let module_accessor: *mut BattleObjectModuleAccessor = fighter.module_accessor;
@@ -298,224 +539,74 @@ unsafe extern "C" fn gastly_dark_effects_handler(fighter: &mut L2CFighterCommon)
     let current_status = StatusModule::status_kind(module_accessor);
     if current_status == *FIGHTER_STATUS_KIND_DEAD || current_status == *FIGHTER_STATUS_KIND_REBIRTH {
         cleanup_flash_effects_on_death(fighter_entry_id_u32);
-        macros::COL_NORMAL(fighter); // Ensure normal color on death
+        cleanup_status_effects_on_death(fighter_entry_id_u32);
+        dark_tint_off(fighter); // Ensure normal color on death
         return;
     }
 
-    // Update global frame counter (only for player 0)
-    if fighter_entry_id_u32 == 0 { 
-        PSEUDO_GLOBAL_FRAME_COUNT = PSEUDO_GLOBAL_FRAME_COUNT.wrapping_add(1);
-        
-        // Clear the list at the start of each frame
-        if let Ok(mut attackers) = GASTLY_ATTACKERS_THIS_FRAME.lock() {
-            attackers.clear();
+    // Update global frame counter (ticks once per frame whichever entries are present)
+    let is_partner = utility::get_kind(&mut *module_accessor) == *FIGHTER_KIND_NANA;
+    let current_global_frame = hit_records::observe_fighter_frame(fighter_entry_id_u32, is_partner);
+
+    // PHASE 1: Collect dark-move hits on this fighter (published by hit_tracking_hook)
+    let mut hit_cursor = 0u64;
+    if let Ok(data_map) = DARK_EFFECT_DATA.lock() {
+        if let Some(data) = data_map.get(&fighter_entry_id_u32) {
+            hit_cursor = data.last_hit_record_sequence;
         }
     }
+    let dark_hits: Vec<(u32, DarkMoveEntry)> = hit_records::take_hits_against(fighter_entry_id_u32, &mut hit_cursor)
+        .iter()
+        .filter(|record| record.attacker_entry_id != fighter_entry_id_u32)
+        .filter_map(|record| {
+            record.dark_move
+                .and_then(dark_move_entry)
+                .map(|entry| (record.attacker_entry_id, with_attacker_palette(entry, record.attacker_color)))
+        })
+        .collect();
+    if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
+        data_map.entry(fighter_entry_id_u32).or_insert_with(DarkEffectData::default).last_hit_record_sequence = hit_cursor;
+    }
 
-    // PHASE 1: Collect Gastly attackers
-    if let Some((move_type, evolution_stage)) = check_for_dark_move(module_accessor) {
-        if move_type == DARK_MOVE_NEUTRAL_SPECIAL_ROLLOUT {
-            // For 0x1E7 status, we don't need to check AttackModule - just add to attackers
-            if let Ok(mut attackers) = GASTLY_ATTACKERS_THIS_FRAME.lock() {
-                let attacker_info = AttackerInfo {
-                    entry_id: fighter_entry_id_u32,
-                    move_type,
-                    evolution_stage,
-                };
-                
-                if !attackers.iter().any(|a| a.entry_id == fighter_entry_id_u32) {
-                    attackers.push(attacker_info);
-                }
-            }
-        } else {
-            // For all other moves, check for hits using AttackModule
-            let infliction_hit = AttackModule::is_infliction_status(module_accessor, 0);
-            let attack_occur = AttackModule::is_attack_occur(module_accessor);
-            
-            if infliction_hit || attack_occur {
-                // This Gastly is hitting someone with a dark move
-                if let Ok(mut attackers) = GASTLY_ATTACKERS_THIS_FRAME.lock() {
-                    let attacker_info = AttackerInfo {
-                        entry_id: fighter_entry_id_u32,
-                        move_type,
-                        evolution_stage,
-                    };
-                    
-                    // Check if this attacker is already in the list
-                    if !attackers.iter().any(|a| a.entry_id == fighter_entry_id_u32) {
-                        attackers.push(attacker_info);
-                    }
-                }
-            }
+    // PHASE 2: Apply effects to this fighter as a victim
+    // Hypnosis doesn't cause a damage reaction - apply it unless the hit was shielded. The victim is the
+    // defender of the hypnosis hitbox's own hit record, so fighters merely standing nearby are never affected.
+    let is_guarding = current_status == *FIGHTER_STATUS_KIND_GUARD_ON ||
+                      current_status == *FIGHTER_STATUS_KIND_GUARD ||
+                      current_status == *FIGHTER_STATUS_KIND_GUARD_DAMAGE;
+    if let Some((_, hypnosis_move)) = dark_hits.iter().find(|(_, m)| m.status_effect == DarkStatusEffect::Hypnosis).filter(|_| !is_guarding) {
+        apply_hypnosis_status(fighter_entry_id_u32);
+        if hypnosis_move.flash.kind != FlashKind::None {
+            begin_purple_flash(fighter_entry_id_u32, &hypnosis_move.flash);
         }
     }
 
-    // PHASE 2: Apply effects to victims
-    let current_pseudo_global_frame = PSEUDO_GLOBAL_FRAME_COUNT;
-
-    // Check if this fighter is taking damage
-    if StopModule::is_damage(module_accessor) {
-        // Check if any Gastly is attacking this frame
-        let gastly_attackers = {
-            if let Ok(attackers) = GASTLY_ATTACKERS_THIS_FRAME.lock() {
-                attackers.clone()
-            } else {
-                Vec::new()
-            }
-        };
-
-        if !gastly_attackers.is_empty() {
-            // This fighter is taking damage while Gastly is attacking - apply appropriate effect
-            let mut should_spawn_effect = false;
-            if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
-                let data = data_map.entry(fighter_entry_id_u32).or_insert_with(DarkEffectData::default);
-                
-                // Check cooldown
-                if current_pseudo_global_frame >= data.last_effect_frame.wrapping_add(DARK_EFFECT_COOLDOWN_FRAMES) {
-                    should_spawn_effect = true;
-                    data.last_effect_frame = current_pseudo_global_frame;
-                }
+    // The highest priority dark move among this frame's hits decides the effect
+    // (default order: Down Special > Down Air > Neutral Special Rollout > Pummel > Regular Dark Moves)
+    let strongest = dark_hits.iter()
+        .filter(|(_, m)| m.priority > 0)
+        .max_by_key(|(_, m)| m.priority);
+
+    // Only react to hits that actually connected (not shielded)
+    if let Some((attacker_entry_id, dark_move)) = strongest.filter(|_| StopModule::is_damage(module_accessor)) {
+        let mut should_spawn_effect = false;
+        if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
+            let data = data_map.entry(fighter_entry_id_u32).or_insert_with(DarkEffectData::default);
+            
+            // Check cooldown
+            if current_global_frame >= data.last_effect_frame.wrapping_add(dark_move.cooldown_frames) {
+                should_spawn_effect = true;
+                data.last_effect_frame = current_global_frame;
             }
+        }
 
-            if should_spawn_effect {
-                // Get character model scale for proper effect sizing
-                let character_model_scale = ModelModule::scale(module_accessor);
-                let final_effect_scale = 1.75 * character_model_scale.max(0.1);
-                
-                // Determine what effects to apply based on the move types
-                let mut has_down_special = false;
-                let mut has_pummel = false;
-                let mut has_down_air = false; // NEW
-                let mut has_neutral_special_rollout = false;
-                let mut has_regular_dark_move = false;
-                
-                for attacker in &gastly_attackers {
-                    match attacker.move_type {
-                        DARK_MOVE_DOWN_SPECIAL => has_down_special = true,
-                        DARK_MOVE_PUMMEL => has_pummel = true,
-                        DARK_MOVE_DOWN_AIR => has_down_air = true, // NEW
-                        DARK_MOVE_NEUTRAL_SPECIAL_ROLLOUT => has_neutral_special_rollout = true,
-                        // All actual dark moves (ganon effect + flash)
-                        DARK_MOVE_DOWN_TILT | DARK_MOVE_DOWN_SMASH | 
-                        DARK_MOVE_NEUTRAL_AIR | DARK_MOVE_FORWARD_AIR | 
-                        DARK_MOVE_FORWARD_SPECIAL => has_regular_dark_move = true,
-                        _ => {} // No other moves should reach here
-                    }
-                }
-                
-                // Apply effects based on move priority: Down Special > Down Air > Neutral Special Rollout > Pummel > Regular Dark Moves
-                if has_down_special {
-                    // Down Special - poison effect + enhanced flash
-                    let effect_handle = EffectModule::req_follow(
-                        module_accessor,
-                        Hash40::new("sys_hit_poison"),
-                        Hash40::new("hip"),
-                        &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
-                        &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
-                        final_effect_scale,
-                        true, 0x40000, 0, -1, 0, 0, false, false
-                    );
-                    
-                    if effect_handle != u64::MAX && effect_handle != 0u64 {
-                        let handle_u32 = effect_handle as u32;
-                        EffectModule::set_rgb(module_accessor, handle_u32, 2.0, 1.0, 1.0);
-                        EffectModule::set_rate(module_accessor, handle_u32, 0.25);
-                    }
-                    
-                    // Start enhanced flash (120 frames = 3 blinks + fade)
-                    begin_enhanced_purple_flash(fighter_entry_id_u32, 120);
-                    
-                } else if has_down_air {
-                    //  Down Air - sys_bomber_sweat effect (NO FLASH) - Gastly/Haunter only
-                    let effect_handle = EffectModule::req_follow(
-                        module_accessor,
-                        Hash40::new("sys_bomber_sweat"),
-                        Hash40::new("hip"),
-                        &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
-                        &Vector3f { x: 0.0, y: 90.0, z: 0.0 }, // rot.xyz: 0, 90, 0 (emit upward)
-                        1.0, // Fixed scale - will be adjusted with set_scale
-                        true, 0x40000, 0, -1, 0, 0, false, false
-                    );
-                    
-                    if effect_handle != u64::MAX && effect_handle != 0u64 {
-                        let handle_u32 = effect_handle as u32;
-                        // Set scale.xyz to 1, 2, 1
-                        let scale_vector = Vector3f { x: 1.0, y: 2.0, z: 1.0 };
-                        EffectModule::set_scale(module_accessor, handle_u32, &scale_vector);
-                        // Reduce lifetime to make it despawn sooner
-                        EffectModule::set_rate(module_accessor, handle_u32, 1.5); // 1.5x speed = shorter duration
-                    }
-                    
-                    // NO FLASH for down air
-                    
-                } else if has_neutral_special_rollout {
-                    // Neutral Special Rollout - ganon effect + purple flash
-                    let effect_handle = EffectModule::req_follow(
-                        module_accessor,
-                        Hash40::new("ganon_attack_purple"),
-                        Hash40::new("hip"),
-                        &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
-                        &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
-                        final_effect_scale,
-                        true, 0x40000, 0, -1, 0, 0, false, false
-                    );
-                    
-                    if effect_handle != u64::MAX && effect_handle != 0u64 {
-                        let handle_u32 = effect_handle as u32;
-                        EffectModule::set_rgb(module_accessor, handle_u32, 2.0, 1.0, 1.0);
-                        EffectModule::set_rate(module_accessor, handle_u32, 0.3);
-                    }
-                    
-                    begin_purple_flash(fighter_entry_id_u32);
-                    
-                } else if has_pummel {
-                    //  Pummel (All stages): sys_bomber_sweat effect (NO FLASH)
-                    let effect_handle = EffectModule::req_follow(
-                        module_accessor,
-                        Hash40::new("sys_bomber_sweat"),
-                        Hash40::new("hip"),
-                        &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
-                        &Vector3f { x: 0.0, y: 90.0, z: 0.0 }, // rot.xyz: 0, 90, 0 (emit upward)
-                        1.0, // Fixed scale - will be adjusted with set_scale
-                        true, 0x40000, 0, -1, 0, 0, false, false
-                    );
-                    
-                    if effect_handle != u64::MAX && effect_handle != 0u64 {
-                        let handle_u32 = effect_handle as u32;
-                        // Set scale.xyz to 1, 2, 1
-                        let scale_vector = Vector3f { x: 1.0, y: 2.0, z: 1.0 };
-                        EffectModule::set_scale(module_accessor, handle_u32, &scale_vector);
-                        // Reduce lifetime to make it despawn sooner
-                        EffectModule::set_rate(module_accessor, handle_u32, 1.5); // 1.5x speed = shorter duration
-                    }
-                    
-                    // NO FLASH for pummel effects
-                    
-                } else if has_regular_dark_move {
-                    // Regular dark moves: ganon_attack_purple effect + purple flash
-                    let effect_handle = EffectModule::req_follow(
-                        module_accessor,
-                        Hash40::new(DARK_EFFECT_NAME),
-                        Hash40::new("hip"),
-                        &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
-                        &Vector3f { x: 0.0, y: 0.0, z: 0.0 },
-                        final_effect_scale,
-                        true, 0x40000, 0, -1, 0, 0, false, false
-                    );
-                    
-                    if effect_handle != u64::MAX && effect_handle != 0u64 {
-                        let handle_u32 = effect_handle as u32;
-                        EffectModule::set_rgb(module_accessor, handle_u32, 2.0, 1.0, 1.0);
-                        EffectModule::set_rate(module_accessor, handle_u32, 0.3);
-                    }
-                    
-                    begin_purple_flash(fighter_entry_id_u32);
-                }
-            }
+        if should_spawn_effect {
+            apply_dark_move_to_victim(module_accessor, fighter_entry_id_u32, *attacker_entry_id, dark_move);
         }
     }
 
-    // PHASE 3: Update flash effects for this fighter
+    // PHASE 3: Tick poison/hypnosis, then update flash effects for this fighter
+    update_status_effects(module_accessor, fighter_entry_id_u32, current_status);
     update_purple_flash_effects_with_status_detection(fighter, fighter_entry_id_u32, current_status);
 }
 
@@ -542,6 +633,7 @@ pub fn install_dark_effects() {
 // Public function to clean up flash effects (call from your main mod on death/respawn)
 pub unsafe fn cleanup_dark_effects_on_death(entry_id: u32) {
     cleanup_flash_effects_on_death(entry_id);
+    cleanup_status_effects_on_death(entry_id);
     
     // Additional cleanup for training mode resets
     if let Ok(mut data_map) = DARK_EFFECT_DATA.lock() {
@@ -564,8 +656,9 @@ pub unsafe fn force_clear_flash_for_entry(entry_id: u32) {
     // Try to get the fighter's module accessor and force COL_NORMAL
     let fighter_boma = smash::app::sv_battle_object::module_accessor(entry_id);
     if !fighter_boma.is_null() {
-        // Force reset color blend
+        // Force reset color blend and the material tint
         smash::app::lua_bind::ColorBlendModule::cancel_main_color(fighter_boma, 0);
+        ModelModule::set_color_rgb(fighter_boma, 1.0, 1.0, 1.0, color_blend());
     }
 }
 
@@ -113,9 +113,7 @@ impl UniversalEffectTracker {
 struct RolloutBombTracker {
     last_bomb_spawn_frame: i32,
     total_bombs_spawned: u32,
-    was_hitting_last_frame: bool,
-    last_enemy_damage_check: f32,
-    consecutive_hit_frames: i32,
+    last_hit_record_sequence: u64, // Cursor into hit_records for hits landed by this player
 }
 
 impl RolloutBombTracker {
@@ -123,13 +121,13 @@ impl RolloutBombTracker {
         Self {
             last_bomb_spawn_frame: -100,
             total_bombs_spawned: 0,
-            was_hitting_last_frame: false,
-            last_enemy_damage_check: 0.0,
-            consecutive_hit_frames: 0,
+            last_hit_record_sequence: 0,
         }
     }
 }
 
+const ROLLOUT_BOMB_COOLDOWN_FRAMES: i32 = 10;
+
 // Static tracking arrays for various effects across all players
 static mut ROLLOUT_BOMB_TRACKERS: [RolloutBombTracker; 256] = [RolloutBombTracker::new(); 256];
 static mut CUSTOM_AURA_HANDLE: [u32; 256] = [0; 256];
@@ -146,7 +144,6 @@ static mut CHARGE_MAX_FRAME1_SPAWNED: [bool; 256] = [false; 256];
 static mut LAST_CHARGE_MAX_STATUS: [i32; 256] = [-1; 256];
 static mut LAST_MAX_SIGN_FRAME: [i32; 256] = [-15; 256];
 static mut LAST_SPEEDBOOSTER_FRAME: [i32; 256] = [-10; 256];
-static mut LAST_STATUS_FOR_BOMB: [i32; 256] = [-1; 256];
 
 static UNIVERSAL_EFFECTS: Lazy<Mutex<HashMap<String, UniversalEffectTracker>>> = 
     Lazy::new(|| Mutex::new(HashMap::new()));
@@ -239,14 +236,9 @@ unsafe fn spawn_gastly_aura(
     
     // Apply visual modifications from consolidated settings
     
-    // Apply RGB - use shiny colors for shiny slots (only for Purin)
-    let is_shiny = unsafe { crate::is_shiny_gastly_costume(boma) };
-
-    if is_shiny {
-        EffectModule::set_rgb(boma, handle, 0.42, 0.75, 1.3); // Shiny blue aura
-    } else {
-        EffectModule::set_rgb(boma, handle, settings.color_r, settings.color_g, settings.color_b); // Normal purple aura
-    }
+    // Apply RGB - marker override, shiny blue or normal purple
+    let (r, g, b) = gastly_aura_rgb(boma);
+    EffectModule::set_rgb(boma, handle, r, g, b);
     EffectModule::set_alpha(boma, handle, settings.alpha);
     EffectModule::set_rate(boma, handle, settings.rate);
     
@@ -288,14 +280,9 @@ pub unsafe fn spawn_gastly_aura_direct(boma: *mut BattleObjectModuleAccessor) ->
     if handle != u64::MAX as u32 && handle != 0 {
         // Apply visual modifications
 
-        // Apply RGB - use shiny colors for shiny slots (only for Purin)
-        let is_shiny = unsafe { crate::is_shiny_gastly_costume(boma) };
-
-        if is_shiny {
-            EffectModule::set_rgb(boma, handle, 0.42, 0.75, 1.3); // Shiny blue aura
-        } else {
-            EffectModule::set_rgb(boma, handle, settings.color_r, settings.color_g, settings.color_b); // Normal purple aura
-        }
+        // Apply RGB - marker override, shiny blue or normal purple
+        let (r, g, b) = gastly_aura_rgb(boma);
+        EffectModule::set_rgb(boma, handle, r, g, b);
         EffectModule::set_alpha(boma, handle, settings.alpha);
         EffectModule::set_rate(boma, handle, settings.rate);
         EffectModule::set_rot(boma, handle, &rotation_vector);
@@ -343,7 +330,8 @@ pub unsafe fn spawn_persistent_gastly_aura_direct(boma: *mut BattleObjectModuleA
     
     if handle != u64::MAX as u32 && handle != 0 {
         // Apply visual modifications with extra persistence
-        EffectModule::set_rgb(boma, handle, settings.color_r, settings.color_g, settings.color_b);
+        let (r, g, b) = gastly_aura_rgb(boma);
+        EffectModule::set_rgb(boma, handle, r, g, b);
         EffectModule::set_alpha(boma, handle, settings.alpha);
         EffectModule::set_rate(boma, handle, settings.rate);
         EffectModule::set_rot(boma, handle, &rotation_vector);
@@ -404,7 +392,8 @@ unsafe fn spawn_shadowball_bone_aura(
         EffectModule::set_rgb(boma, handle, 7.0, 7.0, 7.0);
     } else {
         // Normal RGB
-        EffectModule::set_rgb(boma, handle, settings.color_r, settings.color_g, settings.color_b);
+        let (r, g, b) = gastly_aura_rgb(boma);
+        EffectModule::set_rgb(boma, handle, r, g, b);
     }
     
     EffectModule::set_alpha(boma, handle, settings.alpha);
@@ -474,6 +463,18 @@ unsafe fn should_suppress_gastly_aura(
     false
 }
 
+// Aura color for this slot: marker aura_color override, then the slot's palette (shiny blue by default), then the default purple
+pub unsafe fn gastly_aura_rgb(boma: *mut BattleObjectModuleAccessor) -> (f32, f32, f32) {
+    if let Some([r, g, b]) = crate::gastly::markers::marker_for(boma).aura_color {
+        return (r, g, b);
+    }
+    if let Some([r, g, b]) = crate::gastly::palettes::palette_for(boma).and_then(|palette| palette.aura_color) {
+        return (r, g, b);
+    }
+    let settings = GASTLY_AURA_SETTINGS;
+    (settings.color_r, settings.color_g, settings.color_b) // Normal purple aura
+}
+
 // Handle Gastly gas persistent aura
 unsafe fn handle_gastly_aura(
     boma: *mut BattleObjectModuleAccessor,
@@ -543,14 +544,9 @@ unsafe fn check_and_apply_evolution_aura_rgb(
         // Normal RGB when not evolving or different evolution
         let settings = GASTLY_AURA_SETTINGS;
 
-        // Apply RGB - use shiny colors for shiny slots (only for Purin)
-        let is_shiny = unsafe { crate::is_shiny_gastly_costume(boma) };
-
-        if is_shiny {
-            EffectModule::set_rgb(boma, aura_handle, 0.42, 0.75, 1.3); // Shiny blue aura
-        } else {
-            EffectModule::set_rgb(boma, aura_handle, settings.color_r, settings.color_g, settings.color_b); // Normal purple aura
-        }
+        // Apply RGB - marker override, shiny blue or normal purple
+        let (r, g, b) = gastly_aura_rgb(boma);
+        EffectModule::set_rgb(boma, aura_handle, r, g, b);
         EffectModule::set_alpha(boma, aura_handle, settings.alpha);
         EffectModule::set_rate(boma, aura_handle, settings.rate);
     }
@@ -820,8 +816,9 @@ pub unsafe fn handle_gastly_effects(
     // Handle evolution flash effect
     handle_evolution_effects(boma, player_state, fighter, current_status, current_frame);
 
-    // Handle Gastly persistent aura - ONLY for Gastly stage
-    if player_state.stage == EvolutionStage::Gastly {
+    // Handle Gastly persistent aura - ONLY for Gastly stage, unless the slot's marker disables it
+    let aura_enabled = crate::gastly::markers::marker_for(boma).subsystems.aura;
+    if player_state.stage == EvolutionStage::Gastly && aura_enabled {
         // Use the SAME shadowball detection logic that worked before
         let is_shadowball_hold = current_status == PURIN_SPECIAL_N_HOLD || 
                                 current_status == PURIN_SPECIAL_N_HOLD_MAX;
@@ -1413,7 +1410,7 @@ unsafe fn handle_shadowball_effects(
 ) {
     let instance_key = crate::gastly::get_instance_key(boma) as usize;
     if instance_key >= 256 { return; }
-    let entry_id = (instance_key / 32) as usize; // For backward compatibility with UI effects
+    let entry_id = crate::gastly::entry_id_from_instance_key(instance_key as u32) as usize; // For backward compatibility with UI effects
 
     // Always kill purin_appeal_lw effect
     EffectModule::kill_kind(boma, Hash40::new("purin_appeal_lw"), false, false);
@@ -1646,44 +1643,45 @@ unsafe fn handle_shadowball_effects(
         }
     }
     
-    // Bomb detection code
-    let is_hit_end_status = current_status == 0x1E7;
-    
-    if is_hit_end_status {
-        let status_just_changed = LAST_STATUS_FOR_BOMB[instance_key] != current_status;
-        
-        if status_just_changed {
-            macros::EFFECT(
-                fighter,
-                Hash40::new("mewtwo_shadowball_bomb"),
-                Hash40::new("body"),
-                0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0,
-                true
-            );
-            
-            // MUTE VANILLA HIT SOUNDS FIRST
-            SoundModule::stop_se(boma, Hash40::new("se_common_punch_hit_s"), 0);
-            SoundModule::stop_se(boma, Hash40::new("se_common_punch_hit_m"), 0);
-            SoundModule::stop_se(boma, Hash40::new("se_common_punch_hit_l"), 0);
-            SoundModule::stop_se(boma, Hash40::new("se_common_kick_hit_s"), 0);
-            SoundModule::stop_se(boma, Hash40::new("se_common_kick_hit_m"), 0);
-            SoundModule::stop_se(boma, Hash40::new("se_common_kick_hit_l"), 0);
-            SoundModule::stop_se(boma, Hash40::new("se_common_slap_hit_s"), 0);
-            SoundModule::stop_se(boma, Hash40::new("se_common_slap_hit_m"), 0);
-            SoundModule::stop_se(boma, Hash40::new("se_common_slap_hit_l"), 0);
-            
-            // Play fire sound with volume 1.5
-            let fire_handle = SoundModule::play_se(
-                boma,
-                Hash40::new("se_common_fire_m"),
-                true, false, false, false,
-                smash::app::enSEType(0)
-            );
-            SoundModule::set_se_vol(boma, fire_handle as i32, 0.5, 0);
-        }
+    // Bomb on every rollout hit that actually landed (published by hit_tracking_hook)
+    let tracker = &mut ROLLOUT_BOMB_TRACKERS[instance_key];
+    let rollout_hit_landed = crate::gastly::hit_records::take_hits_by(instance_key as u32, &mut tracker.last_hit_record_sequence)
+        .iter()
+        .any(|record| record.defender_entry_id.is_some() && record.attacker_status >= 0x1E3 && record.attacker_status <= 0x1E7);
+
+    if rollout_hit_landed && current_frame - tracker.last_bomb_spawn_frame >= ROLLOUT_BOMB_COOLDOWN_FRAMES {
+        tracker.last_bomb_spawn_frame = current_frame;
+        tracker.total_bombs_spawned += 1;
+
+        macros::EFFECT(
+            fighter,
+            Hash40::new("mewtwo_shadowball_bomb"),
+            Hash40::new("body"),
+            0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0,
+            true
+        );
+
+        // MUTE VANILLA HIT SOUNDS FIRST
+        SoundModule::stop_se(boma, Hash40::new("se_common_punch_hit_s"), 0);
+        SoundModule::stop_se(boma, Hash40::new("se_common_punch_hit_m"), 0);
+        SoundModule::stop_se(boma, Hash40::new("se_common_punch_hit_l"), 0);
+        SoundModule::stop_se(boma, Hash40::new("se_common_kick_hit_s"), 0);
+        SoundModule::stop_se(boma, Hash40::new("se_common_kick_hit_m"), 0);
+        SoundModule::stop_se(boma, Hash40::new("se_common_kick_hit_l"), 0);
+        SoundModule::stop_se(boma, Hash40::new("se_common_slap_hit_s"), 0);
+        SoundModule::stop_se(boma, Hash40::new("se_common_slap_hit_m"), 0);
+        SoundModule::stop_se(boma, Hash40::new("se_common_slap_hit_l"), 0);
+
+        // Play fire sound with volume 1.5
+        let fire_handle = SoundModule::play_se(
+            boma,
+            Hash40::new("se_common_fire_m"),
+            true, false, false, false,
+            smash::app::enSEType(0)
+        );
+        SoundModule::set_se_vol(boma, fire_handle as i32, 0.5, 0);
     }
     
-    LAST_STATUS_FOR_BOMB[instance_key] = current_status;
     
     // Reset bomb tracker when not in any shadowball-related status
     let is_any_shadowball_status = current_status == 0x1E1 || current_status == 0x1E2 || 
@@ -1733,7 +1731,7 @@ pub unsafe fn cleanup_player_universal_effects(instance_key: u32) {
             .collect();
         
         // Get player's boma for effect killing
-        let entry_id = (instance_key / 32) as u32; // Convert instance_key back to entry_id
+        let entry_id = crate::gastly::entry_id_from_instance_key(instance_key);
         let boma = smash::app::sv_battle_object::module_accessor(entry_id);
         
         for key in player_keys {
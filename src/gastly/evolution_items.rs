@@ -0,0 +1,152 @@
+// src/gastly/evolution_items.rs
+
+// Optional items mode: Linking Cord, Everstone and Rare Candy as stage pickups.
+//
+// Each evolution item stands in for an existing item kind and spawns through the normal item switch.
+// No reskinned models ship with the mod, so the pickups look like the vanilla items for everyone
+// (a Rare Candy is a plain Fairy Bottle by default). When a marked Purin picks one up, the item is
+// removed and fed to the evolution state machine instead:
+//   Linking Cord - manual evolution attempt (same item rules as the command, see item_rules.rs)
+//   Everstone    - turns the Everstone on, where the stage allows it
+//   Rare Candy   - adds `rare_candy_boost` of the current stage's damage/hit requirements
+// Every other fighter gets the vanilla item.
+//
+//   [items]
+//   enabled = true
+//   linking_cord = boomerang     # item kind name or number
+//   everstone = banana
+//   rare_candy = fairybottle
+//   rare_candy_boost = 0.5
+
+use parking_lot::RwLock;
+use smash::app::BattleObjectModuleAccessor;
+use smash::app::lua_bind::ItemModule;
+use smash::app::FighterUtil;
+use smash::lib::lua_const::*;
+
+use crate::gastly::config::{parse_int, IniDocument};
+use crate::gastly::constants::*;
+use crate::gastly::player_state::{PlayerEvolutionState, EvolutionStage};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvolutionItem {
+    LinkingCord,
+    Everstone,
+    RareCandy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ItemsModeConfig {
+    pub enabled: bool,
+    pub linking_cord_kind: Option<i32>,
+    pub everstone_kind: Option<i32>,
+    pub rare_candy_kind: Option<i32>,
+    pub rare_candy_boost: f32,
+}
+
+impl ItemsModeConfig {
+    pub const DISABLED: Self = Self {
+        enabled: false,
+        linking_cord_kind: None,
+        everstone_kind: None,
+        rare_candy_kind: None,
+        rare_candy_boost: 0.5,
+    };
+
+    pub fn item_for_kind(&self, kind: i32) -> Option<EvolutionItem> {
+        if !self.enabled {
+            None
+        } else if self.linking_cord_kind == Some(kind) {
+            Some(EvolutionItem::LinkingCord)
+        } else if self.everstone_kind == Some(kind) {
+            Some(EvolutionItem::Everstone)
+        } else if self.rare_candy_kind == Some(kind) {
+            Some(EvolutionItem::RareCandy)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn item_kind_by_name(name: &str) -> Option<i32> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "boomerang" => Some(*ITEM_KIND_BOOMERANG),
+        "banana" => Some(*ITEM_KIND_BANANA),
+        "fairybottle" | "fairy_bottle" => Some(*ITEM_KIND_FAIRYBOTTLE),
+        other => parse_int(other),
+    }
+}
+
+pub fn items_mode_from_config(config: &IniDocument) -> ItemsModeConfig {
+    let section = match config.section("items") {
+        Some(section) => section,
+        None => return ItemsModeConfig::DISABLED,
+    };
+    let kind = |key: &str, default: &str| item_kind_by_name(section.get(key).unwrap_or(default));
+    ItemsModeConfig {
+        enabled: section.get_bool("enabled").unwrap_or(false),
+        linking_cord_kind: kind("linking_cord", "boomerang"),
+        everstone_kind: kind("everstone", "banana"),
+        rare_candy_kind: kind("rare_candy", "fairybottle"),
+        rare_candy_boost: section.get_f32("rare_candy_boost").map(|boost| boost.clamp(0.0, 1.0)).unwrap_or(0.5),
+    }
+}
+
+pub static ITEMS_MODE: RwLock<ItemsModeConfig> = parking_lot::const_rwlock(ItemsModeConfig::DISABLED);
+
+pub fn load_items_mode() {
+    let config = crate::gastly::config::GASTLY_CONFIG.read();
+    *ITEMS_MODE.write() = items_mode_from_config(&config);
+}
+
+// Rare Candy: add a share of the stage's requirements (penalties included) to the current progress
+pub fn apply_rare_candy(player_state: &mut PlayerEvolutionState, boost: f32) {
+    let (required_dmg_received, required_hits) = match player_state.stage {
+        EvolutionStage::Gastly => (GASTLY_EVO_DMG_RECEIVED_THRESHOLD, GASTLY_EVO_HITS_THRESHOLD),
+        EvolutionStage::Haunter => (HAUNTER_EVO_DMG_RECEIVED_THRESHOLD, HAUNTER_EVO_HITS_THRESHOLD),
+        EvolutionStage::Gengar => return,
+    };
+    let required_dmg_received = required_dmg_received + player_state.evo_attempt_delay_damage_taken_penalty;
+    let required_hits = required_hits + player_state.evo_attempt_delay_hits_penalty;
+
+    player_state.damage_received_this_stage += required_dmg_received * boost;
+    player_state.hits_landed_this_stage += (required_hits as f32 * boost).ceil() as i32;
+}
+
+// Called every frame for marked Purin, before the evolution process
+pub unsafe fn handle_evolution_items(boma: *mut BattleObjectModuleAccessor, player_state: &mut PlayerEvolutionState) {
+    let config = *ITEMS_MODE.read();
+    if !config.enabled || !ItemModule::is_have_item(boma, 0) {
+        return;
+    }
+    let item = match config.item_for_kind(ItemModule::get_have_item_kind(boma, 0)) {
+        Some(item) => item,
+        None => return,
+    };
+
+    // Picked up: consume it
+    ItemModule::remove_item(boma, 0);
+
+    match item {
+        EvolutionItem::LinkingCord => {
+            // Same path as the command; item rules decide what happens
+            player_state.manual_linking_cord_evo_attempted_this_frame = true;
+        }
+        EvolutionItem::Everstone => {
+            let usable = crate::gastly::item_rules::item_rule_for_stage(player_state.stage).map_or(false, |rule| rule.everstone);
+            if usable && !player_state.is_evolving && !player_state.everstone_effect_active {
+                player_state.everstone_effect_active = true;
+                crate::gastly::match_stats::record_everstone_toggle(crate::gastly::get_instance_key(boma));
+                crate::gastly::icon_management::show_everstone_icon(boma, player_state);
+            } else if !usable || player_state.is_evolving {
+                crate::gastly::icon_management::show_everstone_x_icon(boma, player_state);
+            }
+        }
+        EvolutionItem::RareCandy => {
+            if !player_state.is_evolving {
+                apply_rare_candy(player_state, config.rare_candy_boost);
+                FighterUtil::flash_eye_info(boma);
+            }
+        }
+    }
+}
@@ -1,78 +1,85 @@
 // src/gastly/evolution_logic.rs
 
-use smash::app::lua_bind::{StatusModule, ControlModule, MotionModule, ModelModule, PostureModule, EffectModule, SoundModule}; 
+use smash::app::lua_bind::{StatusModule, MotionModule, ModelModule, PostureModule, EffectModule, SoundModule, DamageModule}; 
 use smash::app::BattleObjectModuleAccessor;
 use smash::phx::{Hash40, Vector3f};
 use smash::lua2cpp::L2CFighterCommon;
 use smash::lib::lua_const::*; 
-use skyline::libc::c_int; 
 use smash_script::macros; 
 
 // Import constants
 use crate::gastly::constants::*;
 // Import PlayerEvolutionState and EvolutionStage enum
 use crate::gastly::player_state::{PlayerEvolutionState, EvolutionStage};
+use crate::gastly::input_sequence::{CommandEvent, EvolutionCommand};
+use crate::gastly::item_rules::{StageTransition, LinkingCordCheck, item_rule, check_linking_cord};
+use crate::gastly::expression_engine::ExpressionInput;
 // Import visual functions needed for animation
 use crate::gastly::visuals::{update_body_and_unique_parts_visibility, set_active_eye_mesh};
 
 // This function is called every frame to check for evolution triggers or cancellations.
 pub unsafe fn handle_evolution_process(fighter: &mut L2CFighterCommon, player_state: &mut PlayerEvolutionState) { 
     let boma = fighter.module_accessor;
-    let current_status_val = StatusModule::status_kind(boma);
-    let is_guard_status = current_status_val == *FIGHTER_STATUS_KIND_GUARD || current_status_val == *FIGHTER_STATUS_KIND_GUARD_ON;
-    let on_ground = StatusModule::situation_kind(boma) == *SITUATION_KIND_GROUND;
-    let in_air = StatusModule::situation_kind(boma) == *SITUATION_KIND_AIR;
 
     // Check for evolution cancellation input if currently evolving
     if player_state.is_evolving {
-        let mut cancelled_by_input = false;
-        // Down Taunt x2 to cancel (during guard on ground or anytime in air)
-        if ControlModule::check_button_on_trriger(boma, *CONTROL_PAD_BUTTON_APPEAL_LW as c_int) {
-            if (on_ground && is_guard_status) || in_air { 
-                if player_state.current_frame - player_state.last_down_taunt_cancel_input_frame <= EVO_CANCEL_DOWN_TAUNT_WINDOW {
-                    player_state.down_taunt_cancel_press_count += 1;
-                } else {
-                    player_state.down_taunt_cancel_press_count = 1;
-                }
-                player_state.last_down_taunt_cancel_input_frame = player_state.current_frame;
-
-                if player_state.down_taunt_cancel_press_count >= 2 {
-                    cancelled_by_input = true;
-                }
-            }
-        }
-        // Reset cancel input count if window expires
-        if player_state.down_taunt_cancel_press_count == 1 &&
-           player_state.current_frame - player_state.last_down_taunt_cancel_input_frame > EVO_CANCEL_DOWN_TAUNT_WINDOW {
-            player_state.down_taunt_cancel_press_count = 0;
-        }
-
+        // Down Taunt x2 to cancel by default (during guard on ground or anytime in air)
+        let cancelled_by_input = player_state.command_events.contains(&CommandEvent::Matched(EvolutionCommand::CancelEvolution));
         if cancelled_by_input {
             player_state.cancel_evolution(fighter);
         }
         return; // If evolving (or just cancelled), don't check for new evolution triggers this frame
     }
     
-    // Check for manual evolution trigger for Haunter to Gengar
-    if player_state.stage == EvolutionStage::Haunter && on_ground && is_guard_status {
-        if player_state.manual_linking_cord_evo_attempted_this_frame { 
-            // If Everstone was active, attempting manual evolution consumes it
-            if player_state.everstone_effect_active {
-                player_state.everstone_effect_active = false; 
-                player_state.linking_cord_consumed_everstone_this_frame = true;
-            }
+    // Slot markers can disable evolution or end the chain early
+    let marker = crate::gastly::markers::marker_for(boma);
+    if !marker.subsystems.evolution {
+        return;
+    }
 
-            // Try to start evolution if Everstone is now off (either was off or just consumed)
-            if !player_state.everstone_effect_active { 
-                if !player_state.is_evolving && !player_state.linking_cord_active {
-                    player_state.start_evolution_process(EvolutionStage::Gengar, fighter, true);
+    // Manual evolution (Linking Cord) for the current transition, subject to its item rules.
+    // The Linking Cord command checks guard/situation.
+    let transition = StageTransition::from_stage(player_state.stage).filter(|transition| marker.allows_stage(transition.target()));
+    if let Some(transition) = transition {
+        if player_state.manual_linking_cord_evo_attempted_this_frame && !player_state.is_evolving && !player_state.linking_cord_active {
+            let rule = item_rule(transition);
+            let check = check_linking_cord(
+                &rule,
+                transition,
+                &player_state.linking_cord_usage,
+                player_state.evolution_progress(),
+                player_state.everstone_effect_active,
+                player_state.current_frame,
+            );
+            match check {
+                LinkingCordCheck::Allowed { consumes_everstone } => {
+                    // Attempting manual evolution consumes an active Everstone
+                    if consumes_everstone {
+                        player_state.everstone_effect_active = false; 
+                        player_state.linking_cord_consumed_everstone_this_frame = true;
+                    }
+                    if rule.damage_cost > 0.0 {
+                        DamageModule::add_damage(boma, rule.damage_cost, 0);
+                    }
+                    player_state.linking_cord_usage.record(transition, player_state.current_frame);
+                    player_state.start_evolution_process(transition.target(), fighter, true);
+                }
+                LinkingCordCheck::BlockedByEverstone => {
+                    crate::gastly::icon_management::show_everstone_x_icon(boma, player_state);
+                }
+                _ => {
+                    // Not allowed yet (progress, cooldown, no uses left)
+                    SoundModule::play_se(boma, Hash40::new("cancel_evolve"), true, false, false, false, smash::app::enSEType(0));
                 }
             }
         }
     }
     
+    // Everstone only holds back transitions whose rules allow it
+    let everstone_blocks = player_state.everstone_effect_active && transition.map_or(false, |transition| item_rule(transition).everstone);
+
     // If already evolving (e.g., manual trigger started it) or Everstone is active, don't check auto-evo
-    if player_state.is_evolving || player_state.everstone_effect_active { return; } 
+    if player_state.is_evolving || everstone_blocks { return; } 
 
     //  Check if readiness icons are currently displaying - if so, delay auto-evolution
     let readiness_icons_active = player_state.dmg_t_icon_display_timer > 0 ||
@@ -91,7 +98,7 @@ pub unsafe fn handle_evolution_process(fighter: &mut L2CFighterCommon, player_st
         _ => None, // Gengar cannot auto-evolve further
     };
 
-    if let Some(target_stage) = next_stage_candidate {
+    if let Some(target_stage) = next_stage_candidate.filter(|stage| marker.allows_stage(*stage)) {
         // start_evolution_process will check damage thresholds internally
         player_state.start_evolution_process(target_stage, fighter, false);
     }
@@ -134,22 +141,14 @@ pub unsafe fn advance_evolution_animation(fighter: &mut L2CFighterCommon, player
         ) as u32;
         
         if handle != u64::MAX as u32 && handle != 0 {
+            // Palette recolour (shadow variants etc.); regular slots keep the effect's own colour
+            if let Some([r, g, b]) = crate::gastly::palettes::palette_for(boma).and_then(|palette| palette.evo_flash_color) {
+                EffectModule::set_rgb(boma, handle, r, g, b);
+            }
             EffectModule::set_rate(boma, handle, 0.4);
         }
     }
     
-    // Check if player has moved significantly from evolution start position
-    let pos_diff_x = (current_pos_x - player_state.evolution_start_pos_x).abs();
-    let pos_diff_y = (current_pos_y - player_state.evolution_start_pos_y).abs();
-    let is_moving = pos_diff_x > 0.5 || pos_diff_y > 0.5;
-    
-    // Hide linking cord icon if player is moving during evolution
-    if is_moving && player_state.linking_cord_evo_attempt_icon_timer > 0 {
-        ModelModule::set_mesh_visibility(boma, *LINKING_CORD_ICON, false);
-    } else if player_state.linking_cord_evo_attempt_icon_timer > 0 {
-        ModelModule::set_mesh_visibility(boma, *LINKING_CORD_ICON, true);
-    }
-    
     // The evolving mesh system now handles all visual changes!
     // The set_active_eye_mesh function will detect player_state.is_evolving = true
     // and automatically show the appropriate evolving meshes based on:
@@ -158,10 +157,8 @@ pub unsafe fn advance_evolution_animation(fighter: &mut L2CFighterCommon, player
     // - Default case (main evolving mesh)
     
     // Keep normal blinking for current stage during evolution
-    player_state.blink_timer -= 1;
-    if player_state.blink_timer <= 0 {
-        player_state.advance_blink_phase();
-    }
+    let stage = player_state.stage;
+    player_state.expressions.update(stage, &ExpressionInput::IDLE, &mut |min, max| crate::gastly::random_module::rand_range_i32(min, max));
     
     // The visual system will automatically show:
     // 1. Appropriate evolving mesh (main/floorshadow/shadowball)
@@ -0,0 +1,198 @@
+// src/gastly/expression_audit.rs
+
+// Dev mode: which eye each motion asked for and which custom eye was shown.
+//
+//   [expressions]
+//   audit = true
+//
+// Every frame of a marked Purin adds to a count per (stage, motion, vanilla eye, shown eye). The
+// table accumulates over the session and is rewritten to EXPRESSION_AUDIT_PATH every
+// AUDIT_WRITE_INTERVAL recorded frames, unmapped rows first. Sources:
+//   RULE     - motion/status rule or win pose (expression_map.rs), or the Rest blink
+//   VANILLA  - translated from the vanilla eye
+//   UNMAPPED - no rule and no vanilla eye to translate; the idle timeline picked the eye
+// Nothing is recorded when off.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use smash::app::lua_bind::{MotionModule, StatusModule};
+use smash::app::BattleObjectModuleAccessor;
+use smash::phx::Hash40;
+
+use crate::gastly::config::IniDocument;
+use crate::gastly::expression_map::game_eye_expression;
+use crate::gastly::player_state::EvolutionStage;
+
+pub const EXPRESSION_AUDIT_PATH: &str = "sd:/ultimate/gastly/expression_audit.txt";
+pub const AUDIT_WRITE_INTERVAL: u32 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EyeSource {
+    Unmapped,
+    Rule,
+    Vanilla,
+}
+
+impl EyeSource {
+    fn name(self) -> &'static str {
+        match self {
+            EyeSource::Unmapped => "UNMAPPED",
+            EyeSource::Rule => "RULE",
+            EyeSource::Vanilla => "VANILLA",
+        }
+    }
+}
+
+// `forced`: the eye was set by a win pose / Rest override; `has_rule`: game_eye_expression matched
+pub fn eye_source(forced: bool, has_rule: bool, vanilla_eye: Option<Hash40>) -> EyeSource {
+    if forced || has_rule {
+        EyeSource::Rule
+    } else if vanilla_eye.is_some() {
+        EyeSource::Vanilla
+    } else {
+        EyeSource::Unmapped
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AuditKey {
+    pub stage: EvolutionStage,
+    pub motion: u64,
+    pub vanilla_eye: u64, // 0: none visible
+    pub shown: u64,
+    pub source: EyeSource,
+}
+
+// Purin's moveset, for readable motion names in the report (others print as hashes)
+const PURIN_MOTION_NAMES: &[&str] = &[
+    "wait_1", "wait_2", "wait_3", "walk_slow", "walk_middle", "walk_fast", "walk_brake",
+    "dash", "run", "run_brake_l", "run_brake_r", "turn", "turn_dash", "turn_run", "turn_run_brake_l", "turn_run_brake_r",
+    "jump_squat", "jump_f", "jump_b", "jump_f_mini", "jump_b_mini", "jump_aerial_f", "jump_aerial_b",
+    "fall", "fall_f", "fall_b", "fall_aerial", "fall_aerial_f", "fall_aerial_b", "fall_special",
+    "landing_light", "landing_heavy", "landing_fall_special", "squat", "squat_wait", "squat_rv",
+    "guard_on", "guard", "guard_off", "guard_damage", "escape_n", "escape_f", "escape_b", "escape_air",
+    "attack_11", "attack_12", "attack_dash", "attack_s3_hi", "attack_s3_s", "attack_s3_lw", "attack_hi3", "attack_lw3",
+    "attack_s4_hi", "attack_s4_s", "attack_s4_lw", "attack_s4_hold", "attack_hi4", "attack_hi4_hold", "attack_lw4", "attack_lw4_hold",
+    "attack_air_n", "attack_air_f", "attack_air_b", "attack_air_hi", "attack_air_lw",
+    "landing_air_n", "landing_air_f", "landing_air_b", "landing_air_hi", "landing_air_lw",
+    "catch", "catch_dash", "catch_turn", "catch_wait", "catch_attack", "throw_f", "throw_b", "throw_hi", "throw_lw",
+    "special_n_start", "special_n_hold", "special_n_hold_max", "special_n_regular_release", "special_n_charge_max", "special_n_charge_max_release",
+    "special_air_n_hold", "special_air_n_hold_max", "special_s", "special_air_s",
+    "special_hi_l", "special_hi_r", "special_air_hi_l", "special_air_hi_r",
+    "special_lw_l", "special_lw_r", "special_air_lw_l", "special_air_lw_r",
+    "appeal_hi_l", "appeal_hi_r", "appeal_s_l", "appeal_s_r", "appeal_lw_l", "appeal_lw_r",
+    "damage_hi_1", "damage_hi_2", "damage_hi_3", "damage_n_1", "damage_n_2", "damage_n_3", "damage_lw_1", "damage_lw_2", "damage_lw_3",
+    "damage_air_1", "damage_air_2", "damage_air_3", "damage_fly_hi", "damage_fly_n", "damage_fly_lw", "damage_fly_top", "damage_fly_roll",
+    "down_wait_u", "down_wait_d", "down_stand_u", "down_stand_d", "down_attack_u", "down_attack_d",
+    "down_forward_u", "down_forward_d", "down_back_u", "down_back_d",
+    "cliff_catch", "cliff_wait", "cliff_climb_quick", "cliff_attack_quick", "cliff_escape_quick", "cliff_jump_quick1",
+    "fura_sleep_start", "fura_sleep_loop", "fura_sleep_end", "furafura", "shield_break_fly",
+    "swim", "swim_f", "swim_b", "swim_end", "swim_rise", "swim_turn", "swim_up", "swim_up_damage", "swim_drown", "swim_drown_out",
+    "entry_l", "entry_r", "win_1", "win_1_wait", "win_2", "win_2_wait", "win_3", "win_3_wait", "lose",
+    "final_start_l", "final_start_r", "final_end_r", "final_air_start_l", "final_air_start_r", "final_air_end_r",
+];
+
+const EYE_MESH_PREFIXES: &[&str] = &["purin", "gastly", "haunter", "gengar"];
+const EYE_MESH_SUFFIXES: &[&str] = &[
+    "eyen", "eye_blink", "eye_halfblink1", "eye_attack", "eye_capture", "eye_ouch", "eye_down", "eye_heavyattack",
+    "eye_squint", "eye_look_f", "eye_look_b",
+];
+
+static LABELS: Lazy<HashMap<u64, String>> = Lazy::new(|| {
+    let mut labels = HashMap::new();
+    for name in PURIN_MOTION_NAMES {
+        labels.insert(smash::hash40(name), name.to_string());
+    }
+    for prefix in EYE_MESH_PREFIXES {
+        for suffix in EYE_MESH_SUFFIXES {
+            let name = format!("{}_{}", prefix, suffix);
+            labels.insert(smash::hash40(&name), name);
+        }
+    }
+    labels
+});
+
+fn label(hash: u64) -> String {
+    match hash {
+        0 => "-".to_string(),
+        _ => LABELS.get(&hash).cloned().unwrap_or_else(|| format!("0x{:010x}", hash)),
+    }
+}
+
+fn stage_name(stage: EvolutionStage) -> &'static str {
+    match stage {
+        EvolutionStage::Gastly => "gastly",
+        EvolutionStage::Haunter => "haunter",
+        EvolutionStage::Gengar => "gengar",
+    }
+}
+
+// One line per key, unmapped rows first, then by stage, motion name and frame count
+pub fn audit_report(counts: &HashMap<AuditKey, u32>) -> String {
+    let mut rows: Vec<(&AuditKey, u32, String)> = counts.iter().map(|(key, count)| (key, *count, label(key.motion))).collect();
+    rows.sort_by(|a, b| {
+        a.0.source.cmp(&b.0.source)
+            .then(stage_name(a.0.stage).cmp(stage_name(b.0.stage)))
+            .then(a.2.cmp(&b.2))
+            .then(b.1.cmp(&a.1))
+    });
+
+    let unmapped = rows.iter().filter(|row| row.0.source == EyeSource::Unmapped).count();
+    let mut text = String::new();
+    let _ = writeln!(text, "# Gastly expression audit: {} rows, {} unmapped", rows.len(), unmapped);
+    let _ = writeln!(text, "# source stage motion frames vanilla_eye -> shown_eye");
+    for (key, count, motion) in rows {
+        let _ = writeln!(
+            text,
+            "{} {} {} {} {} -> {}",
+            key.source.name(), stage_name(key.stage), motion, count, label(key.vanilla_eye), label(key.shown),
+        );
+    }
+    text
+}
+
+pub static EXPRESSION_AUDIT: RwLock<bool> = parking_lot::const_rwlock(false);
+
+static AUDIT_COUNTS: Lazy<Mutex<HashMap<AuditKey, u32>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static FRAMES_SINCE_WRITE: Mutex<u32> = parking_lot::const_mutex(0);
+
+pub fn audit_from_config(config: &IniDocument) -> bool {
+    config.section("expressions").and_then(|section| section.get_bool("audit")).unwrap_or(false)
+}
+
+pub fn load_expression_audit() {
+    let config = crate::gastly::config::GASTLY_CONFIG.read();
+    *EXPRESSION_AUDIT.write() = audit_from_config(&config);
+}
+
+pub fn is_enabled() -> bool {
+    *EXPRESSION_AUDIT.read()
+}
+
+// Called once per frame (when enabled) after the eye has been picked; `forced` when a win pose / Rest override set it.
+// `vanilla_eye` is PlayerEvolutionState::visible_vanilla_eye, sampled before the vanilla eyes were hidden.
+pub unsafe fn record_frame(boma: *mut BattleObjectModuleAccessor, stage: EvolutionStage, vanilla_eye: Option<Hash40>, shown: Hash40, forced: bool) {
+    let motion = MotionModule::motion_kind(boma);
+    let frame = MotionModule::frame(boma) as i32;
+    let has_rule = game_eye_expression(StatusModule::status_kind(boma), motion, frame).is_some();
+
+    let key = AuditKey {
+        stage,
+        motion,
+        vanilla_eye: vanilla_eye.map_or(0, |mesh| mesh.hash),
+        shown: shown.hash,
+        source: eye_source(forced, has_rule, vanilla_eye),
+    };
+    *AUDIT_COUNTS.lock().entry(key).or_insert(0) += 1;
+
+    let mut frames = FRAMES_SINCE_WRITE.lock();
+    *frames += 1;
+    if *frames >= AUDIT_WRITE_INTERVAL {
+        *frames = 0;
+        let report = audit_report(&AUDIT_COUNTS.lock());
+        let _ = std::fs::create_dir_all("sd:/ultimate/gastly");
+        let _ = std::fs::write(EXPRESSION_AUDIT_PATH, report);
+    }
+}
@@ -0,0 +1,428 @@
+// src/gastly/expression_engine.rs
+
+// Eye expressions: per-stage idle timelines plus prioritised interrupts.
+//
+// An idle timeline is a list of keys (expression, duration, next). The next key is picked at
+// random from `next`, so repeating an index weights it; that is how the double blink is rolled.
+// Higher-priority expressions interrupt the timeline:
+//   Reaction - a motion rule's eye (expression_map.rs), or an attack / capture / ouch / down /
+//              heavy attack eye from the vanilla face. A translated open or blink eye is not a
+//              reaction; the timeline keeps blinking over it.
+//   Shield   - squint while shielding
+// When the interrupt ends the timeline restarts from its first key with a short open-eye delay,
+// so blinking resumes without picking up half way through a blink.
+// While idle, the open eye looks at the nearest opponent (`_eye_look_f` / `_eye_look_b`).
+// Meshes a slot doesn't have fall back (squint -> half blink, look -> open).
+
+use smash::app::lua_bind::{PostureModule, StatusModule, TeamModule, WorkModule};
+use smash::app::BattleObjectModuleAccessor;
+use smash::lib::lua_const::*;
+use smash::phx::Hash40;
+
+use crate::gastly::constants::*;
+use crate::gastly::player_state::EvolutionStage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Expression {
+    Open,
+    HalfBlink,
+    Blink,
+    Attack,
+    Capture,
+    Ouch,
+    Down,
+    HeavyAttack,
+    Squint,
+    LookForward,
+    LookBack,
+}
+
+impl Expression {
+    pub const REACTIONS: [Expression; 5] = [
+        Expression::Attack, Expression::Capture, Expression::Ouch, Expression::Down, Expression::HeavyAttack,
+    ];
+
+    // Used when the slot's model lacks the mesh
+    pub fn fallback(self) -> Option<Expression> {
+        match self {
+            Expression::Squint => Some(Expression::HalfBlink),
+            Expression::LookForward | Expression::LookBack => Some(Expression::Open),
+            Expression::Open => None,
+            _ => Some(Expression::Open),
+        }
+    }
+}
+
+pub fn expression_mesh(stage: EvolutionStage, expression: Expression) -> Hash40 {
+    match (stage, expression) {
+        (EvolutionStage::Gastly, Expression::Open) => *GASTLY_EYE_N,
+        (EvolutionStage::Gastly, Expression::HalfBlink) => *GASTLY_EYE_HALFBLINK1,
+        (EvolutionStage::Gastly, Expression::Blink) => *GASTLY_EYE_BLINK,
+        (EvolutionStage::Gastly, Expression::Attack) => *GASTLY_EYE_ATTACK,
+        (EvolutionStage::Gastly, Expression::Capture) => *GASTLY_EYE_CAPTURE,
+        (EvolutionStage::Gastly, Expression::Ouch) => *GASTLY_EYE_OUCH,
+        (EvolutionStage::Gastly, Expression::Down) => *GASTLY_EYE_DOWN,
+        (EvolutionStage::Gastly, Expression::HeavyAttack) => *GASTLY_EYE_HEAVYATTACK,
+        (EvolutionStage::Gastly, Expression::Squint) => *GASTLY_EYE_SQUINT,
+        (EvolutionStage::Gastly, Expression::LookForward) => *GASTLY_EYE_LOOK_F,
+        (EvolutionStage::Gastly, Expression::LookBack) => *GASTLY_EYE_LOOK_B,
+
+        (EvolutionStage::Haunter, Expression::Open) => *HAUNTER_EYE_N,
+        (EvolutionStage::Haunter, Expression::HalfBlink) => *HAUNTER_EYE_HALFBLINK1,
+        (EvolutionStage::Haunter, Expression::Blink) => *HAUNTER_EYE_BLINK,
+        (EvolutionStage::Haunter, Expression::Attack) => *HAUNTER_EYE_ATTACK,
+        (EvolutionStage::Haunter, Expression::Capture) => *HAUNTER_EYE_CAPTURE,
+        (EvolutionStage::Haunter, Expression::Ouch) => *HAUNTER_EYE_OUCH,
+        (EvolutionStage::Haunter, Expression::Down) => *HAUNTER_EYE_DOWN,
+        (EvolutionStage::Haunter, Expression::HeavyAttack) => *HAUNTER_EYE_HEAVYATTACK,
+        (EvolutionStage::Haunter, Expression::Squint) => *HAUNTER_EYE_SQUINT,
+        (EvolutionStage::Haunter, Expression::LookForward) => *HAUNTER_EYE_LOOK_F,
+        (EvolutionStage::Haunter, Expression::LookBack) => *HAUNTER_EYE_LOOK_B,
+
+        (EvolutionStage::Gengar, Expression::Open) => *GENGAR_EYE_N,
+        (EvolutionStage::Gengar, Expression::HalfBlink) => *GENGAR_EYE_HALFBLINK1,
+        (EvolutionStage::Gengar, Expression::Blink) => *GENGAR_EYE_BLINK,
+        (EvolutionStage::Gengar, Expression::Attack) => *GENGAR_EYE_ATTACK,
+        (EvolutionStage::Gengar, Expression::Capture) => *GENGAR_EYE_CAPTURE,
+        (EvolutionStage::Gengar, Expression::Ouch) => *GENGAR_EYE_OUCH,
+        (EvolutionStage::Gengar, Expression::Down) => *GENGAR_EYE_DOWN,
+        (EvolutionStage::Gengar, Expression::HeavyAttack) => *GENGAR_EYE_HEAVYATTACK,
+        (EvolutionStage::Gengar, Expression::Squint) => *GENGAR_EYE_SQUINT,
+        (EvolutionStage::Gengar, Expression::LookForward) => *GENGAR_EYE_LOOK_F,
+        (EvolutionStage::Gengar, Expression::LookBack) => *GENGAR_EYE_LOOK_B,
+    }
+}
+
+pub fn is_reaction_mesh(stage: EvolutionStage, mesh: Hash40) -> bool {
+    Expression::REACTIONS.iter().any(|expression| expression_mesh(stage, *expression).hash == mesh.hash)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyDuration {
+    Frames(i32),
+    Random(i32, i32), // Inclusive range
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExpressionKey {
+    pub expression: Expression,
+    pub duration: KeyDuration,
+    pub next: &'static [usize],
+}
+
+const fn key(expression: Expression, duration: KeyDuration, next: &'static [usize]) -> ExpressionKey {
+    ExpressionKey { expression, duration, next }
+}
+
+use Expression::{Open, HalfBlink, Blink};
+use KeyDuration::{Frames, Random};
+
+// Open, a blink (3/5/3), 1 in 4 followed by a quicker second blink
+const GASTLY_IDLE: &[ExpressionKey] = &[
+    key(Open, Random(120, 300), &[1]),
+    key(HalfBlink, Frames(3), &[2]),
+    key(Blink, Frames(5), &[3]),
+    key(HalfBlink, Frames(3), &[0, 0, 0, 4]),
+    key(Open, Frames(6), &[5]),
+    key(HalfBlink, Frames(2), &[6]),
+    key(Blink, Frames(4), &[7]),
+    key(HalfBlink, Frames(2), &[0]),
+];
+
+// Haunter double blinks more often
+const HAUNTER_IDLE: &[ExpressionKey] = &[
+    key(Open, Random(120, 300), &[1]),
+    key(HalfBlink, Frames(3), &[2]),
+    key(Blink, Frames(5), &[3]),
+    key(HalfBlink, Frames(3), &[0, 0, 4]),
+    key(Open, Frames(5), &[5]),
+    key(HalfBlink, Frames(2), &[6]),
+    key(Blink, Frames(4), &[7]),
+    key(HalfBlink, Frames(2), &[0]),
+];
+
+// Gengar's heavier lids close and open slower
+const GENGAR_IDLE: &[ExpressionKey] = &[
+    key(Open, Random(150, 330), &[1]),
+    key(HalfBlink, Frames(4), &[2]),
+    key(Blink, Frames(6), &[3]),
+    key(HalfBlink, Frames(4), &[0, 0, 0, 4]),
+    key(Open, Frames(8), &[5]),
+    key(HalfBlink, Frames(3), &[6]),
+    key(Blink, Frames(5), &[7]),
+    key(HalfBlink, Frames(3), &[0]),
+];
+
+pub fn idle_timeline(stage: EvolutionStage) -> &'static [ExpressionKey] {
+    match stage {
+        EvolutionStage::Gastly => GASTLY_IDLE,
+        EvolutionStage::Haunter => HAUNTER_IDLE,
+        EvolutionStage::Gengar => GENGAR_IDLE,
+    }
+}
+
+// Open-eye time before blinking resumes after an interrupt
+pub const RESUME_DELAY: KeyDuration = Random(20, 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExpressionPriority {
+    Idle,
+    Shield,
+    Reaction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gaze {
+    Camera,
+    Forward,
+    Back,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExpressionInput {
+    pub scripted: Option<Hash40>, // Stage mesh from a motion rule, always shown
+    pub reaction: Option<Hash40>, // Stage mesh from the vanilla eye, shown if is_reaction_mesh
+    pub shielding: bool,
+    pub gaze: Gaze,
+}
+
+impl ExpressionInput {
+    pub const IDLE: Self = Self { scripted: None, reaction: None, shielding: false, gaze: Gaze::Camera };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EyeChoice {
+    Expression(Expression),
+    Mesh(Hash40),
+}
+
+#[derive(Debug, Clone)]
+pub struct ExpressionEngine {
+    key: usize,
+    timer: Option<i32>, // None: draw the key's duration on the next update
+    stage: EvolutionStage,
+    priority: ExpressionPriority,
+    current: EyeChoice,
+}
+
+impl Default for ExpressionEngine {
+    fn default() -> Self {
+        Self {
+            key: 0,
+            timer: None,
+            stage: EvolutionStage::Gastly,
+            priority: ExpressionPriority::Idle,
+            current: EyeChoice::Expression(Expression::Open),
+        }
+    }
+}
+
+fn draw_duration(duration: KeyDuration, rng: &mut dyn FnMut(i32, i32) -> i32) -> i32 {
+    match duration {
+        Frames(frames) => frames,
+        Random(min, max) => rng(min, max),
+    }
+}
+
+impl ExpressionEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn current(&self) -> EyeChoice {
+        self.current
+    }
+
+    pub fn priority(&self) -> ExpressionPriority {
+        self.priority
+    }
+
+    // One frame. `rng(min, max)` returns a value in the inclusive range.
+    pub fn update(&mut self, stage: EvolutionStage, input: &ExpressionInput, rng: &mut dyn FnMut(i32, i32) -> i32) -> EyeChoice {
+        let timeline = idle_timeline(stage);
+        if stage != self.stage {
+            // New stage: start its timeline from the top
+            self.stage = stage;
+            self.key = 0;
+            self.timer = None;
+        }
+
+        let reaction = input.reaction.filter(|mesh| is_reaction_mesh(stage, *mesh));
+        let (priority, interrupt) = if let Some(mesh) = input.scripted.or(reaction) {
+            (ExpressionPriority::Reaction, Some(EyeChoice::Mesh(mesh)))
+        } else if input.shielding {
+            (ExpressionPriority::Shield, Some(EyeChoice::Expression(Expression::Squint)))
+        } else {
+            (ExpressionPriority::Idle, None)
+        };
+
+        if let Some(choice) = interrupt {
+            self.priority = priority;
+            self.current = choice;
+            return choice;
+        }
+
+        if self.priority != ExpressionPriority::Idle {
+            // Interrupt over: resume from an open eye
+            self.priority = ExpressionPriority::Idle;
+            self.key = 0;
+            self.timer = Some(draw_duration(RESUME_DELAY, rng));
+        }
+
+        let mut timer = match self.timer {
+            Some(timer) => timer,
+            None => draw_duration(timeline[self.key].duration, rng),
+        };
+        timer -= 1;
+        if timer <= 0 {
+            let next = timeline[self.key].next;
+            let pick = if next.len() > 1 { rng(0, next.len() as i32 - 1) as usize } else { 0 };
+            self.key = next.get(pick).copied().unwrap_or(0).min(timeline.len() - 1);
+            timer = draw_duration(timeline[self.key].duration, rng);
+        }
+        self.timer = Some(timer);
+
+        let expression = match (timeline[self.key].expression, input.gaze) {
+            (Expression::Open, Gaze::Forward) => Expression::LookForward,
+            (Expression::Open, Gaze::Back) => Expression::LookBack,
+            (expression, _) => expression,
+        };
+        self.current = EyeChoice::Expression(expression);
+        self.current
+    }
+}
+
+// Mesh for a choice, following the fallbacks for meshes the slot doesn't have
+pub fn eye_choice_mesh(stage: EvolutionStage, choice: EyeChoice, available: &dyn Fn(Hash40) -> bool) -> Hash40 {
+    let mut expression = match choice {
+        EyeChoice::Mesh(mesh) => return mesh,
+        EyeChoice::Expression(expression) => expression,
+    };
+    loop {
+        let mesh = expression_mesh(stage, expression);
+        match expression.fallback() {
+            Some(fallback) if !available(mesh) => expression = fallback,
+            _ => return mesh,
+        }
+    }
+}
+
+// Opponents further away than this are ignored for the gaze
+pub const GAZE_RANGE: f32 = 80.0;
+
+pub unsafe fn is_shielding(boma: *mut BattleObjectModuleAccessor) -> bool {
+    let status = StatusModule::status_kind(boma);
+    status == *FIGHTER_STATUS_KIND_GUARD_ON || status == *FIGHTER_STATUS_KIND_GUARD || status == *FIGHTER_STATUS_KIND_GUARD_DAMAGE
+}
+
+// Which way the nearest opponent is relative to where this fighter faces.
+// Only entries whose fighter is running this frame are read (see hit_records::is_entry_present);
+// teammates and partner fighters (Nana) aren't opponents.
+pub unsafe fn opponent_gaze(boma: *mut BattleObjectModuleAccessor) -> Gaze {
+    let own_entry_id = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as u32;
+    let own_team = TeamModule::team_no(boma);
+    let own_x = PostureModule::pos_x(boma);
+    let own_y = PostureModule::pos_y(boma);
+
+    let mut nearest: Option<(f32, f32)> = None; // (distance, x offset)
+    for entry_id in 0..8u32 {
+        if entry_id == own_entry_id || !crate::gastly::hit_records::is_entry_present(entry_id) {
+            continue;
+        }
+        let other = smash::app::sv_battle_object::module_accessor(entry_id);
+        if other.is_null() || TeamModule::team_no(other) == own_team {
+            continue;
+        }
+        if smash::app::utility::get_kind(&mut *other) == *FIGHTER_KIND_NANA {
+            continue;
+        }
+        let dx = PostureModule::pos_x(other) - own_x;
+        let dy = PostureModule::pos_y(other) - own_y;
+        let distance = (dx * dx + dy * dy).sqrt();
+        if distance <= GAZE_RANGE && nearest.map_or(true, |(best, _)| distance < best) {
+            nearest = Some((distance, dx));
+        }
+    }
+
+    match nearest {
+        Some((_, dx)) if dx * PostureModule::lr(boma) >= 0.0 => Gaze::Forward,
+        Some(_) => Gaze::Back,
+        None => Gaze::Camera,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Always the shortest duration and the first `next` entry
+    fn shortest(min: i32, _max: i32) -> i32 {
+        min
+    }
+
+    fn run(engine: &mut ExpressionEngine, input: &ExpressionInput, frames: usize) -> Vec<EyeChoice> {
+        (0..frames).map(|_| engine.update(EvolutionStage::Gastly, input, &mut shortest)).collect()
+    }
+
+    fn reacting(expression: Expression) -> ExpressionInput {
+        ExpressionInput { reaction: Some(expression_mesh(EvolutionStage::Gastly, expression)), ..ExpressionInput::IDLE }
+    }
+
+    #[test]
+    fn idle_blinks_after_the_open_key() {
+        let mut engine = ExpressionEngine::new();
+        let frames = run(&mut engine, &ExpressionInput::IDLE, 120);
+        assert!(frames[..119].iter().all(|choice| *choice == EyeChoice::Expression(Open)));
+        assert_eq!(frames[119], EyeChoice::Expression(HalfBlink));
+    }
+
+    #[test]
+    fn reaction_interrupts_and_blinking_resumes_after_the_delay() {
+        let mut engine = ExpressionEngine::new();
+        run(&mut engine, &ExpressionInput::IDLE, 10);
+
+        let attack = expression_mesh(EvolutionStage::Gastly, Expression::Attack);
+        let frames = run(&mut engine, &reacting(Expression::Attack), 30);
+        assert!(frames.iter().all(|choice| *choice == EyeChoice::Mesh(attack)));
+        assert_eq!(engine.priority(), ExpressionPriority::Reaction);
+
+        // Back to an open eye for RESUME_DELAY, then the timeline's blink
+        let frames = run(&mut engine, &ExpressionInput::IDLE, 20);
+        assert_eq!(engine.priority(), ExpressionPriority::Idle);
+        assert!(frames[..19].iter().all(|choice| *choice == EyeChoice::Expression(Open)));
+        assert_eq!(frames[19], EyeChoice::Expression(HalfBlink));
+    }
+
+    #[test]
+    fn open_and_blink_vanilla_eyes_are_not_reactions() {
+        for expression in [Open, Blink, HalfBlink] {
+            let mut engine = ExpressionEngine::new();
+            let frames = run(&mut engine, &reacting(expression), 120);
+            assert_eq!(engine.priority(), ExpressionPriority::Idle);
+            assert_eq!(frames[119], EyeChoice::Expression(HalfBlink));
+        }
+    }
+
+    #[test]
+    fn every_reaction_expression_interrupts() {
+        for expression in Expression::REACTIONS {
+            let mut engine = ExpressionEngine::new();
+            let choice = engine.update(EvolutionStage::Gastly, &reacting(expression), &mut shortest);
+            assert_eq!(choice, EyeChoice::Mesh(expression_mesh(EvolutionStage::Gastly, expression)));
+            assert_eq!(engine.priority(), ExpressionPriority::Reaction);
+        }
+    }
+
+    #[test]
+    fn scripted_eye_wins_even_when_closed() {
+        let mut engine = ExpressionEngine::new();
+        let blink = expression_mesh(EvolutionStage::Gastly, Blink);
+        let input = ExpressionInput { scripted: Some(blink), reaction: Some(expression_mesh(EvolutionStage::Gastly, Expression::Ouch)), ..ExpressionInput::IDLE };
+        assert_eq!(engine.update(EvolutionStage::Gastly, &input, &mut shortest), EyeChoice::Mesh(blink));
+        assert_eq!(engine.priority(), ExpressionPriority::Reaction);
+    }
+}
@@ -0,0 +1,155 @@
+// src/gastly/expression_map.rs
+
+// How the game's own face animation becomes a custom eye.
+//
+// Purin's motions drive its 8 vanilla eye meshes; VANILLA_EYE_TABLE translates each one to an
+// Expression, and expression_mesh() picks the current stage's mesh for it. Motions whose vanilla eyes
+// don't suit the ghosts get a frame timeline in MOTION_EYE_TABLE, which wins over the vanilla eye.
+// Win poses in the table also take over the eye layer (handle_win_pose_eyes in mod.rs), so the
+// results screen shows them even when vanilla tracking is off.
+// Every other motion follows the vanilla eye; `[expressions] audit = true` (expression_audit.rs)
+// logs the motions where neither applies.
+//
+// Reviewed and left to the vanilla eye on purpose, since Purin's eye already opens, blinks and closes
+// on cue there and translates 1:1: win_1, win_1_wait, win_2, win_2_wait, lose (VANILLA_EYE_WIN_POSES),
+// entry_l, entry_r and the appeals (appeal_hi/s/lw, l and r).
+
+use once_cell::sync::Lazy;
+use smash::app::lua_bind::VisibilityModule;
+use smash::app::BattleObjectModuleAccessor;
+use smash::lib::lua_const::*;
+use smash::phx::Hash40;
+use skyline::libc::c_uint;
+
+use crate::gastly::constants::*;
+use crate::gastly::expression_engine::Expression;
+
+pub static VANILLA_EYE_TABLE: Lazy<[(Hash40, Expression); 8]> = Lazy::new(|| [
+    (*PURIN_VANILLA_EYE_N, Expression::Open),
+    (*PURIN_VANILLA_EYE_BLINK, Expression::Blink),
+    (*PURIN_VANILLA_EYE_HALFBLINK1, Expression::HalfBlink),
+    (*PURIN_VANILLA_EYE_ATTACK, Expression::Attack),
+    (*PURIN_VANILLA_EYE_CAPTURE, Expression::Capture),
+    (*PURIN_VANILLA_EYE_OUCH, Expression::Ouch),
+    (*PURIN_VANILLA_EYE_DOWN, Expression::Down),
+    (*PURIN_VANILLA_EYE_HEAVYATTACK, Expression::HeavyAttack),
+]);
+
+pub fn expression_for_vanilla_eye(vanilla_eye: Hash40) -> Option<Expression> {
+    VANILLA_EYE_TABLE.iter()
+        .find(|(mesh, _)| mesh.hash == vanilla_eye.hash)
+        .map(|(_, expression)| *expression)
+}
+
+// The vanilla eye the animation turned on. Sample before the vanilla eyes are hidden each frame.
+pub unsafe fn visible_vanilla_eye(boma: *mut BattleObjectModuleAccessor) -> Option<Hash40> {
+    VANILLA_EYE_TABLE.iter()
+        .map(|(mesh, _)| *mesh)
+        .find(|mesh| VisibilityModule::is_visible_mesh(boma, mesh.hash as c_uint))
+}
+
+// Inclusive motion frame range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionEyeKey {
+    pub first: i32,
+    pub last: i32,
+    pub expression: Expression,
+}
+
+const fn frames(first: i32, last: i32, expression: Expression) -> MotionEyeKey {
+    MotionEyeKey { first, last, expression }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionEyeTimeline {
+    pub motion: &'static str,
+    pub win_pose: bool,
+    pub keys: &'static [MotionEyeKey],
+}
+
+impl MotionEyeTimeline {
+    // None outside every key: the vanilla eye decides
+    pub fn expression_at(&self, frame: i32) -> Option<Expression> {
+        self.keys.iter()
+            .find(|key| frame >= key.first && frame <= key.last)
+            .map(|key| key.expression)
+    }
+}
+
+use Expression::{Open, HalfBlink, Blink};
+
+pub static MOTION_EYE_TABLE: [MotionEyeTimeline; 5] = [
+    // Dizzy
+    MotionEyeTimeline {
+        motion: "fura_sleep_start",
+        win_pose: false,
+        keys: &[frames(1, 2, Open), frames(3, 24, HalfBlink), frames(25, 30, Blink)],
+    },
+    MotionEyeTimeline {
+        motion: "fura_sleep_loop",
+        win_pose: false,
+        keys: &[frames(1, 78, Blink)],
+    },
+    MotionEyeTimeline {
+        motion: "fura_sleep_end",
+        win_pose: false,
+        keys: &[
+            frames(1, 1, Blink),
+            frames(2, 11, Open),
+            frames(12, 15, Blink),
+            frames(16, 17, HalfBlink),
+            frames(18, 58, Open),
+            frames(59, 59, HalfBlink),
+            frames(60, 62, Blink),
+            frames(63, 64, HalfBlink),
+            frames(65, 76, Open),
+        ],
+    },
+    // Victory pose 3 holds its half blink to the end
+    MotionEyeTimeline {
+        motion: "win_3",
+        win_pose: true,
+        keys: &[frames(1, 113, Blink), frames(114, 155, Open), frames(156, i32::MAX, HalfBlink)],
+    },
+    MotionEyeTimeline {
+        motion: "win_3_wait",
+        win_pose: true,
+        keys: &[frames(1, 52, HalfBlink), frames(53, 207, Blink), frames(208, 251, Open)],
+    },
+];
+
+pub fn motion_eye_timeline(motion: u64) -> Option<&'static MotionEyeTimeline> {
+    MOTION_EYE_TABLE.iter().find(|timeline| smash::hash40(timeline.motion) == motion)
+}
+
+// Win poses without a timeline: they take over the eye layer with the translated vanilla eye
+pub const VANILLA_EYE_WIN_POSES: [&str; 5] = ["win_1", "win_1_wait", "win_2", "win_2_wait", "lose"];
+
+// Rest closes the eyes over these frames
+pub const REST_EYES_CLOSED_FRAMES: (i32, i32) = (10, 60);
+
+// Expression the game state dictates, before the vanilla eye is looked at
+pub fn game_eye_expression(status: i32, motion: u64, frame: i32) -> Option<Expression> {
+    if status == *FIGHTER_STATUS_KIND_SPECIAL_LW &&
+       frame >= REST_EYES_CLOSED_FRAMES.0 && frame <= REST_EYES_CLOSED_FRAMES.1 {
+        return Some(Blink);
+    }
+    if let Some(expression) = motion_eye_timeline(motion).and_then(|timeline| timeline.expression_at(frame)) {
+        return Some(expression);
+    }
+    if status >= *FIGHTER_STATUS_KIND_ATTACK && status <= *FIGHTER_STATUS_KIND_ATTACK_LW4 {
+        return Some(Expression::Attack);
+    }
+    None
+}
+
+// Win poses override the eye layer outright. `vanilla_eye`: sampled before the vanilla eyes were hidden.
+pub fn win_pose_expression(motion: u64, frame: i32, vanilla_eye: Option<Hash40>) -> Option<Expression> {
+    if let Some(timeline) = motion_eye_timeline(motion).filter(|timeline| timeline.win_pose) {
+        return timeline.expression_at(frame);
+    }
+    if VANILLA_EYE_WIN_POSES.iter().any(|name| smash::hash40(name) == motion) {
+        return vanilla_eye.and_then(expression_for_vanilla_eye);
+    }
+    None
+}
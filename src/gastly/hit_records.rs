@@ -0,0 +1,288 @@
+// src/gastly/hit_records.rs
+
+// Precise hit attribution published by `hit_tracking_hook`.
+//
+// Every hit the game registers is pushed as an (attacker, defender, move, frame) record into a
+// small ring buffer. Consumers (dark effects on victims, rollout bombs, the hit-count evolution
+// condition) keep their own sequence cursor and read only records they haven't seen yet, instead
+// of guessing the victim from positions or damage deltas.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+pub const HIT_RECORD_CAPACITY: usize = 64;
+
+// Records older than this are ignored even if a consumer hasn't seen them (e.g. its state was just created)
+pub const HIT_RECORD_MAX_AGE_FRAMES: u32 = 2;
+
+#[derive(Debug, Clone, Copy)]
+pub struct HitRecord {
+    pub sequence: u64,
+    pub frame: u32,
+    pub attacker_entry_id: u32,
+    pub attacker_instance_key: u32,
+    pub attacker_color: u32,            // Costume slot, for per-slot data (palettes) beyond the instance key
+    pub defender_entry_id: Option<u32>, // None when the defender isn't a fighter (items, stage objects...)
+    pub attacker_status: i32,
+    pub attacker_motion: u64,
+    pub dark_move: Option<u32>,         // Dark move type if the attacker was a marked Gastly using one
+}
+
+pub struct HitRecordBuffer {
+    records: VecDeque<HitRecord>,
+    last_sequence: u64,
+}
+
+impl HitRecordBuffer {
+    pub fn new() -> Self {
+        Self {
+            records: VecDeque::with_capacity(HIT_RECORD_CAPACITY),
+            last_sequence: 0,
+        }
+    }
+
+    // Stores the record with the next sequence number and returns it
+    pub fn push(&mut self, mut record: HitRecord) -> u64 {
+        self.last_sequence += 1;
+        record.sequence = self.last_sequence;
+
+        if self.records.len() >= HIT_RECORD_CAPACITY {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+        self.last_sequence
+    }
+
+    pub fn last_sequence(&self) -> u64 {
+        self.last_sequence
+    }
+
+    // Records newer than `cursor` that are still fresh at `current_frame`
+    pub fn since(&self, cursor: u64, current_frame: u32) -> impl Iterator<Item = &HitRecord> {
+        self.records.iter().filter(move |record| {
+            record.sequence > cursor &&
+            current_frame.wrapping_sub(record.frame) <= HIT_RECORD_MAX_AGE_FRAMES
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+}
+
+pub static HIT_RECORDS: Lazy<Mutex<HitRecordBuffer>> = Lazy::new(|| Mutex::new(HitRecordBuffer::new()));
+
+// Shared frame counter. Every fighter reports once per frame, in whatever order the game runs
+// them, so a new frame starts when an entry that already reported this frame reports again.
+// Neither the order nor which slots are occupied matters (entry 0 isn't guaranteed to exist,
+// e.g. when P1 isn't playing), and fighters that skip a frame (dead, not loaded) are just absent.
+// A fighter that runs ahead of the one that started the frame reads one frame behind, and the
+// clock can lose a frame when fighters come and go; HIT_RECORD_MAX_AGE_FRAMES absorbs both.
+// Partner fighters sharing an entry id (Ice Climbers' Nana) read the clock without reporting,
+// otherwise every frame with Ice Climbers in it would count twice.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameClock {
+    frame: u32,
+    reported: u64, // Entry ids seen during `frame`, one bit each
+    previous: u64, // Entry ids seen during the frame before
+}
+
+impl FrameClock {
+    pub const fn new() -> Self {
+        Self { frame: 0, reported: 0, previous: 0 }
+    }
+
+    // Report that `entry_id`'s fighter line is running; returns the current frame
+    pub fn observe(&mut self, entry_id: u32, is_partner: bool) -> u32 {
+        if is_partner {
+            return self.frame;
+        }
+        let bit = 1u64 << (entry_id % 64);
+        if self.reported & bit != 0 || self.frame == 0 {
+            self.frame = self.frame.wrapping_add(1);
+            self.previous = self.reported;
+            self.reported = 0;
+        }
+        self.reported |= bit;
+        self.frame
+    }
+
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    // The entry's fighter ran this frame or the one before (so its battle object exists)
+    pub fn is_present(&self, entry_id: u32) -> bool {
+        (self.reported | self.previous) & (1u64 << (entry_id % 64)) != 0
+    }
+}
+
+static FRAME_CLOCK: Mutex<FrameClock> = parking_lot::const_mutex(FrameClock::new());
+static GLOBAL_FRAME: AtomicU32 = AtomicU32::new(0);
+
+// Called by every fighter's dark effects line
+pub fn observe_fighter_frame(entry_id: u32, is_partner: bool) -> u32 {
+    let frame = FRAME_CLOCK.lock().observe(entry_id, is_partner);
+    GLOBAL_FRAME.store(frame, Ordering::Relaxed);
+    frame
+}
+
+// Safe check before reading another entry's modules: absent entries never report
+pub fn is_entry_present(entry_id: u32) -> bool {
+    FRAME_CLOCK.lock().is_present(entry_id)
+}
+
+pub fn global_frame() -> u32 {
+    GLOBAL_FRAME.load(Ordering::Relaxed)
+}
+
+pub fn publish(record: HitRecord) -> u64 {
+    HIT_RECORDS.lock().push(HitRecord { frame: global_frame(), ..record })
+}
+
+// New hits taken by a fighter; advances the caller's cursor
+pub fn take_hits_against(defender_entry_id: u32, cursor: &mut u64) -> Vec<HitRecord> {
+    take_matching(cursor, |record| record.defender_entry_id == Some(defender_entry_id))
+}
+
+// New hits landed by a player instance; advances the caller's cursor
+pub fn take_hits_by(attacker_instance_key: u32, cursor: &mut u64) -> Vec<HitRecord> {
+    take_matching(cursor, |record| record.attacker_instance_key == attacker_instance_key)
+}
+
+fn take_matching(cursor: &mut u64, filter: impl Fn(&HitRecord) -> bool) -> Vec<HitRecord> {
+    let buffer = HIT_RECORDS.lock();
+    let records: Vec<HitRecord> = buffer.since(*cursor, global_frame())
+        .filter(|record| filter(record))
+        .copied()
+        .collect();
+    *cursor = buffer.last_sequence();
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs `frames` game frames, each reporting the (entry id, is partner) pairs `order(frame)` returns
+    fn simulate_reports(frames: u32, order: impl Fn(u32) -> Vec<(u32, bool)>) -> Vec<Vec<u32>> {
+        let mut clock = FrameClock::new();
+        (0..frames).map(|frame| {
+            order(frame).into_iter().map(|(entry_id, is_partner)| clock.observe(entry_id, is_partner)).collect()
+        }).collect()
+    }
+
+    fn simulate(frames: u32, order: impl Fn(u32) -> Vec<u32>) -> Vec<Vec<u32>> {
+        simulate_reports(frames, |frame| order(frame).into_iter().map(|entry_id| (entry_id, false)).collect())
+    }
+
+    // How far the clock moved during each frame (from the last report of the frame before)
+    fn ticks_per_frame(reported: &[Vec<u32>]) -> Vec<u32> {
+        let mut last = 0;
+        reported.iter().map(|frame| {
+            let end = frame.last().copied().unwrap_or(last);
+            let ticks = end - last;
+            last = end;
+            ticks
+        }).collect()
+    }
+
+    // Every report in a frame reads the same value, one more than the frame before
+    fn assert_in_step(reported: &[Vec<u32>]) {
+        for (index, frame) in reported.iter().enumerate() {
+            assert!(frame.iter().all(|value| *value == index as u32 + 1), "frame {}: {:?}", index, frame);
+        }
+    }
+
+    #[test]
+    fn entry_order() {
+        assert_in_step(&simulate(100, |_| vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn any_fixed_order() {
+        assert_in_step(&simulate(100, |_| vec![3, 1, 0, 2]));
+        assert_in_step(&simulate(100, |_| vec![7, 6, 5, 4, 3, 2, 1, 0]));
+    }
+
+    #[test]
+    fn order_changing_every_frame_still_ticks_once() {
+        let reported = simulate(200, |frame| {
+            let mut order = vec![0, 1, 2, 3, 4, 5, 6, 7];
+            order.rotate_left((frame * 3 % 8) as usize);
+            if frame % 2 == 0 {
+                order.reverse();
+            }
+            order
+        });
+        assert!(ticks_per_frame(&reported).iter().all(|ticks| *ticks == 1));
+    }
+
+    #[test]
+    fn missing_slots_and_late_fighters_behind_the_others() {
+        // No P1, P3 absent every third frame (e.g. dead), P4 joins late
+        assert_in_step(&simulate(120, |frame| {
+            let mut order = vec![1];
+            if frame % 3 != 0 {
+                order.push(2);
+            }
+            if frame >= 40 {
+                order.push(3);
+            }
+            order
+        }));
+    }
+
+    #[test]
+    fn fighters_coming_and_going_ahead_of_the_others_slip_at_most_a_frame() {
+        // P4 runs first from frame 20, P2 is gone between frames 60 and 80
+        let reported = simulate(120, |frame| {
+            let mut order = vec![3];
+            if frame < 20 {
+                order.clear();
+            }
+            if !(60..80).contains(&frame) {
+                order.push(1);
+            }
+            order
+        });
+        let ticks = ticks_per_frame(&reported);
+        assert!(ticks.iter().all(|ticks| *ticks <= 1));
+        assert!(ticks.iter().filter(|ticks| **ticks == 0).count() <= 2, "{:?}", ticks);
+    }
+
+    #[test]
+    fn ice_climbers_partner_sharing_an_entry_id_does_not_tick() {
+        // Popo and Nana are both entry 1, Nana runs right after Popo (or first, on some frames)
+        assert_in_step(&simulate_reports(100, |_| vec![(0, false), (1, false), (1, true), (2, false)]));
+        let reported = simulate_reports(100, |frame| {
+            if frame % 2 == 0 { vec![(1, true), (1, false), (0, false)] } else { vec![(0, false), (1, false), (1, true)] }
+        });
+        assert!(ticks_per_frame(&reported).iter().all(|ticks| *ticks == 1));
+    }
+
+    #[test]
+    fn entries_are_present_until_they_miss_a_whole_frame() {
+        let mut clock = FrameClock::new();
+        for _ in 0..3 {
+            clock.observe(0, false);
+            clock.observe(2, false);
+        }
+        assert!(clock.is_present(0) && clock.is_present(2));
+        assert!(!clock.is_present(1));
+
+        // Entry 2 stops reporting (dead, left): still present during the next frame, gone after
+        clock.observe(0, false);
+        assert!(clock.is_present(2));
+        clock.observe(0, false);
+        assert!(!clock.is_present(2));
+        assert!(clock.is_present(0));
+    }
+
+    #[test]
+    fn single_fighter() {
+        assert_in_step(&simulate(10, |_| vec![5]));
+    }
+}
@@ -0,0 +1,259 @@
+// src/gastly/icon_manager.rs
+
+// Position-sensitive overhead icons (Linking Cord, Everstone, Gengarite, Dynamax).
+//
+// Every icon is one entry in ICON_TABLE: its mesh, how long it stays up, its exclusivity group
+// (showing an icon hides the rest of its group), what moving does to it (cancel, or hide while away),
+// whether taking damage cancels it, and
+// the sound/flash played when it appears. Requests are queued during the frame and applied by
+// `tick`, which also runs the cancels and timers. The icon pose (rise-in / float-out over
+// ICON_ANIMATION_DURATION) comes from `icon_pose` and is applied with the billboarding (billboard.rs).
+
+use once_cell::sync::Lazy;
+use smash::app::BattleObjectModuleAccessor;
+use smash::phx::Hash40;
+
+use crate::gastly::constants::*;
+use crate::gastly::persist_sfx;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverheadIcon {
+    LinkingCord,          // Linking Cord command started
+    LinkingCordEvolving,  // Manual evolution in progress
+    Everstone,
+    EverstoneX,
+    Gengarite,
+    Dynamax,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveResponse {
+    Cancel, // Gone for good once the player moves
+    Hide,   // Hidden while away from where it appeared, shown again on return
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconGroup {
+    Overhead, // Everything drawn above the head; only one at a time
+}
+
+pub struct IconSpec {
+    pub icon: OverheadIcon,
+    pub mesh: &'static Lazy<Hash40>,
+    pub duration: i32, // Frames, including ICON_ANIMATION_DURATION
+    pub group: IconGroup,
+    pub on_move: MoveResponse,
+    pub cancel_on_damage: bool,
+    pub hides_readiness_icons: bool,
+    pub sound: Option<unsafe fn(*mut BattleObjectModuleAccessor)>,
+    pub flash: bool,
+}
+
+pub static ICON_TABLE: [IconSpec; 6] = [
+    IconSpec {
+        icon: OverheadIcon::LinkingCord,
+        mesh: &LINKING_CORD_ICON,
+        duration: LINKING_CORD_VISUAL_ICON_DURATION,
+        group: IconGroup::Overhead,
+        on_move: MoveResponse::Cancel,
+        cancel_on_damage: true,
+        hides_readiness_icons: false,
+        sound: Some(persist_sfx::play_linking_cord_sound),
+        flash: true,
+    },
+    IconSpec {
+        icon: OverheadIcon::LinkingCordEvolving,
+        mesh: &LINKING_CORD_ICON,
+        duration: MANUAL_EVO_HAUNTER_ICON_DURATION,
+        group: IconGroup::Overhead,
+        on_move: MoveResponse::Hide,
+        cancel_on_damage: true,
+        hides_readiness_icons: false,
+        sound: None,
+        flash: false,
+    },
+    IconSpec {
+        icon: OverheadIcon::Everstone,
+        mesh: &EVERSTONE_ICON,
+        duration: EVERSTONE_ICON_DURATION,
+        group: IconGroup::Overhead,
+        on_move: MoveResponse::Cancel,
+        cancel_on_damage: true,
+        hides_readiness_icons: true,
+        sound: Some(persist_sfx::play_everstone_sound),
+        flash: true,
+    },
+    IconSpec {
+        icon: OverheadIcon::EverstoneX,
+        mesh: &EVERSTONE_X_ICON,
+        duration: EVERSTONE_X_ICON_DURATION,
+        group: IconGroup::Overhead,
+        on_move: MoveResponse::Cancel,
+        cancel_on_damage: true,
+        hides_readiness_icons: true,
+        sound: Some(persist_sfx::play_everstone_x_sound),
+        flash: true,
+    },
+    IconSpec {
+        icon: OverheadIcon::Gengarite,
+        mesh: &GENGARITE_ICON,
+        duration: FS_MODE_ICON_DURATION,
+        group: IconGroup::Overhead,
+        on_move: MoveResponse::Cancel,
+        cancel_on_damage: true,
+        hides_readiness_icons: false,
+        sound: Some(persist_sfx::play_gengarite_sound),
+        flash: true,
+    },
+    IconSpec {
+        icon: OverheadIcon::Dynamax,
+        mesh: &DYNAMAX_ICON,
+        duration: FS_MODE_ICON_DURATION,
+        group: IconGroup::Overhead,
+        on_move: MoveResponse::Cancel,
+        cancel_on_damage: true,
+        hides_readiness_icons: false,
+        sound: Some(persist_sfx::play_dynamax_sound),
+        flash: true,
+    },
+];
+
+impl OverheadIcon {
+    pub fn spec(self) -> &'static IconSpec {
+        ICON_TABLE.iter().find(|spec| spec.icon == self).expect("every overhead icon has a table entry")
+    }
+}
+
+// Movement (in units) from where the icon appeared that cancels / hides it
+pub const ICON_MOVE_CANCEL_DISTANCE: f32 = 0.01;
+pub const ICON_MOVE_HIDE_DISTANCE: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActiveIcon {
+    pub icon: OverheadIcon,
+    pub age: i32,
+    pub remaining: i32,
+    pub anchor_x: f32,
+    pub anchor_y: f32,
+    pub hidden: bool, // MoveResponse::Hide and away from the anchor
+}
+
+impl ActiveIcon {
+    fn moved(&self, pos_x: f32, pos_y: f32, distance: f32) -> bool {
+        (pos_x - self.anchor_x).abs() > distance || (pos_y - self.anchor_y).abs() > distance
+    }
+}
+
+// Vertical offset (added to the icon joint's bind translation) and scale of the icon bone
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IconPose {
+    pub y_offset: f32,
+    pub scale: f32,
+}
+
+impl IconPose {
+    pub const REST: Self = Self { y_offset: 0.0, scale: 1.0 };
+}
+
+fn ease_out(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+// First half of ICON_ANIMATION_DURATION: rise into place and grow in.
+// Last half: keep rising and shrink out. In between the icon rests.
+pub fn icon_pose(age: i32, remaining: i32) -> IconPose {
+    let half = (ICON_ANIMATION_DURATION / 2).max(1) as f32;
+    let intro = ease_out(age as f32 / half);
+    let outro = ease_out(remaining as f32 / half);
+    IconPose {
+        y_offset: -ICON_VERTICAL_OFFSET * (1.0 - intro) + ICON_VERTICAL_OFFSET * (1.0 - outro),
+        scale: intro.min(outro).max(0.01),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IconManager {
+    queue: Vec<OverheadIcon>,
+    active: Vec<ActiveIcon>,
+}
+
+impl IconManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Show `icon` on the next tick, replacing its group
+    pub fn request(&mut self, icon: OverheadIcon) {
+        self.queue.retain(|queued| queued.spec().group != icon.spec().group);
+        self.queue.push(icon);
+    }
+
+    pub fn cancel(&mut self, icon: OverheadIcon) {
+        self.queue.retain(|queued| *queued != icon);
+        self.active.retain(|active| active.icon != icon);
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.active.clear();
+    }
+
+    // Shown, or requested this frame
+    pub fn is_active(&self, icon: OverheadIcon) -> bool {
+        self.queue.contains(&icon) || self.active.iter().any(|active| active.icon == icon)
+    }
+
+    pub fn any_active(&self) -> bool {
+        !self.queue.is_empty() || !self.active.is_empty()
+    }
+
+    // Some icon is on screen (not hidden, not just queued)
+    pub fn any_visible(&self) -> bool {
+        self.active.iter().any(|active| !active.hidden)
+    }
+
+    pub fn active(&self) -> &[ActiveIcon] {
+        &self.active
+    }
+
+    pub fn hides_readiness_icons(&self) -> bool {
+        self.active.iter().map(|active| active.icon)
+            .chain(self.queue.iter().copied())
+            .any(|icon| icon.spec().hides_readiness_icons)
+    }
+
+    // One frame: cancels first, then queued requests (anchored at the current position), then timers
+    pub fn tick(&mut self, pos_x: f32, pos_y: f32, in_damage: bool) {
+        self.active.retain(|active| {
+            let spec = active.icon.spec();
+            let moved = spec.on_move == MoveResponse::Cancel && active.moved(pos_x, pos_y, ICON_MOVE_CANCEL_DISTANCE);
+            !(spec.cancel_on_damage && in_damage) && !moved
+        });
+
+        for icon in std::mem::take(&mut self.queue) {
+            let spec = icon.spec();
+            self.active.retain(|active| active.icon.spec().group != spec.group);
+            self.active.push(ActiveIcon { icon, age: 0, remaining: spec.duration, anchor_x: pos_x, anchor_y: pos_y, hidden: false });
+        }
+
+        for active in self.active.iter_mut() {
+            active.hidden = active.icon.spec().on_move == MoveResponse::Hide && active.moved(pos_x, pos_y, ICON_MOVE_HIDE_DISTANCE);
+            active.age += 1;
+            active.remaining -= 1;
+        }
+        self.active.retain(|active| active.remaining > 0);
+    }
+
+    // Whether `mesh` should be visible (meshes can be shared between icons)
+    pub fn mesh_visible(&self, mesh: Hash40) -> bool {
+        self.active.iter().any(|active| !active.hidden && active.icon.spec().mesh.hash == mesh.hash)
+    }
+
+    // Pose of the newest shown icon (REST when none is up)
+    pub fn current_pose(&self) -> IconPose {
+        self.active.iter().rev()
+            .find(|active| !active.hidden)
+            .map_or(IconPose::REST, |active| icon_pose(active.age, active.remaining))
+    }
+}
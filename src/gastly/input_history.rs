@@ -0,0 +1,230 @@
+// src/gastly/input_history.rs
+
+// Per-player input history that the command matcher consults instead of single-frame triggers.
+//
+// Presses are taken from both the trigger check and the held-button edge, so a press during hitstop
+// (where the trigger can be missed) still registers. Each press is buffered for up to
+// `leniency_frames`: it is accepted if the command's guard/situation held on any frame within that
+// many frames of the press (e.g. pressed on the GUARD_ON -> GUARD change, or a frame before
+// shielding). Windows between presses can also be frozen while in hitstop.
+//
+//   [input]
+//   leniency_frames = 4
+//   freeze_windows_in_hitstop = true
+
+use std::collections::VecDeque;
+use parking_lot::RwLock;
+
+use crate::gastly::config::IniDocument;
+use crate::gastly::command_matcher::{CommandButton, CommandEvent, EvolutionCommand, InputFrame, InputSequenceMatcher};
+
+pub const DEFAULT_LENIENCY_FRAMES: i32 = 4;
+pub const MAX_LENIENCY_FRAMES: i32 = 15;
+const CONTEXT_HISTORY_LEN: usize = (MAX_LENIENCY_FRAMES as usize) * 2 + 1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputLeniencyConfig {
+    pub leniency_frames: i32,
+    pub freeze_windows_in_hitstop: bool,
+}
+
+impl InputLeniencyConfig {
+    pub const DEFAULT: Self = Self { leniency_frames: DEFAULT_LENIENCY_FRAMES, freeze_windows_in_hitstop: true };
+}
+
+pub fn input_leniency_from_config(config: &IniDocument) -> InputLeniencyConfig {
+    let section = match config.section("input") {
+        Some(section) => section,
+        None => return InputLeniencyConfig::DEFAULT,
+    };
+    InputLeniencyConfig {
+        leniency_frames: section.get_i32("leniency_frames")
+            .map(|frames| frames.clamp(0, MAX_LENIENCY_FRAMES))
+            .unwrap_or(DEFAULT_LENIENCY_FRAMES),
+        freeze_windows_in_hitstop: section.get_bool("freeze_windows_in_hitstop").unwrap_or(true),
+    }
+}
+
+pub static INPUT_LENIENCY: RwLock<InputLeniencyConfig> = parking_lot::const_rwlock(InputLeniencyConfig::DEFAULT);
+
+pub fn load_input_leniency() {
+    let config = crate::gastly::config::GASTLY_CONFIG.read();
+    *INPUT_LENIENCY.write() = input_leniency_from_config(&config);
+}
+
+// One frame of raw pad/status data. Button sets are bitmasks of CommandButton::bit().
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RawInputSample {
+    pub triggered: u8,
+    pub held: u8,
+    pub guarding: bool,
+    pub on_ground: bool,
+    pub in_hitstop: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ContextRecord {
+    clock: i32,
+    guarding: bool,
+    on_ground: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BufferedPress {
+    pub button: CommandButton,
+    pub clock: i32, // Command clock when pressed
+    pub age: i32,   // Game frames spent waiting in the buffer
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InputHistory {
+    clock: i32, // Frames counted for command windows (optionally frozen in hitstop)
+    last_held: u8,
+    contexts: VecDeque<ContextRecord>,
+    pending: Vec<BufferedPress>,
+}
+
+impl InputHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clock(&self) -> i32 {
+        self.clock
+    }
+
+    pub fn pending(&self) -> &[BufferedPress] {
+        &self.pending
+    }
+
+    pub fn clear(&mut self) {
+        self.last_held = 0;
+        self.contexts.clear();
+        self.pending.clear();
+    }
+
+    // Add one game frame: advance the clock and buffer every new press
+    pub fn record(&mut self, sample: RawInputSample, config: &InputLeniencyConfig) {
+        if !(sample.in_hitstop && config.freeze_windows_in_hitstop) {
+            self.clock += 1;
+        }
+
+        // A trigger for a button already down last frame is the same press seen late. The trigger
+        // can outlast the held bit (buffered trigger, sub-frame tap), so it counts as down too.
+        let down = sample.triggered | sample.held;
+        let pressed = down & !self.last_held;
+        self.last_held = down;
+
+        if self.contexts.len() >= CONTEXT_HISTORY_LEN {
+            self.contexts.pop_front();
+        }
+        self.contexts.push_back(ContextRecord { clock: self.clock, guarding: sample.guarding, on_ground: sample.on_ground });
+
+        for button in CommandButton::ALL {
+            if pressed & button.bit() != 0 {
+                self.pending.push(BufferedPress { button, clock: self.clock, age: 0 });
+            }
+        }
+    }
+
+    // The most permissive guard/situation seen within the leniency range around a press
+    fn lenient_frame(&self, press: &BufferedPress, leniency: i32) -> InputFrame {
+        let nearby = || self.contexts.iter().filter(|record| (record.clock - press.clock).abs() <= leniency);
+        let at_press = self.contexts.iter().rev().find(|record| record.clock <= press.clock).copied();
+
+        let (guarding, on_ground) = if nearby().any(|record| record.guarding && record.on_ground) {
+            (true, true)
+        } else if nearby().any(|record| !record.on_ground) {
+            (false, false)
+        } else {
+            at_press.map_or((false, true), |record| (record.guarding, record.on_ground))
+        };
+
+        InputFrame { frame: press.clock, pressed: Some(press.button), guarding, on_ground }
+    }
+
+    // Feed buffered presses to the matcher. A press is used as soon as some armed command accepts it,
+    // or dropped once it is older than the leniency. Window expiry is checked after the presses.
+    pub fn drain_into(
+        &mut self,
+        matcher: &mut InputSequenceMatcher,
+        armed: &dyn Fn(EvolutionCommand) -> bool,
+        config: &InputLeniencyConfig,
+    ) -> (Vec<CommandEvent>, Option<InputFrame>) {
+        let mut events = Vec::new();
+        let mut last_fed = None;
+        let mut still_pending = Vec::new();
+
+        for mut press in std::mem::take(&mut self.pending) {
+            let input = self.lenient_frame(&press, config.leniency_frames);
+            if matcher.accepts(&input, armed) || press.age >= config.leniency_frames {
+                events.extend(matcher.feed(&input, armed));
+                last_fed = Some(input);
+            } else {
+                press.age += 1;
+                still_pending.push(press);
+            }
+        }
+        self.pending = still_pending;
+
+        let (guarding, on_ground) = self.contexts.back().map_or((false, true), |record| (record.guarding, record.on_ground));
+        events.extend(matcher.feed(&InputFrame { frame: self.clock, pressed: None, guarding, on_ground }, armed));
+
+        (events, last_fed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPECIAL: u8 = 1 << 0; // CommandButton::Special.bit()
+
+    fn sample(triggered: u8, held: u8) -> RawInputSample {
+        RawInputSample { triggered, held, guarding: true, on_ground: true, in_hitstop: false }
+    }
+
+    fn presses(trace: &[RawInputSample]) -> usize {
+        let mut history = InputHistory::new();
+        for frame in trace {
+            history.record(*frame, &InputLeniencyConfig::DEFAULT);
+        }
+        history.pending().len()
+    }
+
+    #[test]
+    fn held_press_counts_once() {
+        assert_eq!(presses(&[sample(SPECIAL, SPECIAL), sample(0, SPECIAL), sample(0, SPECIAL), sample(0, 0)]), 1);
+    }
+
+    #[test]
+    fn lingering_trigger_counts_once() {
+        // Sub-frame tap: the trigger is seen for two frames and the held bit never
+        assert_eq!(presses(&[sample(SPECIAL, 0), sample(SPECIAL, 0), sample(0, 0)]), 1);
+        // Buffered trigger outlasting a one-frame hold
+        assert_eq!(presses(&[sample(SPECIAL, SPECIAL), sample(SPECIAL, 0), sample(SPECIAL, 0), sample(0, 0)]), 1);
+    }
+
+    #[test]
+    fn single_tap_does_not_complete_double_press() {
+        let mut history = InputHistory::new();
+        let mut matcher = InputSequenceMatcher::new(vec![crate::gastly::command_matcher::CommandPattern::new(
+            EvolutionCommand::Everstone,
+            &[CommandButton::Special, CommandButton::Special],
+            crate::gastly::command_matcher::InputContext::GuardOnGround,
+            30,
+        )]);
+        let config = InputLeniencyConfig::DEFAULT;
+        let mut events = Vec::new();
+        for frame in [sample(SPECIAL, 0), sample(SPECIAL, 0), sample(SPECIAL, 0), sample(0, 0), sample(0, 0)] {
+            history.record(frame, &config);
+            events.extend(history.drain_into(&mut matcher, &|_| true, &config).0);
+        }
+        assert_eq!(events, vec![CommandEvent::Started(EvolutionCommand::Everstone)]);
+    }
+
+    #[test]
+    fn separate_taps_count_twice() {
+        assert_eq!(presses(&[sample(SPECIAL, 0), sample(0, 0), sample(SPECIAL, 0)]), 2);
+    }
+}
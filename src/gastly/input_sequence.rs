@@ -0,0 +1,111 @@
+// src/gastly/input_sequence.rs
+
+// Declarative button-sequence commands (Linking Cord, Everstone, evolution cancel, Gengarite, Dynamax).
+//
+// The patterns and the InputSequenceMatcher live in command_matcher.rs, which has no game
+// dependencies so synthetic input traces can drive it on the host. This module holds the stock
+// bindings and update_command_input(), the per-frame bridge that samples the pad into the player's
+// InputHistory (buffering and leniency, see input_history.rs) and stores the resulting events on the
+// player state. The stock bindings below can be remapped per player or slot, see command_bindings.rs.
+
+use smash::app::BattleObjectModuleAccessor;
+use smash::app::lua_bind::{ControlModule, StatusModule, StopModule, WorkModule};
+use smash::lib::lua_const::*;
+
+use crate::gastly::constants::{DOUBLE_PRESS_WINDOW, EVO_CANCEL_DOWN_TAUNT_WINDOW};
+use crate::gastly::player_state::{PlayerEvolutionState, EvolutionStage};
+use crate::gastly::input_history::{RawInputSample, INPUT_LENIENCY};
+use crate::gastly::item_rules::item_rule_for_stage;
+
+pub use crate::gastly::command_matcher::{
+    CommandButton, CommandEvent, CommandPattern, EvolutionCommand, InputContext, InputFrame, InputSequenceMatcher,
+};
+
+fn pad_button(button: CommandButton) -> i32 {
+    match button {
+        CommandButton::Special => *CONTROL_PAD_BUTTON_SPECIAL,
+        CommandButton::AppealHi => *CONTROL_PAD_BUTTON_APPEAL_HI,
+        CommandButton::AppealLw => *CONTROL_PAD_BUTTON_APPEAL_LW,
+        CommandButton::AppealSL => *CONTROL_PAD_BUTTON_APPEAL_S_L,
+        CommandButton::AppealSR => *CONTROL_PAD_BUTTON_APPEAL_S_R,
+    }
+}
+
+// The stock bindings
+pub fn default_patterns() -> Vec<CommandPattern> {
+    use CommandButton::*;
+    vec![
+        CommandPattern::new(EvolutionCommand::LinkingCord, &[AppealHi, AppealHi], InputContext::GuardOnGround, DOUBLE_PRESS_WINDOW),
+        CommandPattern::new(EvolutionCommand::Everstone, &[Special, Special], InputContext::GuardOnGround, DOUBLE_PRESS_WINDOW),
+        CommandPattern::new(EvolutionCommand::CancelEvolution, &[AppealLw, AppealLw], InputContext::GuardOnGroundOrAir, EVO_CANCEL_DOWN_TAUNT_WINDOW),
+        CommandPattern::new(EvolutionCommand::Gengarite, &[AppealSL, AppealSL], InputContext::GuardOnGround, DOUBLE_PRESS_WINDOW),
+        CommandPattern::new(EvolutionCommand::Dynamax, &[AppealSR, AppealSR], InputContext::GuardOnGround, DOUBLE_PRESS_WINDOW),
+    ]
+}
+
+impl Default for InputSequenceMatcher {
+    fn default() -> Self {
+        Self::new(default_patterns())
+    }
+}
+
+// Whether the player's current state lets a command be entered
+pub fn command_armed(player_state: &PlayerEvolutionState, command: EvolutionCommand) -> bool {
+    match command {
+        // Which transitions allow the Linking Cord / Everstone comes from the item rules
+        EvolutionCommand::LinkingCord => {
+            item_rule_for_stage(player_state.stage).map_or(false, |rule| rule.linking_cord)
+                && !player_state.is_evolving && !player_state.linking_cord_active
+        }
+        EvolutionCommand::Everstone => {
+            item_rule_for_stage(player_state.stage).map_or(false, |rule| rule.everstone)
+                && !player_state.is_evolving && !player_state.linking_cord_active
+        }
+        EvolutionCommand::CancelEvolution => player_state.is_evolving,
+        EvolutionCommand::Gengarite | EvolutionCommand::Dynamax => player_state.stage == EvolutionStage::Gengar,
+    }
+}
+
+pub unsafe fn sample_raw_input(boma: *mut BattleObjectModuleAccessor) -> RawInputSample {
+    let status = StatusModule::status_kind(boma);
+    let mut sample = RawInputSample {
+        guarding: status == *FIGHTER_STATUS_KIND_GUARD || status == *FIGHTER_STATUS_KIND_GUARD_ON,
+        on_ground: StatusModule::situation_kind(boma) == *SITUATION_KIND_GROUND,
+        in_hitstop: StopModule::is_stop(boma),
+        ..Default::default()
+    };
+    for button in CommandButton::ALL {
+        if ControlModule::check_button_on_trriger(boma, pad_button(button)) {
+            sample.triggered |= button.bit();
+        }
+        if ControlModule::check_button_on(boma, pad_button(button)) {
+            sample.held |= button.bit();
+        }
+    }
+    sample
+}
+
+// Sample this frame's input and store the command events for the icon/evolution handlers
+pub unsafe fn update_command_input(boma: *mut BattleObjectModuleAccessor, player_state: &mut PlayerEvolutionState) {
+    // Resolve the player's bindings (config remaps) once per entry/slot
+    let binding_key = (
+        WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as u32,
+        WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR) as u32,
+    );
+    if player_state.command_bindings_for != Some(binding_key) {
+        player_state.command_matcher = InputSequenceMatcher::new(crate::gastly::command_bindings::patterns_for(binding_key.0, binding_key.1));
+        player_state.command_bindings_for = Some(binding_key);
+    }
+
+    let leniency = *INPUT_LENIENCY.read();
+    player_state.input_history.record(sample_raw_input(boma), &leniency);
+
+    let armed: Vec<EvolutionCommand> = EvolutionCommand::ALL.iter().copied()
+        .filter(|command| command_armed(player_state, *command))
+        .collect();
+    let is_armed = |command: EvolutionCommand| armed.contains(&command);
+
+    let (events, last_fed) = player_state.input_history.drain_into(&mut player_state.command_matcher, &is_armed, &leniency);
+    player_state.command_events = events;
+    player_state.last_command_input = last_fed.unwrap_or_default();
+}
@@ -0,0 +1,190 @@
+// src/gastly/item_rules.rs
+
+// Linking Cord / Everstone rules per stage transition.
+//
+// The defaults match the original hardcoded behaviour: Linking Cord only for Haunter -> Gengar,
+// free and unlimited, consuming an active Everstone; Everstone usable as Gastly and Haunter.
+// Every part can be changed per transition in config.ini:
+//
+//   [item_rules.gastly_haunter]
+//   linking_cord = true          # allow manual evolution for this transition
+//   required_progress = 0.5      # fraction of the damage/hit requirement needed first (0-1)
+//   damage_cost = 10             # percent damage taken when used
+//   cooldown_frames = 600        # frames between uses
+//   uses_per_stock = 1           # 0 = unlimited
+//   everstone = true             # Everstone can be toggled at this stage
+//   everstone_blocks_linking_cord = false   # true: Everstone must be turned off first; false: the cord consumes it
+//
+//   [item_rules.haunter_gengar]
+//   ...
+
+use parking_lot::RwLock;
+
+use crate::gastly::config::{IniDocument, IniSection};
+use crate::gastly::player_state::EvolutionStage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageTransition {
+    GastlyToHaunter,
+    HaunterToGengar,
+}
+
+impl StageTransition {
+    pub const ALL: [StageTransition; 2] = [StageTransition::GastlyToHaunter, StageTransition::HaunterToGengar];
+
+    pub fn from_stage(stage: EvolutionStage) -> Option<Self> {
+        match stage {
+            EvolutionStage::Gastly => Some(StageTransition::GastlyToHaunter),
+            EvolutionStage::Haunter => Some(StageTransition::HaunterToGengar),
+            EvolutionStage::Gengar => None,
+        }
+    }
+
+    pub fn target(self) -> EvolutionStage {
+        match self {
+            StageTransition::GastlyToHaunter => EvolutionStage::Haunter,
+            StageTransition::HaunterToGengar => EvolutionStage::Gengar,
+        }
+    }
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    fn config_key(self) -> &'static str {
+        match self {
+            StageTransition::GastlyToHaunter => "gastly_haunter",
+            StageTransition::HaunterToGengar => "haunter_gengar",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransitionRule {
+    pub linking_cord: bool,
+    pub required_progress: f32,
+    pub damage_cost: f32,
+    pub cooldown_frames: i32,
+    pub uses_per_stock: u32,
+    pub everstone: bool,
+    pub everstone_blocks_linking_cord: bool,
+}
+
+impl TransitionRule {
+    const BASE: Self = Self {
+        linking_cord: false,
+        required_progress: 0.0,
+        damage_cost: 0.0,
+        cooldown_frames: 0,
+        uses_per_stock: 0,
+        everstone: true,
+        everstone_blocks_linking_cord: false,
+    };
+}
+
+pub const DEFAULT_ITEM_RULES: [TransitionRule; 2] = [
+    TransitionRule::BASE,                                      // Gastly -> Haunter: Everstone only
+    TransitionRule { linking_cord: true, ..TransitionRule::BASE }, // Haunter -> Gengar
+];
+
+fn apply_rule_section(rule: &mut TransitionRule, section: &IniSection) {
+    if let Some(value) = section.get_bool("linking_cord") {
+        rule.linking_cord = value;
+    }
+    if let Some(value) = section.get_f32("required_progress") {
+        rule.required_progress = value.clamp(0.0, 1.0);
+    }
+    if let Some(value) = section.get_f32("damage_cost") {
+        rule.damage_cost = value.max(0.0);
+    }
+    if let Some(value) = section.get_u32("cooldown_frames") {
+        rule.cooldown_frames = value as i32;
+    }
+    if let Some(value) = section.get_u32("uses_per_stock") {
+        rule.uses_per_stock = value;
+    }
+    if let Some(value) = section.get_bool("everstone") {
+        rule.everstone = value;
+    }
+    if let Some(value) = section.get_bool("everstone_blocks_linking_cord") {
+        rule.everstone_blocks_linking_cord = value;
+    }
+}
+
+pub fn build_item_rules(config: &IniDocument) -> [TransitionRule; 2] {
+    let mut rules = DEFAULT_ITEM_RULES;
+    for transition in StageTransition::ALL {
+        if let Some(section) = config.section(&format!("item_rules.{}", transition.config_key())) {
+            apply_rule_section(&mut rules[transition.index()], section);
+        }
+    }
+    rules
+}
+
+static ITEM_RULES: RwLock<[TransitionRule; 2]> = parking_lot::const_rwlock(DEFAULT_ITEM_RULES);
+
+pub fn load_item_rules() {
+    let config = crate::gastly::config::GASTLY_CONFIG.read();
+    *ITEM_RULES.write() = build_item_rules(&config);
+}
+
+pub fn item_rule(transition: StageTransition) -> TransitionRule {
+    ITEM_RULES.read()[transition.index()]
+}
+
+// Rule for the transition out of a stage (None for Gengar)
+pub fn item_rule_for_stage(stage: EvolutionStage) -> Option<TransitionRule> {
+    StageTransition::from_stage(stage).map(item_rule)
+}
+
+// Linking Cord uses this stock (reset on respawn)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LinkingCordUsage {
+    pub uses: [u32; 2],
+    pub last_use_frame: Option<i32>,
+}
+
+impl LinkingCordUsage {
+    pub fn record(&mut self, transition: StageTransition, frame: i32) {
+        self.uses[transition.index()] += 1;
+        self.last_use_frame = Some(frame);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinkingCordCheck {
+    Allowed { consumes_everstone: bool },
+    NotAllowed,
+    BlockedByEverstone,
+    NeedsProgress,
+    CoolingDown,
+    NoUsesLeft,
+}
+
+// `progress` is the current stage's evolution progress (0-1)
+pub fn check_linking_cord(
+    rule: &TransitionRule,
+    transition: StageTransition,
+    usage: &LinkingCordUsage,
+    progress: f32,
+    everstone_active: bool,
+    frame: i32,
+) -> LinkingCordCheck {
+    if !rule.linking_cord {
+        return LinkingCordCheck::NotAllowed;
+    }
+    if rule.uses_per_stock > 0 && usage.uses[transition.index()] >= rule.uses_per_stock {
+        return LinkingCordCheck::NoUsesLeft;
+    }
+    if rule.cooldown_frames > 0 && usage.last_use_frame.map_or(false, |last| frame - last < rule.cooldown_frames) {
+        return LinkingCordCheck::CoolingDown;
+    }
+    if progress < rule.required_progress {
+        return LinkingCordCheck::NeedsProgress;
+    }
+    let everstone_in_play = everstone_active && rule.everstone;
+    if everstone_in_play && rule.everstone_blocks_linking_cord {
+        return LinkingCordCheck::BlockedByEverstone;
+    }
+    LinkingCordCheck::Allowed { consumes_everstone: everstone_in_play }
+}
@@ -0,0 +1,304 @@
+// src/gastly/markers.rs
+
+// Costume slot markers (mods:/fighter/purin/model/body/cXX/gastly.marker and shiny.marker).
+//
+// A marker's presence is what enables the mod on a slot; an empty marker behaves exactly like
+// before. Markers can optionally carry key/value metadata (same syntax as config.ini, no section):
+//
+//   variant = shiny                  # regular | shiny | custom
+//   aura_color = 0.42, 0.75, 1.3     # Gastly aura RGB override
+//   chain = gastly, haunter          # evolution chain (default: gastly, haunter, gengar)
+//   subsystems = evolution, aura     # enabled subsystems (default: all; unknown names are ignored)
+//   palette = shadow                 # named shiny palette (see palettes.rs; shiny slots default to "shiny")
+//
+// shiny.marker keys override gastly.marker keys for the same slot. Values that can't be used are
+// listed in MARKER_REPORT_PATH.
+
+use std::collections::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use smash::app::lua_bind::WorkModule;
+use smash::app::BattleObjectModuleAccessor;
+use smash::lib::lua_const::*;
+
+use crate::gastly::config::{parse_ini, IniSection};
+use crate::gastly::player_state::EvolutionStage;
+
+pub const GASTLY_MARKER_FILE: &str = "gastly.marker";
+pub const SHINY_MARKER_FILE: &str = "shiny.marker";
+pub const MARKER_REPORT_PATH: &str = "sd:/ultimate/gastly/marker_report.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarkerVariant {
+    Regular,
+    Shiny,
+    Custom, // No shiny effects; meant to be paired with aura_color
+}
+
+impl MarkerVariant {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "regular" | "normal" => Some(Self::Regular),
+            "shiny" => Some(Self::Shiny),
+            "custom" => Some(Self::Custom),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarkerSubsystems {
+    pub evolution: bool,
+    pub dark_moves: bool,
+    pub aura: bool,
+}
+
+impl MarkerSubsystems {
+    pub const ALL: Self = Self { evolution: true, dark_moves: true, aura: true };
+
+    const NAMES: [&'static str; 3] = ["evolution", "dark_moves", "aura"];
+
+    // Unknown names are skipped (and reported); a list with nothing but unknown names keeps everything on
+    fn parse(items: &[String], problems: &mut Vec<String>) -> Self {
+        for item in items.iter().filter(|item| !Self::NAMES.contains(&item.as_str())) {
+            problems.push(format!("unknown subsystem '{}' ignored (use {})", item, Self::NAMES.join(", ")));
+        }
+        if !items.is_empty() && !items.iter().any(|item| Self::NAMES.contains(&item.as_str())) {
+            return Self::ALL;
+        }
+        Self {
+            evolution: items.iter().any(|item| item == "evolution"),
+            dark_moves: items.iter().any(|item| item == "dark_moves"),
+            aura: items.iter().any(|item| item == "aura"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotMarker {
+    pub variant: MarkerVariant,
+    pub aura_color: Option<[f32; 3]>,
+    pub final_stage: EvolutionStage, // Last stage of the evolution chain
+    pub subsystems: MarkerSubsystems,
+    pub palette: Option<String>,
+}
+
+impl Default for SlotMarker {
+    fn default() -> Self {
+        Self {
+            variant: MarkerVariant::Regular,
+            aura_color: None,
+            final_stage: EvolutionStage::Gengar,
+            subsystems: MarkerSubsystems::ALL,
+            palette: None,
+        }
+    }
+}
+
+impl SlotMarker {
+    pub fn is_shiny(&self) -> bool {
+        self.variant == MarkerVariant::Shiny
+    }
+
+    pub fn allows_stage(&self, stage: EvolutionStage) -> bool {
+        self.settings().allows_stage(stage)
+    }
+
+    pub fn settings(&self) -> MarkerSettings {
+        MarkerSettings {
+            variant: self.variant,
+            aura_color: self.aura_color,
+            final_stage: self.final_stage,
+            subsystems: self.subsystems,
+        }
+    }
+}
+
+// Everything but the palette name, for the per-frame checks (see marker_for)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarkerSettings {
+    pub variant: MarkerVariant,
+    pub aura_color: Option<[f32; 3]>,
+    pub final_stage: EvolutionStage,
+    pub subsystems: MarkerSubsystems,
+}
+
+impl MarkerSettings {
+    pub fn allows_stage(&self, stage: EvolutionStage) -> bool {
+        stage_index(stage) <= stage_index(self.final_stage)
+    }
+}
+
+fn stage_index(stage: EvolutionStage) -> u8 {
+    match stage {
+        EvolutionStage::Gastly => 0,
+        EvolutionStage::Haunter => 1,
+        EvolutionStage::Gengar => 2,
+    }
+}
+
+fn parse_stage(value: &str) -> Option<EvolutionStage> {
+    match value {
+        "gastly" => Some(EvolutionStage::Gastly),
+        "haunter" => Some(EvolutionStage::Haunter),
+        "gengar" => Some(EvolutionStage::Gengar),
+        _ => None,
+    }
+}
+
+// Chains always start at Gastly and can't skip a stage
+fn parse_chain(items: &[String]) -> Option<EvolutionStage> {
+    let stages: Option<Vec<EvolutionStage>> = items.iter().map(|item| parse_stage(item)).collect();
+    let stages = stages?;
+    let in_order = stages.iter().enumerate().all(|(index, stage)| stage_index(*stage) == index as u8);
+    if stages.is_empty() || !in_order {
+        return None;
+    }
+    stages.last().copied()
+}
+
+fn apply_marker_section(marker: &mut SlotMarker, section: &IniSection, variant_set: &mut bool, problems: &mut Vec<String>) {
+    if let Some(variant) = section.get("variant").and_then(MarkerVariant::parse) {
+        marker.variant = variant;
+        *variant_set = true;
+    }
+    if let Some(color) = section.get_f32_list("aura_color").filter(|c| c.len() == 3) {
+        marker.aura_color = Some([color[0], color[1], color[2]]);
+    }
+    if let Some(items) = section.get_list("chain") {
+        match parse_chain(&items) {
+            Some(final_stage) => marker.final_stage = final_stage,
+            None => problems.push(format!("chain '{}' ignored (gastly first, no skipped stages)", items.join(", "))),
+        }
+    }
+    if let Some(items) = section.get_list("subsystems") {
+        marker.subsystems = MarkerSubsystems::parse(&items, problems);
+    }
+    if let Some(palette) = section.get("palette").filter(|name| !name.is_empty()) {
+        marker.palette = Some(palette.to_ascii_lowercase());
+    }
+}
+
+// Build a slot's marker from the raw marker files (None = file absent). Empty files give the defaults.
+// Also returns the values that were ignored.
+pub fn parse_slot_marker(gastly_marker: Option<&str>, shiny_marker: Option<&str>) -> (SlotMarker, Vec<String>) {
+    let mut marker = SlotMarker::default();
+    let mut variant_set = false;
+    let mut problems = Vec::new();
+
+    for text in [gastly_marker, shiny_marker].iter().flatten() {
+        let document = parse_ini(text);
+        for section in document.sections.iter().filter(|section| section.name.is_empty()) {
+            apply_marker_section(&mut marker, section, &mut variant_set, &mut problems);
+        }
+    }
+
+    // Presence of shiny.marker still means shiny unless a variant was given explicitly
+    if !variant_set && shiny_marker.is_some() {
+        marker.variant = MarkerVariant::Shiny;
+    }
+
+    (marker, problems)
+}
+
+// Parsed markers per costume slot (only slots that have a marker file)
+static SLOT_MARKERS: Lazy<RwLock<HashMap<usize, SlotMarker>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+fn read_marker(path: &str) -> Option<String> {
+    std::fs::read(path).ok().map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+// Scan every slot for markers and fill MARKED_COLORS / SHINY_COLORS.
+// Marked slots don't have to be contiguous.
+pub fn load_slot_markers(fighter_name: &str) {
+    let mut markers = HashMap::new();
+    let mut report = Vec::new();
+
+    for color_id in 0..256usize {
+        let slot_dir = format!("mods:/fighter/{}/model/body/c{:02}", fighter_name, color_id);
+        let gastly_marker = read_marker(&format!("{}/{}", slot_dir, GASTLY_MARKER_FILE));
+        let shiny_marker = read_marker(&format!("{}/{}", slot_dir, SHINY_MARKER_FILE));
+        if gastly_marker.is_none() && shiny_marker.is_none() {
+            continue;
+        }
+
+        let (marker, problems) = parse_slot_marker(gastly_marker.as_deref(), shiny_marker.as_deref());
+        report.extend(problems.into_iter().map(|problem| format!("c{:02}: {}", color_id, problem)));
+        unsafe {
+            crate::MARKED_COLORS[color_id] = gastly_marker.is_some();
+            crate::SHINY_COLORS[color_id] = marker.is_shiny();
+        }
+        markers.insert(color_id, marker);
+    }
+
+    *SLOT_MARKERS.write() = markers;
+    crate::gastly::config::write_report(MARKER_REPORT_PATH, &report);
+}
+
+pub fn slot_marker(color_id: usize) -> SlotMarker {
+    SLOT_MARKERS.read().get(&color_id).cloned().unwrap_or_default()
+}
+
+// Called every frame, so only the Copy settings are handed out
+pub unsafe fn marker_for(boma: *mut BattleObjectModuleAccessor) -> MarkerSettings {
+    let color_id = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR) as usize;
+    SLOT_MARKERS.read()
+        .get(&color_id)
+        .map_or_else(|| SlotMarker::default().settings(), SlotMarker::settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(gastly_marker: Option<&str>, shiny_marker: Option<&str>) -> SlotMarker {
+        parse_slot_marker(gastly_marker, shiny_marker).0
+    }
+
+    #[test]
+    fn empty_marker_gives_the_defaults() {
+        assert_eq!(parse(Some(""), None), SlotMarker::default());
+        assert!(parse(Some(""), Some("")).is_shiny());
+    }
+
+    #[test]
+    fn shiny_marker_overrides_gastly_marker() {
+        let gastly = "variant = custom\naura_color = 1, 0, 0\nchain = gastly, haunter\npalette = shadow\n";
+        let shiny = "aura_color = 0, 0, 1\npalette = Frost\n";
+        let marker = parse(Some(gastly), Some(shiny));
+        assert_eq!(marker.aura_color, Some([0.0, 0.0, 1.0]));
+        assert_eq!(marker.palette.as_deref(), Some("frost"));
+        // Keys shiny.marker doesn't set keep the gastly.marker values, an explicit variant included
+        assert_eq!(marker.variant, MarkerVariant::Custom);
+        assert_eq!(marker.final_stage, EvolutionStage::Haunter);
+    }
+
+    #[test]
+    fn chains_start_at_gastly_and_keep_their_order() {
+        let chain = |items: &[&str]| parse_chain(&items.iter().map(|item| item.to_string()).collect::<Vec<_>>());
+        assert_eq!(chain(&["gastly"]), Some(EvolutionStage::Gastly));
+        assert_eq!(chain(&["gastly", "haunter", "gengar"]), Some(EvolutionStage::Gengar));
+        assert_eq!(chain(&["haunter", "gastly"]), None);
+        assert_eq!(chain(&["gastly", "gengar"]), None);
+        assert_eq!(chain(&["gastly", "ghost"]), None);
+        assert_eq!(chain(&[]), None);
+    }
+
+    #[test]
+    fn out_of_order_chain_is_reported_and_keeps_the_default() {
+        let (marker, problems) = parse_slot_marker(Some("chain = gengar, haunter, gastly\n"), None);
+        assert_eq!(marker.final_stage, EvolutionStage::Gengar);
+        assert_eq!(problems.len(), 1);
+    }
+
+    #[test]
+    fn subsystems_with_only_unknown_names_keep_everything_on() {
+        let (marker, problems) = parse_slot_marker(Some("subsystems = evolutoin, darkmoves\n"), None);
+        assert_eq!(marker.subsystems, MarkerSubsystems::ALL);
+        assert_eq!(problems.len(), 2);
+
+        let (marker, problems) = parse_slot_marker(Some("subsystems = aura, evolutoin\n"), None);
+        assert_eq!(marker.subsystems, MarkerSubsystems { evolution: false, dark_moves: false, aura: true });
+        assert_eq!(problems.len(), 1);
+    }
+}
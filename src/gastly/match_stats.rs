@@ -0,0 +1,278 @@
+// src/gastly/match_stats.rs
+
+// Per-match evolution stats for every Gastly, and the results-screen hand-off.
+//
+// Stats start over on the fighter's ENTRY status and are sampled every battle frame. On the results
+// screen the player state has been reset by the new results fighter's on_start, so the stage shown
+// (portrait, stock icons, victory/lose pose meshes) is restored from results_stage() and a summary of
+// every Gastly in the match is written to MATCH_SUMMARY_PATH.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use smash::lib::lua_const::*;
+
+use crate::gastly::player_state::EvolutionStage;
+
+pub const MATCH_SUMMARY_PATH: &str = "sd:/ultimate/gastly/match_summary.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinalSmashForm {
+    Gengar, // Vanilla-style final smash without a Mega/Gigantamax selection
+    MegaGengar,
+    GigaGengar,
+}
+
+impl FinalSmashForm {
+    fn name(self) -> &'static str {
+        match self {
+            FinalSmashForm::Gengar => "gengar",
+            FinalSmashForm::MegaGengar => "mega_gengar",
+            FinalSmashForm::GigaGengar => "giga_gengar",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchStats {
+    pub entry_id: u32,
+    pub color_id: u32,
+    pub is_shiny: bool,
+    pub frames_elapsed: u32,
+    pub haunter_frame: Option<u32>, // Frames from entry until first reaching Haunter
+    pub gengar_frame: Option<u32>,
+    pub highest_stage: EvolutionStage,
+    pub last_stage: EvolutionStage,
+    pub evolution_cancels: u32,
+    pub everstone_toggles: u32,
+    pub final_smash_forms: Vec<FinalSmashForm>, // One per final smash, in order
+    in_final_smash: bool,
+}
+
+impl MatchStats {
+    pub fn new(entry_id: u32, color_id: u32, is_shiny: bool) -> Self {
+        Self {
+            entry_id,
+            color_id,
+            is_shiny,
+            frames_elapsed: 0,
+            haunter_frame: None,
+            gengar_frame: None,
+            highest_stage: EvolutionStage::Gastly,
+            last_stage: EvolutionStage::Gastly,
+            evolution_cancels: 0,
+            everstone_toggles: 0,
+            final_smash_forms: Vec::new(),
+            in_final_smash: false,
+        }
+    }
+
+    pub fn record_frame(&mut self, stage: EvolutionStage, final_smash: Option<FinalSmashForm>) {
+        self.frames_elapsed += 1;
+        self.last_stage = stage;
+        if stage_rank(stage) > stage_rank(self.highest_stage) {
+            self.highest_stage = stage;
+        }
+        match stage {
+            EvolutionStage::Haunter if self.haunter_frame.is_none() => self.haunter_frame = Some(self.frames_elapsed),
+            EvolutionStage::Gengar if self.gengar_frame.is_none() => {
+                // Reaching Gengar implies Haunter was passed even if it was never sampled
+                self.haunter_frame.get_or_insert(self.frames_elapsed);
+                self.gengar_frame = Some(self.frames_elapsed);
+            }
+            _ => {}
+        }
+        if let Some(form) = final_smash {
+            if !self.in_final_smash {
+                self.final_smash_forms.push(form);
+            }
+        }
+        self.in_final_smash = final_smash.is_some();
+    }
+}
+
+fn stage_rank(stage: EvolutionStage) -> u8 {
+    match stage {
+        EvolutionStage::Gastly => 0,
+        EvolutionStage::Haunter => 1,
+        EvolutionStage::Gengar => 2,
+    }
+}
+
+fn stage_name(stage: EvolutionStage) -> &'static str {
+    match stage {
+        EvolutionStage::Gastly => "gastly",
+        EvolutionStage::Haunter => "haunter",
+        EvolutionStage::Gengar => "gengar",
+    }
+}
+
+fn seconds_json(frames: Option<u32>) -> String {
+    match frames {
+        Some(frames) => format!("{:.2}", frames as f32 / 60.0),
+        None => "null".to_string(),
+    }
+}
+
+// Hand-written JSON (no serde in this crate); every value is a number, bool, null or fixed identifier
+pub fn summary_json(players: &[MatchStats]) -> String {
+    let mut json = String::from("{\n  \"players\": [");
+    for (index, stats) in players.iter().enumerate() {
+        let forms: Vec<String> = stats.final_smash_forms.iter().map(|form| format!("\"{}\"", form.name())).collect();
+        let _ = write!(
+            json,
+            "{}\n    {{\n      \"entry_id\": {},\n      \"color\": {},\n      \"shiny\": {},\n      \"highest_stage\": \"{}\",\n      \"final_stage\": \"{}\",\n      \"seconds_to_haunter\": {},\n      \"seconds_to_gengar\": {},\n      \"evolution_cancels\": {},\n      \"everstone_toggles\": {},\n      \"final_smash_forms\": [{}],\n      \"match_seconds\": {}\n    }}",
+            if index == 0 { "" } else { "," },
+            stats.entry_id,
+            stats.color_id,
+            stats.is_shiny,
+            stage_name(stats.highest_stage),
+            stage_name(stats.last_stage),
+            seconds_json(stats.haunter_frame),
+            seconds_json(stats.gengar_frame),
+            stats.evolution_cancels,
+            stats.everstone_toggles,
+            forms.join(", "),
+            seconds_json(Some(stats.frames_elapsed)),
+        );
+    }
+    json.push_str("\n  ]\n}\n");
+    json
+}
+
+#[derive(Default)]
+struct MatchTracker {
+    players: HashMap<u32, MatchStats>, // Keyed by instance key
+    summary_written: bool,
+}
+
+// One lock for the stats and the written flag, so the results hand-off can't race a new match
+static MATCH_STATS: Lazy<Mutex<MatchTracker>> = Lazy::new(|| Mutex::new(MatchTracker::default()));
+
+pub fn is_results_status(status: i32) -> bool {
+    status == *FIGHTER_STATUS_KIND_WIN || status == *FIGHTER_STATUS_KIND_LOSE || status == 0x107
+}
+
+// Called every frame from the fighter frame callback with the live stage/form
+pub fn track_match_frame(
+    instance_key: u32,
+    entry_id: u32,
+    color_id: u32,
+    is_shiny: bool,
+    status: i32,
+    stage: EvolutionStage,
+    final_smash: Option<FinalSmashForm>,
+) {
+    if is_results_status(status) {
+        return;
+    }
+
+    let mut tracker = MATCH_STATS.lock();
+    if status == *FIGHTER_STATUS_KIND_ENTRY {
+        // New match: drop stats from earlier matches (other instance keys included)
+        if tracker.players.get(&instance_key).map_or(true, |stats| stats.frames_elapsed > 0) {
+            tracker.players.retain(|key, stats| *key != instance_key && stats.frames_elapsed == 0);
+            tracker.players.insert(instance_key, MatchStats::new(entry_id, color_id, is_shiny));
+            tracker.summary_written = false;
+        }
+        return;
+    }
+
+    tracker.players.entry(instance_key)
+        .or_insert_with(|| MatchStats::new(entry_id, color_id, is_shiny))
+        .record_frame(stage, final_smash);
+}
+
+pub fn record_evolution_cancel(instance_key: u32) {
+    if let Some(stats) = MATCH_STATS.lock().players.get_mut(&instance_key) {
+        stats.evolution_cancels += 1;
+    }
+}
+
+pub fn record_everstone_toggle(instance_key: u32) {
+    if let Some(stats) = MATCH_STATS.lock().players.get_mut(&instance_key) {
+        stats.everstone_toggles += 1;
+    }
+}
+
+// Stage the results screen shows for this player: the highest evolution reached in the match
+pub fn results_stage(instance_key: u32) -> Option<EvolutionStage> {
+    MATCH_STATS.lock().players.get(&instance_key).map(|stats| stats.highest_stage)
+}
+
+// Write the summary once per results screen
+pub fn write_match_summary_once() {
+    let mut players: Vec<MatchStats> = {
+        let mut tracker = MATCH_STATS.lock();
+        if tracker.summary_written {
+            return;
+        }
+        tracker.summary_written = true;
+        tracker.players.values().cloned().collect()
+    };
+    if players.is_empty() {
+        return;
+    }
+    players.sort_by_key(|stats| stats.entry_id);
+
+    let _ = std::fs::create_dir_all(crate::gastly::config::REPORT_DIR);
+    let _ = std::fs::write(MATCH_SUMMARY_PATH, summary_json(&players));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reaching_gengar_fills_in_an_unsampled_haunter() {
+        let mut stats = MatchStats::new(0, 0, false);
+        stats.record_frame(EvolutionStage::Gastly, None);
+        stats.record_frame(EvolutionStage::Gengar, None);
+        assert_eq!(stats.haunter_frame, Some(2));
+        assert_eq!(stats.gengar_frame, Some(2));
+        assert_eq!(stats.highest_stage, EvolutionStage::Gengar);
+
+        // Dropping back keeps the highest stage and the first times
+        stats.record_frame(EvolutionStage::Haunter, None);
+        assert_eq!(stats.haunter_frame, Some(2));
+        assert_eq!(stats.highest_stage, EvolutionStage::Gengar);
+        assert_eq!(stats.last_stage, EvolutionStage::Haunter);
+    }
+
+    #[test]
+    fn one_final_smash_entry_per_activation() {
+        let mut stats = MatchStats::new(0, 0, false);
+        for _ in 0..90 {
+            stats.record_frame(EvolutionStage::Gengar, Some(FinalSmashForm::MegaGengar));
+        }
+        stats.record_frame(EvolutionStage::Gengar, None);
+        for _ in 0..60 {
+            stats.record_frame(EvolutionStage::Gengar, Some(FinalSmashForm::GigaGengar));
+        }
+        assert_eq!(stats.final_smash_forms, vec![FinalSmashForm::MegaGengar, FinalSmashForm::GigaGengar]);
+    }
+
+    #[test]
+    fn summary_json_lists_every_player() {
+        let mut first = MatchStats::new(0, 2, true);
+        for _ in 0..120 {
+            first.record_frame(EvolutionStage::Haunter, None);
+        }
+        first.record_frame(EvolutionStage::Gengar, Some(FinalSmashForm::Gengar));
+        let second = MatchStats::new(3, 0, false);
+
+        let json = summary_json(&[first, second]);
+        assert!(json.contains("\"entry_id\": 0,\n      \"color\": 2,\n      \"shiny\": true,"));
+        assert!(json.contains("\"highest_stage\": \"gengar\""));
+        assert!(json.contains("\"seconds_to_haunter\": 0.02,"));
+        assert!(json.contains("\"seconds_to_gengar\": 2.02,"));
+        assert!(json.contains("\"final_smash_forms\": [\"gengar\"],"));
+        assert!(json.contains("\"entry_id\": 3,"));
+        assert!(json.contains("\"seconds_to_haunter\": null,"));
+        assert!(json.contains("\"final_smash_forms\": [],"));
+        assert_eq!(json.matches("\"entry_id\"").count(), 2);
+        assert!(json.starts_with("{\n  \"players\": [\n    {"));
+        assert!(json.ends_with("}\n  ]\n}\n"));
+    }
+}
@@ -0,0 +1,330 @@
+// src/gastly/mesh_manifest.rs
+
+// Mount-time check that each marked slot's model actually contains the meshes the mod drives.
+//
+// The mesh list is read from the slot's model.numdlb string data (mesh object names such as
+// "gastly_body_VIS_O_OBJShape" are normalized to the name used by set_mesh_visibility).
+// Only the stages in the slot marker's chain are checked. Slots missing a required mesh are
+// unmarked (they'd show an invisible fighter); slots missing optional meshes stay enabled and
+// the visuals fall back to the normal body for those meshes.
+// Findings are written to MESH_REPORT_PATH, listing meshes by their constants.rs name.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use smash::app::lua_bind::WorkModule;
+use smash::app::BattleObjectModuleAccessor;
+use smash::lib::lua_const::*;
+use smash::phx::Hash40;
+use crate::gastly::constants::*;
+use crate::gastly::markers::{slot_marker, SlotMarker};
+use crate::gastly::player_state::EvolutionStage;
+
+pub const MESH_REPORT_PATH: &str = "sd:/ultimate/gastly/mesh_report.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeshRequirement {
+    Required, // Slot is disabled without it
+    Optional, // Feature degrades (falls back to the normal body / no icon)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlotVariant {
+    Regular,
+    Shiny,
+}
+
+use MeshRequirement::{Required, Optional};
+
+pub struct ManifestEntry {
+    pub label: &'static str, // Constant name, used in the report
+    pub mesh: &'static Lazy<Hash40>,
+    pub requirement: MeshRequirement,
+    pub stage: Option<EvolutionStage>, // Only checked if the slot's chain reaches this stage
+}
+
+// `MESH => Required for Haunter,`: the stage part is left out for meshes every chain uses
+macro_rules! manifest {
+    (@stage) => { None };
+    (@stage $stage:ident) => { Some(EvolutionStage::$stage) };
+    ($($mesh:ident => $requirement:ident $(for $stage:ident)?,)*) => {
+        &[$(ManifestEntry {
+            label: stringify!($mesh),
+            mesh: &$mesh,
+            requirement: $requirement,
+            stage: manifest!(@stage $($stage)?),
+        },)*]
+    };
+}
+
+// Meshes a Gastly slot must provide (shiny slots use the same mesh set)
+pub static MESH_MANIFEST: &[ManifestEntry] = manifest! {
+    // Stage bodies and default eyes
+    GASTLY_BODY => Required for Gastly,
+    GASTLY_EYE_N => Required for Gastly,
+    HAUNTER_BODY => Required for Haunter,
+    HAUNTER_EYE_N => Required for Haunter,
+    GENGAR_BODY => Required for Gengar,
+    GENGAR_EYE_N => Required for Gengar,
+    // Body parts
+    HAUNTER_HANDS => Optional for Haunter,
+    HAUNTER_IRIS => Optional for Haunter,
+    GENGAR_IRIS => Optional for Gengar,
+    GASTLY_TONGUE => Optional for Gastly,
+    GENGAR_TONGUE_LONG => Optional for Gengar,
+    GENGAR_TONGUE_NORMAL => Optional for Gengar,
+    GASTLY_TONGUE_L => Optional for Gastly,
+    GENGAR_TONGUE_LONG_L => Optional for Gengar,
+    GENGAR_TONGUE_NORMAL_L => Optional for Gengar,
+    // Eye expressions
+    GASTLY_EYE_BLINK => Optional for Gastly,
+    GASTLY_EYE_HALFBLINK1 => Optional for Gastly,
+    GASTLY_EYE_ATTACK => Optional for Gastly,
+    GASTLY_EYE_CAPTURE => Optional for Gastly,
+    GASTLY_EYE_OUCH => Optional for Gastly,
+    GASTLY_EYE_DOWN => Optional for Gastly,
+    GASTLY_EYE_HEAVYATTACK => Optional for Gastly,
+    GASTLY_EYE_SQUINT => Optional for Gastly,
+    GASTLY_EYE_LOOK_F => Optional for Gastly,
+    GASTLY_EYE_LOOK_B => Optional for Gastly,
+    HAUNTER_EYE_BLINK => Optional for Haunter,
+    HAUNTER_EYE_HALFBLINK1 => Optional for Haunter,
+    HAUNTER_EYE_ATTACK => Optional for Haunter,
+    HAUNTER_EYE_CAPTURE => Optional for Haunter,
+    HAUNTER_EYE_OUCH => Optional for Haunter,
+    HAUNTER_EYE_DOWN => Optional for Haunter,
+    HAUNTER_EYE_HEAVYATTACK => Optional for Haunter,
+    HAUNTER_EYE_SQUINT => Optional for Haunter,
+    HAUNTER_EYE_LOOK_F => Optional for Haunter,
+    HAUNTER_EYE_LOOK_B => Optional for Haunter,
+    GENGAR_EYE_BLINK => Optional for Gengar,
+    GENGAR_EYE_HALFBLINK1 => Optional for Gengar,
+    GENGAR_EYE_ATTACK => Optional for Gengar,
+    GENGAR_EYE_CAPTURE => Optional for Gengar,
+    GENGAR_EYE_OUCH => Optional for Gengar,
+    GENGAR_EYE_DOWN => Optional for Gengar,
+    GENGAR_EYE_HEAVYATTACK => Optional for Gengar,
+    GENGAR_EYE_SQUINT => Optional for Gengar,
+    GENGAR_EYE_LOOK_F => Optional for Gengar,
+    GENGAR_EYE_LOOK_B => Optional for Gengar,
+    // Evolution
+    GASTLY_EVOLVING => Optional for Haunter,
+    GASTLY_EVOLVING_FLOORSHADOW => Optional for Haunter,
+    GASTLY_EVOLVING_SHADOWBALL => Optional for Haunter,
+    GASTLY_EVOLVING_TONGUE => Optional for Haunter,
+    HAUNTER_EVOLVING => Optional for Gengar,
+    HAUNTER_EVOLVING_FLOORSHADOW => Optional for Gengar,
+    HAUNTER_EVOLVING_SHADOWBALL => Optional for Gengar,
+    // Animation meshes
+    GASTLY_FLOORSHADOW => Optional for Gastly,
+    HAUNTER_FLOORSHADOW => Optional for Haunter,
+    GENGAR_FLOORSHADOW => Optional for Gengar,
+    GASTLY_SHADOWBALL => Optional for Gastly,
+    HAUNTER_SHADOWBALL => Optional for Haunter,
+    GENGAR_SHADOWBALL => Optional for Gengar,
+    GHOST => Optional,
+    GENGAR_RUN => Optional for Gengar,
+    // Final smash forms
+    MEGA_GENGAR_BODY => Optional for Gengar,
+    GIGA_GENGAR_BODY => Optional for Gengar,
+    // Icons
+    LINKING_CORD_ICON => Optional,
+    EVERSTONE_ICON => Optional,
+    EVERSTONE_X_ICON => Optional,
+    GENGARITE_ICON => Optional,
+    DYNAMAX_ICON => Optional,
+    STG1_DMG_T_ICON => Optional,
+    STG1_DMG_D_ICON => Optional,
+    STG2_DMG_SS_ICON => Optional,
+    STG2_DMG_SE_ICON => Optional,
+};
+
+// "gastly_body_VIS_O_OBJShape" -> "gastly_body" (only the last "_vis_" starts the suffix)
+pub fn normalize_mesh_name(raw: &str) -> String {
+    let lower = raw.to_ascii_lowercase();
+    let trimmed = match lower.rfind("_vis_") {
+        Some(index) => &lower[..index],
+        None => lower.strip_suffix("shape").unwrap_or(&lower),
+    };
+    trimmed.to_string()
+}
+
+// Null-terminated printable strings in a numdlb (mesh object names, material labels, file names)
+pub fn extract_numdlb_names(data: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut start = None;
+
+    for (index, byte) in data.iter().enumerate() {
+        let printable = (0x20..0x7F).contains(byte);
+        match (printable, start) {
+            (true, None) => start = Some(index),
+            (false, Some(begin)) => {
+                // Strings in SSBH files are null-terminated; other runs are binary noise
+                if *byte == 0 && index - begin >= 3 {
+                    if let Ok(name) = std::str::from_utf8(&data[begin..index]) {
+                        names.push(normalize_mesh_name(name));
+                    }
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+#[derive(Clone, Default)]
+pub struct SlotMeshReport {
+    pub missing_required: Vec<&'static ManifestEntry>,
+    pub missing_optional: Vec<&'static ManifestEntry>,
+}
+
+impl SlotMeshReport {
+    pub fn is_usable(&self) -> bool {
+        self.missing_required.is_empty()
+    }
+}
+
+fn labels(entries: &[&ManifestEntry]) -> String {
+    entries.iter().map(|entry| entry.label).collect::<Vec<_>>().join(", ")
+}
+
+// Stages past the marker's chain aren't checked (a `chain = gastly` slot needs no Haunter/Gengar meshes)
+pub fn diff_against_manifest(model_names: &[String], marker: &SlotMarker) -> SlotMeshReport {
+    let model_meshes: HashSet<u64> = model_names.iter().map(|name| smash::hash40(name)).collect();
+    let mut report = SlotMeshReport::default();
+    for entry in MESH_MANIFEST {
+        if entry.stage.map_or(false, |stage| !marker.allows_stage(stage)) {
+            continue;
+        }
+        if model_meshes.contains(&entry.mesh.hash) {
+            continue;
+        }
+        match entry.requirement {
+            Required => report.missing_required.push(entry),
+            Optional => report.missing_optional.push(entry),
+        }
+    }
+    report
+}
+
+// Missing optional meshes per costume slot (mesh hashes), consulted by the visuals
+static MISSING_SLOT_MESHES: Lazy<RwLock<HashMap<usize, Vec<u64>>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+pub fn is_slot_mesh_available(color_id: usize, mesh: u64) -> bool {
+    MISSING_SLOT_MESHES.read()
+        .get(&color_id)
+        .map_or(true, |missing| !missing.contains(&mesh))
+}
+
+pub unsafe fn is_mesh_available(boma: *mut BattleObjectModuleAccessor, mesh: Hash40) -> bool {
+    let color_id = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR) as usize;
+    is_slot_mesh_available(color_id, mesh.hash)
+}
+
+// Validate every marked slot; unmarks unusable ones and writes the report. Called from mods_mounted.
+pub fn validate_marked_slots(fighter_name: &str) {
+    let mut report_text = String::new();
+    let mut missing_map = HashMap::new();
+
+    for color_id in 0..256usize {
+        let (is_marked, is_shiny) = unsafe { (crate::MARKED_COLORS[color_id], crate::SHINY_COLORS[color_id]) };
+        if !is_marked {
+            continue;
+        }
+
+        let variant = if is_shiny { SlotVariant::Shiny } else { SlotVariant::Regular };
+        let model_path = format!("mods:/fighter/{}/model/body/c{:02}/model.numdlb", fighter_name, color_id);
+        let data = match std::fs::read(&model_path) {
+            Ok(data) => data,
+            Err(_) => {
+                // Model may come from another slot or the base game - nothing to check against
+                let _ = writeln!(report_text, "c{:02} ({:?}): no model.numdlb in mod folder, skipped", color_id, variant);
+                continue;
+            }
+        };
+
+        let report = diff_against_manifest(&extract_numdlb_names(&data), &slot_marker(color_id));
+        if report.missing_required.is_empty() && report.missing_optional.is_empty() {
+            let _ = writeln!(report_text, "c{:02} ({:?}): OK", color_id, variant);
+            continue;
+        }
+
+        if !report.is_usable() {
+            let _ = writeln!(report_text, "c{:02} ({:?}): DISABLED, missing required: {}", color_id, variant, labels(&report.missing_required));
+            unsafe {
+                crate::MARKED_COLORS[color_id] = false;
+                crate::SHINY_COLORS[color_id] = false;
+            }
+        }
+        if !report.missing_optional.is_empty() {
+            let _ = writeln!(report_text, "c{:02} ({:?}): missing optional: {}", color_id, variant, labels(&report.missing_optional));
+            missing_map.insert(color_id, report.missing_optional.iter().map(|entry| entry.mesh.hash).collect());
+        }
+    }
+
+    *MISSING_SLOT_MESHES.write() = missing_map;
+
+    if !report_text.is_empty() {
+        let _ = std::fs::create_dir_all("sd:/ultimate/gastly");
+        let _ = std::fs::write(MESH_REPORT_PATH, report_text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn normalize_strips_the_visibility_suffix() {
+        assert_eq!(normalize_mesh_name("gastly_body_VIS_O_OBJShape"), "gastly_body");
+        assert_eq!(normalize_mesh_name("Haunter_EyeN_VIS_O_OBJShape"), "haunter_eyen");
+        assert_eq!(normalize_mesh_name("gastly_bodyShape"), "gastly_body");
+        assert_eq!(normalize_mesh_name("gengar_body"), "gengar_body");
+    }
+
+    #[test]
+    fn normalize_keeps_vis_inside_the_name() {
+        assert_eq!(normalize_mesh_name("x_visor_VIS_O_OBJShape"), "x_visor");
+        assert_eq!(normalize_mesh_name("gengar_vis_eye_VIS_O_OBJShape"), "gengar_vis_eye");
+        assert_eq!(normalize_mesh_name("x_visor"), "x_visor");
+    }
+
+    #[test]
+    fn numdlb_names_are_null_terminated_printable_runs() {
+        let mut data = vec![0x01, 0x02];
+        data.extend_from_slice(b"gastly_body_VIS_O_OBJShape\0");
+        data.extend_from_slice(&[0xFF, 0x10]);
+        data.extend_from_slice(b"ab\0");            // Too short
+        data.extend_from_slice(b"haunter_eyen\0");
+        data.extend_from_slice(b"noise\xFF");       // Not null-terminated
+        data.extend_from_slice(b"tail");             // Runs into the end of the file
+        assert_eq!(extract_numdlb_names(&data), names(&["gastly_body", "haunter_eyen"]));
+    }
+
+    #[test]
+    fn required_meshes_follow_the_marker_chain() {
+        let gastly_only = names(&["gastly_body", "gastly_eyen"]);
+        let full_chain = SlotMarker::default();
+        let short_chain = SlotMarker { final_stage: EvolutionStage::Gastly, ..SlotMarker::default() };
+
+        let report = diff_against_manifest(&gastly_only, &full_chain);
+        assert_eq!(labels(&report.missing_required), "HAUNTER_BODY, HAUNTER_EYE_N, GENGAR_BODY, GENGAR_EYE_N");
+
+        let report = diff_against_manifest(&gastly_only, &short_chain);
+        assert!(report.is_usable());
+        assert!(report.missing_optional.iter().all(|entry| entry.stage.map_or(true, |stage| stage == EvolutionStage::Gastly)));
+
+        let haunter_chain = SlotMarker { final_stage: EvolutionStage::Haunter, ..SlotMarker::default() };
+        let report = diff_against_manifest(&names(&["gastly_body", "gastly_eyen", "haunter_body", "haunter_eyen"]), &haunter_chain);
+        assert!(report.is_usable());
+        assert!(report.missing_optional.iter().any(|entry| entry.label == "GASTLY_EVOLVING"));
+        assert!(!report.missing_optional.iter().any(|entry| entry.label == "HAUNTER_EVOLVING"));
+    }
+}
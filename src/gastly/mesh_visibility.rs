@@ -0,0 +1,198 @@
+// src/gastly/mesh_visibility.rs
+
+// Declarative mesh visibility.
+//
+// Each subsystem (shadowball, evolution, animation meshes, body, tongue, eyes, final smash) adds
+// show/hide requests at a priority. For every mesh mentioned, the highest priority request wins
+// (ties go to the request added last). Only meshes whose resolved visibility differs from what was
+// last pushed for that player are sent to ModelModule; the imperative helpers go through the same
+// cache via set_mesh_visibility. Meshes the engine animates itself (Purin's vanilla eyes) are switched
+// back on by the motion behind our back, so they are always sent.
+
+use std::collections::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use smash::app::lua_bind::ModelModule;
+use smash::app::BattleObjectModuleAccessor;
+use smash::phx::Hash40;
+
+use crate::gastly::constants::PURIN_VANILLA_EYES_TO_HIDE;
+
+// Layer priorities (higher overrides lower)
+pub const LAYER_BASE: u8 = 0;          // Everything managed starts hidden
+pub const LAYER_BODY: u8 = 10;         // Stage body parts
+pub const LAYER_EYES: u8 = 15;         // Stage eye expression
+pub const LAYER_TONGUE: u8 = 20;       // Animation-specific tongues
+pub const LAYER_ANIMATION: u8 = 30;    // Floor shadow / ghost / Gengar run replace the body
+pub const LAYER_EVOLVING: u8 = 40;     // Evolving meshes replace everything below
+pub const LAYER_SHADOWBALL: u8 = 50;   // Shadow ball hold mesh
+pub const LAYER_ROLLOUT: u8 = 60;      // Rollout with an active hitbox is fully invisible
+pub const LAYER_FINAL_SMASH: u8 = 70;  // Gastly body must never show during Haunter/Gengar final smash
+
+#[derive(Debug, Clone)]
+struct MeshRequest {
+    priority: u8,
+    visible: bool,
+    meshes: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MeshLayerStack {
+    requests: Vec<MeshRequest>,
+}
+
+impl MeshLayerStack {
+    pub fn new() -> Self {
+        Self { requests: Vec::new() }
+    }
+
+    pub fn show(&mut self, priority: u8, meshes: &[Hash40]) {
+        self.request(priority, true, meshes.iter().map(|mesh| mesh.hash).collect());
+    }
+
+    pub fn hide(&mut self, priority: u8, meshes: &[Hash40]) {
+        self.request(priority, false, meshes.iter().map(|mesh| mesh.hash).collect());
+    }
+
+    fn request(&mut self, priority: u8, visible: bool, meshes: Vec<u64>) {
+        self.requests.push(MeshRequest { priority, visible, meshes });
+    }
+
+    // Would a mesh shown at `priority` stay visible after everything already requested?
+    pub fn would_show_at(&self, mesh: u64, priority: u8) -> bool {
+        !self.requests.iter().any(|request| {
+            request.priority > priority && !request.visible && request.meshes.contains(&mesh)
+        })
+    }
+
+    // Final visibility of every mentioned mesh, in first-mentioned order
+    pub fn resolve(&self) -> Vec<(u64, bool)> {
+        let mut resolved: Vec<(u64, (u8, bool))> = Vec::new();
+        for request in &self.requests {
+            for mesh in &request.meshes {
+                match resolved.iter_mut().find(|(hash, _)| hash == mesh) {
+                    Some((_, winner)) => {
+                        if request.priority >= winner.0 {
+                            *winner = (request.priority, request.visible);
+                        }
+                    }
+                    None => resolved.push((*mesh, (request.priority, request.visible))),
+                }
+            }
+        }
+        resolved.into_iter().map(|(mesh, (_, visible))| (mesh, visible)).collect()
+    }
+}
+
+// Last visibility pushed to the engine per player instance (instance key -> mesh hash -> visible)
+static PUSHED_VISIBILITY: Lazy<Mutex<HashMap<u32, HashMap<u64, bool>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Debug counters: engine calls sent vs skipped because the mesh was already in that state
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MeshCallStats {
+    pub sent: u32,
+    pub saved: u32,
+}
+
+struct MeshCallCounter {
+    frame: u32,
+    current: MeshCallStats,
+    last_frame: MeshCallStats,
+}
+
+static MESH_CALL_COUNTER: Mutex<MeshCallCounter> = parking_lot::const_mutex(MeshCallCounter {
+    frame: 0,
+    current: MeshCallStats { sent: 0, saved: 0 },
+    last_frame: MeshCallStats { sent: 0, saved: 0 },
+});
+
+fn count_mesh_call(sent: bool) {
+    let frame = crate::gastly::hit_records::global_frame();
+    let mut counter = MESH_CALL_COUNTER.lock();
+    let new_frame = counter.frame != frame;
+    if new_frame {
+        counter.last_frame = counter.current;
+        counter.current = MeshCallStats::default();
+        counter.frame = frame;
+    }
+    if sent {
+        counter.current.sent += 1;
+    } else {
+        counter.current.saved += 1;
+    }
+    drop(counter);
+
+    #[cfg(feature = "dev")]
+    if new_frame && frame % MESH_CALL_STATS_INTERVAL == 0 {
+        write_mesh_call_stats(frame);
+    }
+}
+
+// Totals over all players for the last completed frame
+pub fn mesh_call_stats_last_frame() -> MeshCallStats {
+    MESH_CALL_COUNTER.lock().last_frame
+}
+
+// dev builds: the last frame's counts are rewritten to MESH_CALL_STATS_PATH every few seconds
+#[cfg(feature = "dev")]
+pub const MESH_CALL_STATS_PATH: &str = "sd:/ultimate/gastly/mesh_calls.txt";
+#[cfg(feature = "dev")]
+const MESH_CALL_STATS_INTERVAL: u32 = 300;
+
+#[cfg(feature = "dev")]
+fn write_mesh_call_stats(frame: u32) {
+    let stats = mesh_call_stats_last_frame();
+    let _ = std::fs::create_dir_all(crate::gastly::config::REPORT_DIR);
+    let _ = std::fs::write(MESH_CALL_STATS_PATH, format!("frame {}: {} sent, {} saved\n", frame, stats.sent, stats.saved));
+}
+
+// Visibility driven by the motion every frame; the cache can't know its state
+fn is_engine_animated(mesh: u64) -> bool {
+    PURIN_VANILLA_EYES_TO_HIDE.iter().any(|eye| eye.hash == mesh)
+}
+
+// Push one mesh unless the cache says it's already in that state
+unsafe fn push_if_changed(boma: *mut BattleObjectModuleAccessor, pushed: &mut HashMap<u64, bool>, mesh: u64, visible: bool) {
+    if is_engine_animated(mesh) {
+        force_mesh_visibility(boma, Hash40 { hash: mesh }, visible);
+        return;
+    }
+    if pushed.get(&mesh) == Some(&visible) {
+        count_mesh_call(false);
+        return;
+    }
+    ModelModule::set_mesh_visibility(boma, Hash40 { hash: mesh }, visible);
+    pushed.insert(mesh, visible);
+    count_mesh_call(true);
+}
+
+// Always reaches the engine, for meshes the motion animates (see is_engine_animated)
+pub unsafe fn force_mesh_visibility(boma: *mut BattleObjectModuleAccessor, mesh: Hash40, visible: bool) {
+    ModelModule::set_mesh_visibility(boma, mesh, visible);
+    count_mesh_call(true);
+}
+
+// Drop-in for ModelModule::set_mesh_visibility: only reaches the engine when the mesh changes
+pub unsafe fn set_mesh_visibility(boma: *mut BattleObjectModuleAccessor, mesh: Hash40, visible: bool) {
+    let instance_key = crate::gastly::get_instance_key(boma);
+    let mut pushed_map = PUSHED_VISIBILITY.lock();
+    let pushed = pushed_map.entry(instance_key).or_insert_with(HashMap::new);
+    push_if_changed(boma, pushed, mesh.hash, visible);
+}
+
+// Push the resolved stack, skipping meshes already in the requested state
+pub unsafe fn apply_mesh_layers(boma: *mut BattleObjectModuleAccessor, layers: &MeshLayerStack) {
+    let instance_key = crate::gastly::get_instance_key(boma);
+    let mut pushed_map = PUSHED_VISIBILITY.lock();
+    let pushed = pushed_map.entry(instance_key).or_insert_with(HashMap::new);
+
+    for (mesh, visible) in layers.resolve() {
+        push_if_changed(boma, pushed, mesh, visible);
+    }
+}
+
+// Forget what was pushed for a player so the next frame re-sends everything.
+// Needed whenever the engine may have reset visibility behind our back (model reload, training reset, rebirth).
+pub fn invalidate_mesh_cache(instance_key: u32) {
+    PUSHED_VISIBILITY.lock().remove(&instance_key);
+}
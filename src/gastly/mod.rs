@@ -18,7 +18,7 @@ use smash::app::{
     BattleObjectModuleAccessor,
     FighterUtil,
 };
-use smash::phx::{Hash40 as PhxHash40, Vector3f};
+use smash::phx::Vector3f;
 use smash::lua2cpp::{L2CFighterCommon};
 use smashline::*;
 use smash_script::macros;
@@ -35,6 +35,28 @@ pub mod animation_hooks;
 pub mod effects;
 pub mod acmd;
 pub mod darkfx;
+pub mod dark_moves;
+pub mod hit_records;
+pub mod mesh_visibility;
+pub mod mesh_manifest;
+pub mod markers;
+pub mod palettes;
+pub mod shiny_odds;
+pub mod cutins;
+pub mod match_stats;
+pub mod input_sequence;
+pub mod command_matcher;
+pub mod input_history;
+pub mod command_bindings;
+pub mod item_rules;
+pub mod icon_manager;
+pub mod billboard;
+pub mod tongue;
+pub mod expression_engine;
+pub mod expression_map;
+pub mod expression_audit;
+pub mod evolution_items;
+pub mod config;
 pub mod sounds;
 pub mod acmdsound;
 mod persist_sfx;
@@ -43,9 +65,14 @@ mod ui_management;
 
 // Use items from our submodules
 use crate::gastly::constants::*;
-use crate::gastly::player_state::{PlayerEvolutionState, EvolutionStage, BlinkPhase};
+use crate::gastly::mesh_visibility::{set_mesh_visibility, force_mesh_visibility};
+use crate::gastly::player_state::{PlayerEvolutionState, EvolutionStage};
+use crate::gastly::expression_engine::{ExpressionInput, expression_mesh, eye_choice_mesh};
+use crate::gastly::expression_map::{win_pose_expression, visible_vanilla_eye};
+use crate::gastly::mesh_manifest::is_mesh_available;
 use crate::gastly::visuals::{update_body_and_unique_parts_visibility_with_enforcement, update_body_and_unique_parts_visibility, set_active_eye_mesh, handle_final_smash_model_swap, hide_all_animation_specific_meshes};
 use crate::gastly::icon_management::{handle_icon_toggles_and_effects, deactivate_all_pos_sensitive_icons};
+use crate::gastly::icon_manager::OverheadIcon;
 use crate::gastly::evolution_logic::{handle_evolution_process, advance_evolution_animation};
 use crate::gastly::effects::{handle_gastly_effects, GASTLY_AURA_HANDLE_WORK_ID};
 use crate::gastly::ui_management::{handle_ui_management, reset_ui_state_on_death, track_cry_sound_playback};
@@ -137,21 +164,52 @@ unsafe fn cleanup_all_evolution_sounds_on_death(boma: *mut BattleObjectModuleAcc
 // Global state for all fighters playing as Purin/Gastly - using entry_id + color_id for full isolation
 pub static FIGHTER_STATES: Lazy<RwLock<HashMap<u32, PlayerEvolutionState>>> = Lazy::new(|| RwLock::new(HashMap::new()));
 
-// Helper function to create unique instance key (entry_id + costume color)
-pub unsafe fn get_instance_key(boma: *mut BattleObjectModuleAccessor) -> u32 {
-    let entry_id = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as u32;
-    let color_id = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR) as u32;
-    
+pub const MAX_FIGHTER_ENTRIES: u32 = 8;
+pub const INSTANCE_KEY_SLOTS: usize = 256;
+const INSTANCE_KEYS_PER_ENTRY: u32 = 32;
+
+// Keying rules for per-player state (keep every subsystem on one of these two):
+//   - Per-Gastly state (FIGHTER_STATES, effect/UI/visual trackers) -> instance key
+//   - Per-victim state (darkfx DARK_EFFECT_DATA, hit records) -> entry id, since any fighter can be a victim
+// Instance keys map back to exactly one entry id, so dittos with regular and shiny slots never share state.
+
+// Pure part of get_instance_key
+pub fn instance_key_for(entry_id: u32, color_id: u32) -> u32 {
     // FIXED: Proper unique instance key for duos without collision
-    let safe_entry_id = if entry_id < 8 { entry_id } else { 0 };
+    let safe_entry_id = if entry_id < MAX_FIGHTER_ENTRIES { entry_id } else { 0 };
     let safe_color_id = color_id & 0xFF; // Limit to 8 bits
-    
+
     // Use entry_id * 32 + color_id to ensure no collisions in duos
     // This gives each player a unique 32-slot range: P1(0-31), P2(32-63), etc.
-    let result = (safe_entry_id * 32) + (safe_color_id % 32);
-    
-    // Ensure result fits in our array bounds (0-255)
-    if result < 256 { result } else { 0 }
+    (safe_entry_id * INSTANCE_KEYS_PER_ENTRY) + (safe_color_id % INSTANCE_KEYS_PER_ENTRY)
+}
+
+pub fn entry_id_from_instance_key(instance_key: u32) -> u32 {
+    instance_key / INSTANCE_KEYS_PER_ENTRY
+}
+
+// Helper function to create unique instance key (entry_id + costume color)
+pub unsafe fn get_instance_key(boma: *mut BattleObjectModuleAccessor) -> u32 {
+    let entry_id = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as u32;
+    let color_id = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR) as u32;
+    instance_key_for(entry_id, color_id)
+}
+
+// A player only ever owns one instance key; drop states left behind by the same entry
+// on a different costume (e.g. training mode costume swaps) so they can't be picked up again
+pub fn prune_stale_states_for_entry(states: &mut HashMap<u32, PlayerEvolutionState>, instance_key: u32) {
+    let entry_id = entry_id_from_instance_key(instance_key);
+    states.retain(|key, _| *key == instance_key || entry_id_from_instance_key(*key) != entry_id);
+}
+
+// Create the state for an instance key seen for the first time (pruning the entry's old ones); true if new
+pub fn claim_player_state(states: &mut HashMap<u32, PlayerEvolutionState>, instance_key: u32) -> bool {
+    if states.contains_key(&instance_key) {
+        return false;
+    }
+    prune_stale_states_for_entry(states, instance_key);
+    states.insert(instance_key, PlayerEvolutionState::new());
+    true
 }
 
 // Static tracking for grab effect cleanup - now using instance keys
@@ -178,16 +236,30 @@ unsafe fn hit_tracking_hook(
     let result = call_original!(fighter_manager, attacker_id, defender_id, move_type, arg5, move_type_again, fighter, arg8);
 
     let attacker_boma = smash::app::sv_battle_object::module_accessor(attacker_id);
-    if !attacker_boma.is_null() && utility::get_kind(&mut *attacker_boma) == *FIGHTER_KIND_PURIN {
-        let attacker_instance_key = get_instance_key(attacker_boma);
-
-        let mut states_map_writer = FIGHTER_STATES.write();
-        if let Some(player_state) = states_map_writer.get_mut(&attacker_instance_key) {
-            if !player_state.is_evolving {
-                player_state.hits_landed_this_stage += 1;
-            }
-        }
+    if attacker_boma.is_null() || utility::get_category(&mut *attacker_boma) != *BATTLE_OBJECT_CATEGORY_FIGHTER {
+        return result;
     }
+
+    // Record exactly who hit whom; consumers read these instead of guessing from positions
+    let defender_boma = smash::app::sv_battle_object::module_accessor(defender_id);
+    let defender_entry_id = if !defender_boma.is_null() && utility::get_category(&mut *defender_boma) == *BATTLE_OBJECT_CATEGORY_FIGHTER {
+        Some(WorkModule::get_int(defender_boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as u32)
+    } else {
+        None
+    };
+
+    hit_records::publish(hit_records::HitRecord {
+        sequence: 0,
+        frame: 0,
+        attacker_entry_id: WorkModule::get_int(attacker_boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as u32,
+        attacker_instance_key: get_instance_key(attacker_boma),
+        attacker_color: WorkModule::get_int(attacker_boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR) as u32,
+        defender_entry_id,
+        attacker_status: StatusModule::status_kind(attacker_boma),
+        attacker_motion: MotionModule::motion_kind(attacker_boma),
+        dark_move: darkfx::classify_dark_move(attacker_boma),
+    });
+
     result
 }
 
@@ -258,7 +330,7 @@ unsafe extern "C" fn init_gastly_aura(fighter: &mut L2CFighterCommon) {
 }
 
 //  Handle down special blink mesh visibility with proper mesh management
-unsafe fn handle_down_special_blink(boma: *mut BattleObjectModuleAccessor, player_state: &mut PlayerEvolutionState) -> bool {
+unsafe fn handle_down_special_blink(boma: *mut BattleObjectModuleAccessor, player_state: &mut PlayerEvolutionState) -> Option<Hash40> {
     let current_status = StatusModule::status_kind(boma);
     
     // Check if we're in down special (Rest) status
@@ -280,21 +352,21 @@ unsafe fn handle_down_special_blink(boma: *mut BattleObjectModuleAccessor, playe
             // SECOND: Ensure normal body parts are visible for current stage
             match player_state.stage {
                 EvolutionStage::Gastly => { 
-                    ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, true);
+                    set_mesh_visibility(boma, *GASTLY_BODY, true);
                 }
                 EvolutionStage::Haunter => {
-                    ModelModule::set_mesh_visibility(boma, *HAUNTER_BODY, true);
-                    ModelModule::set_mesh_visibility(boma, *HAUNTER_HANDS, true);
-                    ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, true);
+                    set_mesh_visibility(boma, *HAUNTER_BODY, true);
+                    set_mesh_visibility(boma, *HAUNTER_HANDS, true);
+                    set_mesh_visibility(boma, *HAUNTER_IRIS, true);
                 }
                 EvolutionStage::Gengar => {
-                    ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, true);
-                    ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, true);
+                    set_mesh_visibility(boma, *GENGAR_BODY, true);
+                    set_mesh_visibility(boma, *GENGAR_IRIS, true);
                     
                     // SPECIAL CASE: Hide Gastly body if in final smash form for Gengar/Haunter
                     if player_state.is_in_final_smash_form || 
                        WorkModule::is_flag(boma, *FIGHTER_INSTANCE_WORK_ID_FLAG_FINAL) {
-                        ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
+                        set_mesh_visibility(boma, *GASTLY_BODY, false);
                     }
                 }
             }
@@ -303,153 +375,102 @@ unsafe fn handle_down_special_blink(boma: *mut BattleObjectModuleAccessor, playe
             match player_state.stage {
                 EvolutionStage::Gastly => {
                     for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() {
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false);
+                        set_mesh_visibility(boma, *eye_hash, false);
                     }
                 }
                 EvolutionStage::Haunter => {
                     for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() {
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false);
+                        set_mesh_visibility(boma, *eye_hash, false);
                     }
                     // Also hide Gastly body for Haunter during final smash
                     if player_state.is_in_final_smash_form || 
                        WorkModule::is_flag(boma, *FIGHTER_INSTANCE_WORK_ID_FLAG_FINAL) {
-                        ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
+                        set_mesh_visibility(boma, *GASTLY_BODY, false);
                     }
                 }
                 EvolutionStage::Gengar => {
                     for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() {
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false);
+                        set_mesh_visibility(boma, *eye_hash, false);
                     }
                 }
             }
             
             // FOURTH: Show ONLY the blink mesh
-            ModelModule::set_mesh_visibility(boma, blink_mesh, true);
+            set_mesh_visibility(boma, blink_mesh, true);
             
             //        motion_frame, blink_mesh.hash);
             
-            return true; // Indicate we're overriding eye expression for down special
+            return Some(blink_mesh); // Indicate we're overriding eye expression for down special
         }
     }
     
-    false // Not in down special blink range
+    None // Not in down special blink range
 }
 
-// Handle win_3 motion blink sequences for all pokemon stages
-unsafe fn handle_win_3_blink(boma: *mut BattleObjectModuleAccessor, player_state: &mut PlayerEvolutionState) -> bool {
-    let current_status = StatusModule::status_kind(boma);
+// Win pose eyes from MOTION_EYE_TABLE (expression_map.rs); returns the mesh forced on
+unsafe fn handle_win_pose_eyes(boma: *mut BattleObjectModuleAccessor, player_state: &mut PlayerEvolutionState) -> Option<Hash40> {
     let current_motion = MotionModule::motion_kind(boma);
-    
-    // Check if we're in win_3 or win_3_wait motions
-    let is_win_3 = current_motion == smash::hash40("win_3");
-    let is_win_3_wait = current_motion == smash::hash40("win_3_wait");
-    
-    if is_win_3 || is_win_3_wait {
-        let motion_frame = MotionModule::frame(boma);
-        let mut target_blink_mesh = None;
-        
-        if is_win_3 {
-            // win_3: 1-113 blink, 114-155 open, 156 halfblink
-            if motion_frame >= 1.0 && motion_frame <= 113.0 {
-                target_blink_mesh = Some(match player_state.stage {
-                    EvolutionStage::Gastly => *GASTLY_EYE_BLINK,
-                    EvolutionStage::Haunter => *HAUNTER_EYE_BLINK,
-                    EvolutionStage::Gengar => *GENGAR_EYE_BLINK,
-                });
-            } else if motion_frame >= 114.0 && motion_frame <= 155.0 {
-                target_blink_mesh = Some(match player_state.stage {
-                    EvolutionStage::Gastly => *GASTLY_EYE_N,
-                    EvolutionStage::Haunter => *HAUNTER_EYE_N,
-                    EvolutionStage::Gengar => *GENGAR_EYE_N,
-                });
-            } else if motion_frame >= 156.0 {
-                target_blink_mesh = Some(match player_state.stage {
-                    EvolutionStage::Gastly => *GASTLY_EYE_HALFBLINK1,
-                    EvolutionStage::Haunter => *HAUNTER_EYE_HALFBLINK1,
-                    EvolutionStage::Gengar => *GENGAR_EYE_HALFBLINK1,
-                });
-            }
-        } else if is_win_3_wait {
-            // win_3_wait: 1-52 halfblink, 53-207 blink, 208-251 open
-            if motion_frame >= 1.0 && motion_frame <= 52.0 {
-                target_blink_mesh = Some(match player_state.stage {
-                    EvolutionStage::Gastly => *GASTLY_EYE_HALFBLINK1,
-                    EvolutionStage::Haunter => *HAUNTER_EYE_HALFBLINK1,
-                    EvolutionStage::Gengar => *GENGAR_EYE_HALFBLINK1,
-                });
-            } else if motion_frame >= 53.0 && motion_frame <= 207.0 {
-                target_blink_mesh = Some(match player_state.stage {
-                    EvolutionStage::Gastly => *GASTLY_EYE_BLINK,
-                    EvolutionStage::Haunter => *HAUNTER_EYE_BLINK,
-                    EvolutionStage::Gengar => *GENGAR_EYE_BLINK,
-                });
-            } else if motion_frame >= 208.0 && motion_frame <= 251.0 {
-                target_blink_mesh = Some(match player_state.stage {
-                    EvolutionStage::Gastly => *GASTLY_EYE_N,
-                    EvolutionStage::Haunter => *HAUNTER_EYE_N,
-                    EvolutionStage::Gengar => *GENGAR_EYE_N,
-                });
-            }
-        }
-        
-        if let Some(blink_mesh) = target_blink_mesh {
-            // FIRST: Hide ALL animation-specific meshes that might interfere
-            hide_all_animation_specific_meshes(boma);
-            
-            // SECOND: Ensure normal body parts are visible for current stage
-            match player_state.stage {
-                EvolutionStage::Gastly => { 
-                    ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, true);
-                }
-                EvolutionStage::Haunter => {
-                    ModelModule::set_mesh_visibility(boma, *HAUNTER_BODY, true);
-                    ModelModule::set_mesh_visibility(boma, *HAUNTER_HANDS, true);
-                    ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, true);
+    let motion_frame = MotionModule::frame(boma) as i32;
+    let target_blink_mesh = win_pose_expression(current_motion, motion_frame, player_state.visible_vanilla_eye)
+        .map(|expression| expression_mesh(player_state.stage, expression));
+    
+    if let Some(blink_mesh) = target_blink_mesh {
+        // FIRST: Hide ALL animation-specific meshes that might interfere
+        hide_all_animation_specific_meshes(boma);
+        
+        // SECOND: Ensure normal body parts are visible for current stage
+        match player_state.stage {
+            EvolutionStage::Gastly => { 
+                set_mesh_visibility(boma, *GASTLY_BODY, true);
+            }
+            EvolutionStage::Haunter => {
+                set_mesh_visibility(boma, *HAUNTER_BODY, true);
+                set_mesh_visibility(boma, *HAUNTER_HANDS, true);
+                set_mesh_visibility(boma, *HAUNTER_IRIS, true);
+            }
+            EvolutionStage::Gengar => {
+                set_mesh_visibility(boma, *GENGAR_BODY, true);
+                set_mesh_visibility(boma, *GENGAR_IRIS, true);
+                
+                // SPECIAL CASE: Hide Gastly body if in final smash form for Gengar/Haunter
+                if player_state.is_in_final_smash_form || 
+                   WorkModule::is_flag(boma, *FIGHTER_INSTANCE_WORK_ID_FLAG_FINAL) {
+                    set_mesh_visibility(boma, *GASTLY_BODY, false);
                 }
-                EvolutionStage::Gengar => {
-                    ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, true);
-                    ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, true);
-                    
-                    // SPECIAL CASE: Hide Gastly body if in final smash form for Gengar/Haunter
-                    if player_state.is_in_final_smash_form || 
-                       WorkModule::is_flag(boma, *FIGHTER_INSTANCE_WORK_ID_FLAG_FINAL) {
-                        ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
-                    }
+            }
+        }
+        
+        // THIRD: Hide all other eye expressions for this stage
+        match player_state.stage {
+            EvolutionStage::Gastly => {
+                for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() {
+                    set_mesh_visibility(boma, *eye_hash, false);
                 }
             }
-            
-            // THIRD: Hide all other eye expressions for this stage
-            match player_state.stage {
-                EvolutionStage::Gastly => {
-                    for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() {
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false);
-                    }
+            EvolutionStage::Haunter => {
+                for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() {
+                    set_mesh_visibility(boma, *eye_hash, false);
                 }
-                EvolutionStage::Haunter => {
-                    for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() {
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false);
-                    }
-                    // Also hide Gastly body for Haunter during final smash
-                    if player_state.is_in_final_smash_form || 
-                       WorkModule::is_flag(boma, *FIGHTER_INSTANCE_WORK_ID_FLAG_FINAL) {
-                        ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
-                    }
+                // Also hide Gastly body for Haunter during final smash
+                if player_state.is_in_final_smash_form || 
+                   WorkModule::is_flag(boma, *FIGHTER_INSTANCE_WORK_ID_FLAG_FINAL) {
+                    set_mesh_visibility(boma, *GASTLY_BODY, false);
                 }
-                EvolutionStage::Gengar => {
-                    for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() {
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false);
-                    }
+            }
+            EvolutionStage::Gengar => {
+                for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() {
+                    set_mesh_visibility(boma, *eye_hash, false);
                 }
             }
-            
-            // FOURTH: Show ONLY the target blink mesh
-            ModelModule::set_mesh_visibility(boma, blink_mesh, true);
-            
-            return true; // Indicate we're overriding eye expression for win_3 motions
         }
+        
+        // FOURTH: Show ONLY the target blink mesh
+        set_mesh_visibility(boma, blink_mesh, true);
+        
+        return Some(blink_mesh); // Overriding the eye expression for the win pose
     }
     
-    false // Not in win_3 motion range
+    None // Not in a scripted win pose
 }
 
 unsafe fn handle_evolution_readiness_icons(boma: *mut BattleObjectModuleAccessor, player_state: &mut PlayerEvolutionState, fighter: &mut L2CFighterCommon) {
@@ -461,7 +482,7 @@ unsafe fn handle_evolution_readiness_icons(boma: *mut BattleObjectModuleAccessor
     let should_suppress_icons = player_state.is_evolving ||
                                 player_state.everstone_effect_active ||
                                 player_state.stage == EvolutionStage::Gengar ||
-                                (player_state.linking_cord_evo_attempt_icon_is_pos_sensitive && player_state.linking_cord_evo_attempt_icon_timer > 0);
+                                player_state.icons.is_active(OverheadIcon::LinkingCordEvolving);
 
     if should_suppress_icons {
         if player_state.dmg_t_icon_display_timer > 0 || player_state.dmg_t_icon_is_locked_out ||
@@ -470,10 +491,10 @@ unsafe fn handle_evolution_readiness_icons(boma: *mut BattleObjectModuleAccessor
            player_state.dmg_se_icon_display_timer > 0 || player_state.dmg_se_icon_is_locked_out {
             player_state.reset_evo_readiness_icons();
         }
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
+        set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
+        set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
+        set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
+        set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
         
         // Clean up charge bullet effects when suppressing icons
         EffectModule::kill_kind(boma, Hash40::new("bayonetta_chargebullet_hold"), false, true);
@@ -609,11 +630,11 @@ unsafe fn handle_evolution_readiness_icons(boma: *mut BattleObjectModuleAccessor
 
             player_state.dmg_t_icon_display_timer = 0;
             player_state.dmg_t_icon_is_locked_out = true;
-            ModelModule::set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
+            set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
 
             player_state.dmg_d_icon_display_timer = 0;
             player_state.dmg_d_icon_is_locked_out = true;
-            ModelModule::set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
+            set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
         }
     }
 
@@ -656,29 +677,29 @@ unsafe fn handle_evolution_readiness_icons(boma: *mut BattleObjectModuleAccessor
 
     // STG1_DMG_T (show for both Gastly and Haunter stages)
     if player_state.dmg_t_icon_display_timer > 0 && (player_state.stage == EvolutionStage::Gastly || player_state.stage == EvolutionStage::Haunter) {
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_T_ICON, true);
+        set_mesh_visibility(boma, *STG1_DMG_T_ICON, true);
         player_state.dmg_t_icon_display_timer -= 1;
         if player_state.dmg_t_icon_display_timer == 0 {
             player_state.dmg_t_icon_is_locked_out = true;
         }
     } else {
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
+        set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
     }
 
     // STG1_DMG_D (show for both Gastly and Haunter stages)
     if player_state.dmg_d_icon_display_timer > 0 && (player_state.stage == EvolutionStage::Gastly || player_state.stage == EvolutionStage::Haunter) {
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_D_ICON, true);
+        set_mesh_visibility(boma, *STG1_DMG_D_ICON, true);
         player_state.dmg_d_icon_display_timer -= 1;
         if player_state.dmg_d_icon_display_timer == 0 {
             player_state.dmg_d_icon_is_locked_out = true;
         }
     } else {
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
+        set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
     }
 
     // STG2_DMG_SS with Charge Bullet Hold effect (show for both Gastly and Haunter stages)
     if player_state.dmg_ss_icon_display_timer > 0 && (player_state.stage == EvolutionStage::Gastly || player_state.stage == EvolutionStage::Haunter) {
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SS_ICON, true);
+        set_mesh_visibility(boma, *STG2_DMG_SS_ICON, true);
 
         //  DON'T play evolve_ss sound here - let the persistent sound system handle it
         // The persistent sound system will detect dmg_ss_icon_display_timer > 0 and play the sound
@@ -727,7 +748,7 @@ unsafe fn handle_evolution_readiness_icons(boma: *mut BattleObjectModuleAccessor
             }
         }
     } else {
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
+        set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
         // Clean up charge bullet hold effect when SS icon not visible or wrong stage
         if player_state.dmg_se_icon_display_timer == 0 || 
            (player_state.stage != EvolutionStage::Gastly && player_state.stage != EvolutionStage::Haunter) {
@@ -741,7 +762,7 @@ unsafe fn handle_evolution_readiness_icons(boma: *mut BattleObjectModuleAccessor
     
     // STG2_DMG_SE with both Charge Bullet effects (show for both Gastly and Haunter stages)
     if player_state.dmg_se_icon_display_timer > 0 && (player_state.stage == EvolutionStage::Gastly || player_state.stage == EvolutionStage::Haunter) {
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SE_ICON, true);
+        set_mesh_visibility(boma, *STG2_DMG_SE_ICON, true);
         
         let instance_key = get_instance_key(boma) as usize;
         
@@ -809,7 +830,7 @@ unsafe fn handle_evolution_readiness_icons(boma: *mut BattleObjectModuleAccessor
             // The persistent sound system will detect is_evolving and start the evolving sound
         }
     } else {
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
+        set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
         
         // Clean up charge bullet effects when SE icon not visible or wrong stage
         let instance_key = get_instance_key(boma) as usize;
@@ -860,9 +881,16 @@ unsafe fn handle_evolution_readiness_icons(boma: *mut BattleObjectModuleAccessor
     }
 }
 
-// Shiny detection
+// Shiny detection (shiny slot, or a won shiny odds roll this match)
 unsafe fn detect_shiny_slot(boma: *mut BattleObjectModuleAccessor) -> bool {
-    crate::is_shiny_gastly_costume(boma)
+    crate::gastly::shiny_odds::is_shiny_this_match(boma)
+}
+
+// Sparkle effect from the slot's palette (default rosetta_tico_happy_light)
+unsafe fn shiny_sparkle_effect(boma: *mut BattleObjectModuleAccessor) -> String {
+    crate::gastly::palettes::palette_for(boma)
+        .map(|palette| palette.sparkle_effect)
+        .unwrap_or_else(|| crate::gastly::palettes::DEFAULT_SPARKLE_EFFECT.to_string())
 }
 
 unsafe fn spawn_shiny_effect(
@@ -887,9 +915,11 @@ unsafe fn spawn_shiny_effect(
     let position_offset = Vector3f { x: 0.0, y: 0.0, z: 0.0 };
     let rotation_vector = Vector3f { x: 0.0, y: 90.0, z: 0.0 };
     
+    let palette = crate::gastly::palettes::palette_for(boma);
+    let sparkle_effect = shiny_sparkle_effect(boma);
     let effect_handle = EffectModule::req_follow(
         boma,
-        Hash40::new("rosetta_tico_happy_light"),
+        Hash40::new(&sparkle_effect),
         Hash40::new("body"),
         &position_offset,
         &rotation_vector,
@@ -898,6 +928,9 @@ unsafe fn spawn_shiny_effect(
     ) as u32;
     
     if effect_handle != u64::MAX as u32 && effect_handle != 0 {
+        if let Some([r, g, b]) = palette.and_then(|palette| palette.sparkle_color) {
+            EffectModule::set_rgb(boma, effect_handle, r, g, b);
+        }
         EffectModule::set_rate(boma, effect_handle, 0.7);
         WorkModule::set_flag(boma, true, FIGHTER_PURIN_INSTANCE_WORK_ID_FLAG_SHINY_EFFECT_ACTIVE);
         WorkModule::set_float(boma, 0.0, FIGHTER_PURIN_INSTANCE_WORK_ID_FLOAT_SHINY_EFFECT_TIMER);
@@ -960,7 +993,7 @@ unsafe fn handle_shiny_effects(
         
         if new_timer >= 90.0 {
             WorkModule::set_flag(boma, false, FIGHTER_PURIN_INSTANCE_WORK_ID_FLAG_SHINY_EFFECT_ACTIVE);
-            EffectModule::kill_kind(boma, Hash40::new("rosetta_tico_happy_light"), false, true);
+            EffectModule::kill_kind(boma, Hash40::new(&shiny_sparkle_effect(boma)), false, true);
         }
     }
 }
@@ -1080,6 +1113,7 @@ pub unsafe extern "C" fn gastly_fighter_frame_callback(fighter: &mut L2CFighterC
                 crate::gastly::reset_heal_tracker_for_entry(boma);
                 
                 // Force visual update
+                crate::gastly::mesh_visibility::invalidate_mesh_cache(instance_key);
                 crate::gastly::visuals::update_body_and_unique_parts_visibility(boma, crate::gastly::player_state::EvolutionStage::Gastly);
                 crate::gastly::visuals::set_active_eye_mesh(boma, new_state, None);
                 
@@ -1142,6 +1176,9 @@ pub unsafe extern "C" fn gastly_fighter_frame_callback(fighter: &mut L2CFighterC
     // ===== POKECENTER LOGIC (for Purin only) =====
     let current_status_val: i32 = StatusModule::status_kind(boma);
 
+    // The animation's eye, read before the vanilla eye meshes are hidden below
+    player_state.visible_vanilla_eye = visible_vanilla_eye(boma);
+
     let current_damage = DamageModule::damage(boma, 0);
     let instance_idx_pokecenter = instance_key as usize; // Convert to usize for array indexing
 
@@ -1220,10 +1257,11 @@ pub unsafe extern "C" fn gastly_fighter_frame_callback(fighter: &mut L2CFighterC
     }
 
     let mut states_map_writer = FIGHTER_STATES.write();
-    let is_new_state = !states_map_writer.contains_key(&instance_key);
+    let is_new_state = claim_player_state(&mut states_map_writer, instance_key);
 
     // Initialize shiny detection for new players
     if is_new_state {
+        crate::gastly::mesh_visibility::invalidate_mesh_cache(instance_key);
         let player_state = states_map_writer.entry(instance_key).or_insert_with(PlayerEvolutionState::new);
         player_state.is_shiny = detect_shiny_slot(boma);
         if player_state.is_shiny {
@@ -1459,22 +1497,25 @@ pub unsafe extern "C" fn gastly_fighter_frame_callback(fighter: &mut L2CFighterC
             EffectModule::kill_kind(boma, Hash40::new("sys_final_aura"), false, true);
             
             // Hide normal Gengar completely
-            ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, false);
-            ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, false);
-            ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
-            ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
+            set_mesh_visibility(boma, *GENGAR_BODY, false);
+            set_mesh_visibility(boma, *GENGAR_IRIS, false);
+            set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
+            set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
+            for tongue in MIRRORED_TONGUES.iter() {
+                set_mesh_visibility(boma, *tongue, false);
+            }
             for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() {
-                ModelModule::set_mesh_visibility(boma, *eye_hash, false);
+                set_mesh_visibility(boma, *eye_hash, false);
             }
             
             // Hide Gastly body during special forms
-            ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
+            set_mesh_visibility(boma, *GASTLY_BODY, false);
             
             // Show appropriate special form
             if player_state.mega_gengar_form_active {
-                ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, true);
+                set_mesh_visibility(boma, *MEGA_GENGAR_BODY, true);
             } else if player_state.giga_gengar_form_active {
-                ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, true);
+                set_mesh_visibility(boma, *GIGA_GENGAR_BODY, true);
             }
             
             player_state.is_in_final_smash_form = true;
@@ -1491,16 +1532,16 @@ pub unsafe extern "C" fn gastly_fighter_frame_callback(fighter: &mut L2CFighterC
     if current_status == 0x1E9 { // FINAL_END
         
         // AGGRESSIVELY hide Gastly body during FINAL_END
-        ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
+        set_mesh_visibility(boma, *GASTLY_BODY, false);
         
         // Ensure special form stays visible during FINAL_END
         if player_state.is_in_final_smash_form {
             if player_state.mega_gengar_form_active {
-                ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, true);
-                ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
+                set_mesh_visibility(boma, *MEGA_GENGAR_BODY, true);
+                set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
             } else if player_state.giga_gengar_form_active {
-                ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, true);
-                ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
+                set_mesh_visibility(boma, *GIGA_GENGAR_BODY, true);
+                set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
             }
         }
     }
@@ -1527,17 +1568,17 @@ pub unsafe extern "C" fn gastly_fighter_frame_callback(fighter: &mut L2CFighterC
         EffectModule::kill_kind(boma, Hash40::new("sys_final_aura2"), false, true);
         
         // Hide ALL special meshes
-        ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
-        ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
-        ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
+        set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
+        set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
+        set_mesh_visibility(boma, *GASTLY_BODY, false);
         
         // Reset flag
         player_state.is_in_final_smash_form = false;
         
         // Force normal Gengar appearance
-        ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, true);
-        ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, true);
-        ModelModule::set_mesh_visibility(boma, *GENGAR_EYE_N, true);
+        set_mesh_visibility(boma, *GENGAR_BODY, true);
+        set_mesh_visibility(boma, *GENGAR_IRIS, true);
+        set_mesh_visibility(boma, *GENGAR_EYE_N, true);
         
     }
 }
@@ -1581,7 +1622,43 @@ unsafe fn handle_grab_effect_cleanup(boma: *mut BattleObjectModuleAccessor, play
     player_state.current_frame += 1;
     player_state.manual_linking_cord_evo_attempted_this_frame = false;
     player_state.linking_cord_consumed_everstone_this_frame = false;
-    
+
+    // Count hits landed by this player (published by hit_tracking_hook)
+    let landed_hits = hit_records::take_hits_by(instance_key, &mut player_state.last_hit_record_sequence);
+    if !player_state.is_evolving {
+        player_state.hits_landed_this_stage += landed_hits.len() as i32;
+    }
+
+    // Per-match stats; on the results screen show the stage reached and write the match summary
+    let final_smash_form = if WorkModule::is_flag(boma, *FIGHTER_INSTANCE_WORK_ID_FLAG_FINAL) {
+        Some(if player_state.mega_gengar_form_active {
+            match_stats::FinalSmashForm::MegaGengar
+        } else if player_state.giga_gengar_form_active {
+            match_stats::FinalSmashForm::GigaGengar
+        } else {
+            match_stats::FinalSmashForm::Gengar
+        })
+    } else {
+        None
+    };
+    if let Some(shiny_roll) = crate::gastly::shiny_odds::roll_on_match_entry(boma, current_status_val) {
+        player_state.shiny_roll = shiny_roll;
+        player_state.is_shiny = crate::gastly::shiny_odds::is_shiny_this_match(boma);
+    }
+    let color_id_u32 = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR) as u32;
+    match_stats::track_match_frame(instance_key, my_entry_id_u32, color_id_u32, player_state.is_shiny, current_status_val, player_state.stage, final_smash_form);
+
+    if match_stats::is_results_status(current_status_val) {
+        if let Some(results_stage) = match_stats::results_stage(instance_key) {
+            if player_state.stage != results_stage && !player_state.is_evolving {
+                player_state.stage = results_stage;
+                player_state.evolution_target_stage = results_stage;
+                update_body_and_unique_parts_visibility(boma, results_stage);
+                set_active_eye_mesh(boma, player_state, None);
+            }
+        }
+        match_stats::write_match_summary_once();
+    }
 
     let current_total_damage_on_self = DamageModule::damage(boma, 0);
 
@@ -1713,6 +1790,7 @@ unsafe fn handle_grab_effect_cleanup(boma: *mut BattleObjectModuleAccessor, play
     if is_dead_or_rebirth {
                
         cleanup_all_evolution_sounds_on_death(boma);
+        crate::gastly::mesh_visibility::invalidate_mesh_cache(instance_key);
 
         // Preserve shiny timer states during cleanup
         let preserve_sparkle_timer = WorkModule::get_float(boma, FIGHTER_PURIN_INSTANCE_WORK_ID_FLOAT_SHINY_SPARKLE_TIMER);
@@ -1802,20 +1880,20 @@ unsafe fn handle_grab_effect_cleanup(boma: *mut BattleObjectModuleAccessor, play
         // Clean up UI state on death AFTER player state reset to prevent override
         reset_ui_state_on_death(my_entry_id_u32);
 
-        ModelModule::set_mesh_visibility(boma, *LINKING_CORD_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *EVERSTONE_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *EVERSTONE_X_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *GENGARITE_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *DYNAMAX_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
-        ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
+        set_mesh_visibility(boma, *LINKING_CORD_ICON, false);
+        set_mesh_visibility(boma, *EVERSTONE_ICON, false);
+        set_mesh_visibility(boma, *EVERSTONE_X_ICON, false);
+        set_mesh_visibility(boma, *GENGARITE_ICON, false);
+        set_mesh_visibility(boma, *DYNAMAX_ICON, false);
+        set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
+        set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
+        set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
+        set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
+        set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
+        set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
 
         for vanilla_eye in PURIN_VANILLA_EYES_TO_HIDE.iter() {
-            ModelModule::set_mesh_visibility(boma, *vanilla_eye, false);
+            force_mesh_visibility(boma, *vanilla_eye, false);
         }
 
         update_body_and_unique_parts_visibility_with_enforcement(boma, EvolutionStage::Gastly, player_state);
@@ -1825,25 +1903,28 @@ unsafe fn handle_grab_effect_cleanup(boma: *mut BattleObjectModuleAccessor, play
     }
 
     for vanilla_eye in PURIN_VANILLA_EYES_TO_HIDE.iter() {
-        ModelModule::set_mesh_visibility(boma, *vanilla_eye, false);
+        force_mesh_visibility(boma, *vanilla_eye, false);
     }
 
     handle_grab_effect_cleanup(boma, player_state);
 
     handle_final_smash_aggressive_fixes(boma, player_state, fighter);
     
+    input_sequence::update_command_input(boma, player_state);
+    evolution_items::handle_evolution_items(boma, player_state);
     handle_icon_toggles_and_effects(boma, player_state);
+    billboard::update_billboards(boma, player_state, instance_key as usize);
     
     // Handle readiness icons BEFORE evolution process
     handle_evolution_readiness_icons(boma, player_state, fighter);
 
     // EVERSTONE ICON FIX HERE:
-    if player_state.everstone_icon_active || player_state.everstone_x_icon_active {
+    if player_state.icons.hides_readiness_icons() {
         // Force hide readiness icons when everstone is active
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
+        set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
+        set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
+        set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
+        set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
     }
     
     // Check for final smash cancellation during evolution
@@ -1880,34 +1961,36 @@ unsafe fn handle_grab_effect_cleanup(boma: *mut BattleObjectModuleAccessor, play
     }
 
     if !player_state.is_evolving && !player_state.is_in_final_smash_form {
-        let mut expression_override: Option<PhxHash40> = None;
-
-        // Handle win_3 motion blink override first
-        let is_win_3_blink = handle_win_3_blink(boma, player_state);
-
-        //  Check for down special blink override
-        let is_down_special_blink = if !is_win_3_blink {
-            handle_down_special_blink(boma, player_state)
-        } else {
-            false
+        // Handle win pose eye override first
+        let forced_eye = match handle_win_pose_eyes(boma, player_state) {
+            Some(mesh) => Some(mesh),
+            //  Check for down special blink override
+            None => handle_down_special_blink(boma, player_state),
         };
 
-        if !is_down_special_blink && !is_win_3_blink {
+        if forced_eye.is_none() {
             // Only do normal expression processing if not in down special blink
-            if let Some(vanilla_detected_expression) = player_state.detect_vanilla_expression(boma) {
-                expression_override = Some(vanilla_detected_expression);
-            } else {
-                player_state.blink_timer -= 1;
-                if player_state.blink_timer <= 0 {
-                    player_state.advance_blink_phase();
-                }
-            }
+            let expression_input = ExpressionInput {
+                scripted: player_state.detect_scripted_expression(boma),
+                reaction: player_state.detect_vanilla_expression(boma),
+                shielding: expression_engine::is_shielding(boma),
+                gaze: expression_engine::opponent_gaze(boma),
+            };
+            let stage = player_state.stage;
+            player_state.expressions.update(stage, &expression_input, &mut |min, max| random_module::rand_range_i32(min, max));
             
             //  Only call set_active_eye_mesh if NOT in down special blink
-            set_active_eye_mesh(boma, player_state, expression_override);
+            set_active_eye_mesh(boma, player_state, None);
         }
         // If is_down_special_blink is true, we DON'T call set_active_eye_mesh
         // because handle_down_special_blink already set the correct mesh
+
+        if expression_audit::is_enabled() {
+            let shown_eye = forced_eye.unwrap_or_else(|| {
+                eye_choice_mesh(player_state.stage, player_state.expressions.current(), &|mesh| is_mesh_available(boma, mesh))
+            });
+            expression_audit::record_frame(boma, player_state.stage, player_state.visible_vanilla_eye, shown_eye, forced_eye.is_some());
+        }
     }
 
     // UI management is now handled globally above to detect character switches
@@ -1920,7 +2003,7 @@ unsafe fn handle_grab_effect_cleanup(boma: *mut BattleObjectModuleAccessor, play
         
         if new_timer >= 90.0 {
             WorkModule::set_flag(boma, false, FIGHTER_PURIN_INSTANCE_WORK_ID_FLAG_SHINY_EFFECT_ACTIVE);
-            EffectModule::kill_kind(boma, Hash40::new("rosetta_tico_happy_light"), false, true);
+            EffectModule::kill_kind(boma, Hash40::new(&shiny_sparkle_effect(boma)), false, true);
         }
     }
 
@@ -2970,8 +3053,7 @@ unsafe fn reset_evolution_progress_on_match_start(
             player_state.is_evolving = false;
             player_state.linking_cord_active = false;
             player_state.evolution_timer = 0;
-            player_state.linking_cord_evo_attempt_icon_timer = 0;
-            player_state.linking_cord_evo_attempt_icon_is_pos_sensitive = false;
+            player_state.icons.cancel(OverheadIcon::LinkingCordEvolving);
             
             // Apply evolution cancellation penalty
             player_state.evo_attempt_delay_damage_taken_penalty += 15.0;
@@ -2988,10 +3070,10 @@ unsafe fn reset_evolution_progress_on_match_start(
         player_state.last_evolution_confirmation_frame = -1;
 
         // Force hide all readiness icons
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
-        ModelModule::set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
+        set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
+        set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
+        set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
+        set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
 
     }
 
@@ -3270,6 +3352,18 @@ unsafe extern "C" fn gastly_early_frame_callback(fighter: &mut L2CFighterCommon)
 }
 
 pub fn install() {
+    // Optional user config - every subsystem falls back to its built-in defaults without it
+    crate::gastly::config::load_config();
+    crate::gastly::dark_moves::load_dark_move_table();
+    crate::gastly::palettes::load_palette_table();
+    crate::gastly::shiny_odds::load_shiny_odds();
+    crate::gastly::command_bindings::load_command_bindings();
+    crate::gastly::input_history::load_input_leniency();
+    crate::gastly::item_rules::load_item_rules();
+    crate::gastly::evolution_items::load_items_mode();
+    crate::gastly::billboard::load_billboard_config();
+    crate::gastly::expression_audit::load_expression_audit();
+
     skyline::install_hooks!(hit_tracking_hook);
 
     crate::gastly::agent_init::install();
@@ -3330,4 +3424,78 @@ pub fn install_acmd_with_costumes() {
     crate::gastly::acmdsound::install_acmd_sound_with_costumes(&costume);
     crate::gastly::attack_voices::install_attack_voices_remaining_with_costumes(&costume);
     crate::gastly::acmd::install_acmd_with_costumes(&costume);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instance_keys_are_unique_and_map_back_to_their_entry() {
+        let mut seen = std::collections::HashSet::new();
+        for entry_id in 0..MAX_FIGHTER_ENTRIES {
+            for color_id in 0..INSTANCE_KEYS_PER_ENTRY {
+                let key = instance_key_for(entry_id, color_id);
+                assert!(key < 256);
+                assert!(seen.insert(key), "entry {} color {} collides", entry_id, color_id);
+                assert_eq!(entry_id_from_instance_key(key), entry_id);
+            }
+        }
+        // Out of range entries fall back to entry 0 instead of overflowing the 256-slot tables
+        assert_eq!(entry_id_from_instance_key(instance_key_for(40, 3)), 0);
+    }
+
+    #[test]
+    fn ditto_on_the_same_costume_number_gets_separate_keys() {
+        // Both players on the same slot, then regular c00 vs shiny c01
+        assert_ne!(instance_key_for(0, 0), instance_key_for(1, 0));
+        assert_ne!(instance_key_for(0, 0), instance_key_for(1, 1));
+    }
+
+    #[test]
+    fn pruning_keeps_other_entries_and_drops_the_old_costume() {
+        let mut states = HashMap::new();
+        for key in [instance_key_for(0, 0), instance_key_for(0, 1), instance_key_for(1, 0), instance_key_for(2, 5)] {
+            states.insert(key, PlayerEvolutionState::new());
+        }
+        prune_stale_states_for_entry(&mut states, instance_key_for(0, 1));
+        let mut keys: Vec<u32> = states.keys().copied().collect();
+        keys.sort();
+        assert_eq!(keys, vec![instance_key_for(0, 1), instance_key_for(1, 0), instance_key_for(2, 5)]);
+    }
+
+    // Three players through a frame loop: a regular/shiny ditto on P1/P2 and P3 swapping costume
+    // mid-session (training mode). Each frame every player claims its state and takes damage/lands hits.
+    #[test]
+    fn several_players_through_a_frame_loop_keep_their_own_state() {
+        let mut states: HashMap<u32, PlayerEvolutionState> = HashMap::new();
+        let players = |frame: u32| [(0u32, 0u32, false), (1, 1, true), (2, if frame < 50 { 2 } else { 3 }, false)];
+
+        for frame in 0..100u32 {
+            for (entry_id, color_id, shiny) in players(frame) {
+                let instance_key = instance_key_for(entry_id, color_id);
+                if claim_player_state(&mut states, instance_key) {
+                    states.get_mut(&instance_key).unwrap().is_shiny = shiny;
+                }
+                let state = states.get_mut(&instance_key).unwrap();
+                state.damage_received_this_stage += (entry_id + 1) as f32;
+                if frame % (entry_id + 2) == 0 {
+                    state.hits_landed_this_stage += 1;
+                }
+            }
+            assert_eq!(states.len(), 3, "frame {}", frame);
+        }
+
+        let state = |entry_id, color_id| &states[&instance_key_for(entry_id, color_id)];
+        assert_eq!(state(0, 0).damage_received_this_stage, 100.0);
+        assert_eq!(state(0, 0).hits_landed_this_stage, 50);
+        assert!(!state(0, 0).is_shiny);
+        assert_eq!(state(1, 1).damage_received_this_stage, 200.0);
+        assert_eq!(state(1, 1).hits_landed_this_stage, 34);
+        assert!(state(1, 1).is_shiny);
+        // The swapped-in costume started fresh and the old one is gone
+        assert_eq!(state(2, 3).damage_received_this_stage, 150.0);
+        assert_eq!(state(2, 3).hits_landed_this_stage, 12);
+        assert!(!states.contains_key(&instance_key_for(2, 2)));
+    }
+}
@@ -0,0 +1,172 @@
+// src/gastly/palettes.rs
+
+// Named shiny palettes. A palette recolours everything the shiny bool used to hard-code:
+// Gastly aura, the dark-hit tint this player inflicts, the evolution flash and the shiny sparkle.
+//
+// Slots pick a palette with `palette = <name>` in their marker; shiny slots without one use "shiny".
+// Palettes come from the built-ins below plus `[palette.<name>]` sections in config.ini:
+//
+//   [palette.shadow]
+//   aura_color = 0.35, 0.05, 0.45
+//   dark_flash_color = 0.4, 0.0, 0.1, 0.8
+//   dark_tint_color = 0.8, 0.3, 0.4
+//   evo_flash_color = 1.8, 0.4, 1.8
+//   sparkle_effect = rosetta_tico_happy_light
+//   sparkle_color = 1.0, 0.3, 1.0
+//
+// Unset colours keep the regular (non-shiny) look for that part; a colour that doesn't parse is
+// ignored with a warning.
+
+use std::collections::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use smash::app::BattleObjectModuleAccessor;
+
+use crate::gastly::config::{IniDocument, IniSection};
+
+pub const DEFAULT_SHINY_PALETTE: &str = "shiny";
+pub const DEFAULT_SPARKLE_EFFECT: &str = "rosetta_tico_happy_light";
+pub const PALETTE_REPORT_PATH: &str = "sd:/ultimate/gastly/palette_report.txt";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShinyPalette {
+    pub name: String,
+    pub aura_color: Option<[f32; 3]>,
+    pub dark_flash_color: Option<[f32; 4]>, // FLASH backend colour for dark hits this player lands
+    pub dark_tint_color: Option<[f32; 3]>,  // Material backend tint for the same
+    pub evo_flash_color: Option<[f32; 3]>,  // Evolution transform effect RGB
+    pub sparkle_effect: String,
+    pub sparkle_color: Option<[f32; 3]>,
+}
+
+impl ShinyPalette {
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            aura_color: None,
+            dark_flash_color: None,
+            dark_tint_color: None,
+            evo_flash_color: None,
+            sparkle_effect: DEFAULT_SPARKLE_EFFECT.to_string(),
+            sparkle_color: None,
+        }
+    }
+}
+
+fn builtin_palettes() -> Vec<ShinyPalette> {
+    vec![
+        // The original shiny look: blue aura, default sparkle
+        ShinyPalette {
+            aura_color: Some([0.42, 0.75, 1.3]),
+            ..ShinyPalette::named(DEFAULT_SHINY_PALETTE)
+        },
+        ShinyPalette {
+            aura_color: Some([0.35, 0.05, 0.45]),
+            dark_flash_color: Some([0.4, 0.0, 0.1, 0.8]),
+            dark_tint_color: Some([0.8, 0.3, 0.4]),
+            evo_flash_color: Some([1.8, 0.4, 1.8]),
+            sparkle_color: Some([1.0, 0.3, 1.0]),
+            ..ShinyPalette::named("shadow")
+        },
+    ]
+}
+
+// A colour list with one of `lengths` components; anything else is ignored and reported
+fn color_list(palette: &str, section: &IniSection, key: &str, lengths: &[usize], problems: &mut Vec<String>) -> Option<Vec<f32>> {
+    let value = section.get(key)?;
+    let color = section.get_f32_list(key).filter(|c| lengths.contains(&c.len()));
+    if color.is_none() {
+        problems.push(format!("[palette.{}] can't parse {} = '{}', ignored", palette, key, value));
+    }
+    color
+}
+
+fn rgb(palette: &str, section: &IniSection, key: &str, problems: &mut Vec<String>) -> Option<[f32; 3]> {
+    color_list(palette, section, key, &[3], problems).map(|c| [c[0], c[1], c[2]])
+}
+
+fn apply_palette_section(palette: &mut ShinyPalette, section: &IniSection, problems: &mut Vec<String>) {
+    let name = palette.name.clone();
+    if let Some(color) = rgb(&name, section, "aura_color", problems) {
+        palette.aura_color = Some(color);
+    }
+    if let Some(color) = color_list(&name, section, "dark_flash_color", &[3, 4], problems) {
+        let alpha = color.get(3).copied().unwrap_or(0.8);
+        palette.dark_flash_color = Some([color[0], color[1], color[2], alpha]);
+    }
+    if let Some(color) = rgb(&name, section, "dark_tint_color", problems) {
+        palette.dark_tint_color = Some(color);
+    }
+    if let Some(color) = rgb(&name, section, "evo_flash_color", problems) {
+        palette.evo_flash_color = Some(color);
+    }
+    if let Some(effect) = section.get("sparkle_effect").filter(|effect| !effect.is_empty()) {
+        palette.sparkle_effect = effect.to_string();
+    }
+    if let Some(color) = rgb(&name, section, "sparkle_color", problems) {
+        palette.sparkle_color = Some(color);
+    }
+}
+
+// Built-ins, then `[palette.<name>]` overrides. Unknown names add new palettes.
+// Also returns the values that were ignored.
+pub fn build_palette_table(config: &IniDocument) -> (Vec<ShinyPalette>, Vec<String>) {
+    let mut table = builtin_palettes();
+    let mut problems = Vec::new();
+
+    for (name, section) in config.sections_with_prefix("palette") {
+        match table.iter_mut().find(|palette| palette.name == name) {
+            Some(palette) => apply_palette_section(palette, section, &mut problems),
+            None => {
+                let mut palette = ShinyPalette::named(name);
+                apply_palette_section(&mut palette, section, &mut problems);
+                table.push(palette);
+            }
+        }
+    }
+
+    (table, problems)
+}
+
+static PALETTE_TABLE: Lazy<RwLock<HashMap<String, ShinyPalette>>> = Lazy::new(|| {
+    RwLock::new(builtin_palettes().into_iter().map(|palette| (palette.name.clone(), palette)).collect())
+});
+
+pub fn load_palette_table() {
+    let config = crate::gastly::config::GASTLY_CONFIG.read();
+    let (table, problems) = build_palette_table(&config);
+    *PALETTE_TABLE.write() = table
+        .into_iter()
+        .map(|palette| (palette.name.clone(), palette))
+        .collect();
+    crate::gastly::config::write_report(PALETTE_REPORT_PATH, &problems);
+}
+
+pub fn palette_by_name(name: &str) -> Option<ShinyPalette> {
+    PALETTE_TABLE.read().get(name).cloned()
+}
+
+// Palette for a costume slot: the marker's palette, else "shiny" for shiny slots, else none.
+// (A won shiny odds roll is per player, see palette_for.)
+pub fn slot_palette(color_id: usize) -> Option<ShinyPalette> {
+    let marker = crate::gastly::markers::slot_marker(color_id);
+    match marker.palette.as_deref() {
+        Some(name) => palette_by_name(name),
+        None if unsafe { crate::SHINY_COLORS.get(color_id).copied().unwrap_or(false) } => palette_by_name(DEFAULT_SHINY_PALETTE),
+        None => None,
+    }
+}
+
+pub unsafe fn palette_for(boma: *mut BattleObjectModuleAccessor) -> Option<ShinyPalette> {
+    if !crate::is_marked_gastly_costume(boma) && !crate::is_shiny_gastly_costume(boma) {
+        return None;
+    }
+    let color_id = smash::app::lua_bind::WorkModule::get_int(boma, *smash::lib::lua_const::FIGHTER_INSTANCE_WORK_ID_INT_COLOR) as usize;
+    slot_palette(color_id).or_else(|| {
+        if crate::gastly::shiny_odds::shiny_roll_for(boma).is_won() {
+            palette_by_name(DEFAULT_SHINY_PALETTE)
+        } else {
+            None
+        }
+    })
+}
@@ -2,27 +2,28 @@
 
 use std::collections::HashMap;
 use smash::app::BattleObjectModuleAccessor;
-use smash::app::lua_bind::{WorkModule, DamageModule, ModelModule, VisibilityModule, AttackModule, StatusModule};
+use smash::app::lua_bind::{WorkModule, DamageModule, ModelModule, AttackModule, StatusModule};
 use smash::lua2cpp::L2CFighterCommon;
 use smash_script::macros;
 use smash::lib::lua_const::*;
 use smash::phx::{Hash40, Vector3f};
 use smash::app::utility;
-use skyline::libc::c_uint;
 
 // Import from our modules
 use crate::gastly::constants::*;
+use crate::gastly::mesh_visibility::set_mesh_visibility;
 use crate::gastly::visuals::{update_body_and_unique_parts_visibility, set_active_eye_mesh, handle_final_smash_model_swap};
-use crate::gastly::icon_management::{enforce_icon_exclusivity, update_is_any_pos_sensitive_icon_active_flag};
-use crate::gastly::random_module;
+use crate::gastly::icon_manager::{IconManager, OverheadIcon};
+use crate::gastly::expression_engine::{ExpressionEngine, expression_mesh};
+use crate::gastly::expression_map::expression_for_vanilla_eye;
 use crate::gastly::effects::kill_gastly_aura_on_evolution;
+use crate::gastly::input_sequence::{InputSequenceMatcher, CommandEvent, EvolutionCommand, InputFrame};
+use crate::gastly::input_history::InputHistory;
+use crate::gastly::item_rules::LinkingCordUsage;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EvolutionStage { Gastly, Haunter, Gengar }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum BlinkPhase { Open, HalfClose, FullClose, HalfOpen }
-
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum HitboxDetectionMethod {
     None,
@@ -35,10 +36,10 @@ pub enum HitboxDetectionMethod {
 #[derive(Debug, Clone)]
 pub struct PlayerEvolutionState {
     pub stage: EvolutionStage,
-    pub blink_timer: i32,
-    pub blink_phase: BlinkPhase,
+    pub expressions: ExpressionEngine, // Blink timelines and expression interrupts
     pub damage_received_this_stage: f32,
     pub hits_landed_this_stage: i32,
+    pub last_hit_record_sequence: u64, // Cursor into hit_records for hits landed by this player
     pub previous_total_damage: f32,
 
     pub shadowball_status_frames: i32,
@@ -56,6 +57,7 @@ pub struct PlayerEvolutionState {
 
     pub vanilla_expression_tracking: bool,
     pub last_vanilla_expression: Hash40,
+    pub visible_vanilla_eye: Option<Hash40>, // Sampled each frame before the vanilla eyes are hidden
     pub vanilla_expression_changed: bool,
 
     pub last_attackmodule_check_frame: i32,
@@ -77,43 +79,22 @@ pub struct PlayerEvolutionState {
     pub current_frame: i32,
     pub last_debug_taunt_frame: i32,
 
-    pub linking_cord_visual_icon_active: bool,
-    pub linking_cord_visual_icon_timer: i32,
-    pub up_taunt_press_count_linking_cord: u8,
-    pub last_up_taunt_input_frame_linking_cord: i32,
     pub manual_linking_cord_evo_attempted_this_frame: bool,
+    pub linking_cord_usage: LinkingCordUsage, // Uses/cooldown this stock (see item_rules)
     pub linking_cord_consumed_everstone_this_frame: bool,
 
-    pub linking_cord_evo_attempt_icon_timer: i32,
-    pub linking_cord_evo_attempt_icon_is_pos_sensitive: bool,
-
-    pub everstone_icon_active: bool,
-    pub everstone_icon_timer: i32,
-    pub everstone_x_icon_active: bool,
-    pub everstone_x_icon_timer: i32,
-    pub special_press_count_everstone: u8,
-    pub last_special_press_frame_everstone: i32,
+    pub icons: IconManager, // Overhead Linking Cord / Everstone / Gengarite / Dynamax icons
 
-    pub down_taunt_cancel_press_count: u8,
-    pub last_down_taunt_cancel_input_frame: i32,
+    // Button-sequence commands (Linking Cord, Everstone, cancel, Gengarite, Dynamax)
+    pub command_matcher: InputSequenceMatcher,
+    pub command_bindings_for: Option<(u32, u32)>, // (entry id, color) the matcher's bindings were resolved for
+    pub command_events: Vec<CommandEvent>, // Events from this frame's input
+    pub last_command_input: InputFrame,
+    pub input_history: InputHistory, // Buffered presses + recent guard/situation for input leniency
 
     pub mega_gengar_form_active: bool,
     pub giga_gengar_form_active: bool,
     pub is_in_final_smash_form: bool,
-    pub gengarite_icon_display_active: bool,
-    pub gengarite_icon_display_timer: i32,
-    pub dynamax_icon_display_active: bool,
-    pub dynamax_icon_display_timer: i32,
-
-    pub gengarite_input_sequence: Vec<i32>,
-    pub last_gengarite_input_frame: i32,
-
-    pub s_taunt_r_press_count_dynamax: u8,
-    pub last_s_taunt_r_input_frame_dynamax: i32,
-
-    pub icon_start_pos_x: f32,
-    pub icon_start_pos_y: f32,
-    pub is_any_pos_sensitive_icon_active: bool,
 
     // --- Evolution Readiness Icons State ---
     pub dmg_t_icon_display_timer: i32,
@@ -149,6 +130,7 @@ pub struct PlayerEvolutionState {
 
     // Shiny
     pub is_shiny: bool,
+    pub shiny_roll: crate::gastly::shiny_odds::ShinyRoll, // Shiny odds result this match (Won = regular slot turned shiny)
     pub shiny_effect_pending: bool,
     pub shiny_effect_delay_timer: i32,
     pub evolution_completion_frame: i32,
@@ -160,10 +142,10 @@ impl PlayerEvolutionState {
     pub fn new() -> Self {
         Self {
             stage: EvolutionStage::Gastly,
-            blink_timer: unsafe { random_module::rand_range_i32(120, 300) },
-            blink_phase: BlinkPhase::Open,
+            expressions: ExpressionEngine::new(),
             damage_received_this_stage: 0.0,
             hits_landed_this_stage: 0,
+            last_hit_record_sequence: 0,
             previous_total_damage: 0.0,
 
             shadowball_status_frames: 0,
@@ -181,6 +163,7 @@ impl PlayerEvolutionState {
 
             vanilla_expression_tracking: true,
             last_vanilla_expression: Hash40 { hash: 0 },
+            visible_vanilla_eye: None,
             vanilla_expression_changed: false,
 
             last_attackmodule_check_frame: -1,
@@ -200,36 +183,18 @@ impl PlayerEvolutionState {
             evo_attempt_delay_hits_penalty: 0,
             current_frame: 0,
             last_debug_taunt_frame: -DEBUG_TAUNT_COOLDOWN -1,
-            linking_cord_visual_icon_active: false,
-            linking_cord_visual_icon_timer: 0,
-            up_taunt_press_count_linking_cord: 0,
-            last_up_taunt_input_frame_linking_cord: 0,
             manual_linking_cord_evo_attempted_this_frame: false,
+            linking_cord_usage: LinkingCordUsage::default(),
             linking_cord_consumed_everstone_this_frame: false,
-            linking_cord_evo_attempt_icon_timer: 0,
-            linking_cord_evo_attempt_icon_is_pos_sensitive: false,
-            everstone_icon_active: false,
-            everstone_icon_timer: 0,
-            everstone_x_icon_active: false,
-            everstone_x_icon_timer: 0,
-            special_press_count_everstone: 0,
-            last_special_press_frame_everstone: 0,
-            down_taunt_cancel_press_count: 0,
-            last_down_taunt_cancel_input_frame: 0,
+            icons: IconManager::new(),
+            command_matcher: InputSequenceMatcher::default(),
+            command_bindings_for: None,
+            command_events: Vec::new(),
+            last_command_input: InputFrame::default(),
+            input_history: InputHistory::new(),
             mega_gengar_form_active: false,
             giga_gengar_form_active: false,
             is_in_final_smash_form: false,
-            gengarite_icon_display_active: false,
-            gengarite_icon_display_timer: 0,
-            dynamax_icon_display_active: false,
-            dynamax_icon_display_timer: 0,
-            gengarite_input_sequence: Vec::with_capacity(MAX_TAUNT_SEQUENCE_LEN),
-            last_gengarite_input_frame: 0,
-            s_taunt_r_press_count_dynamax: 0,
-            last_s_taunt_r_input_frame_dynamax: 0,
-            icon_start_pos_x: 0.0,
-            icon_start_pos_y: 0.0,
-            is_any_pos_sensitive_icon_active: false,
 
             // Evolution Readiness Icons State
             dmg_t_icon_display_timer: 0,
@@ -261,6 +226,7 @@ impl PlayerEvolutionState {
 
             // Shiny
             is_shiny: false,
+            shiny_roll: crate::gastly::shiny_odds::ShinyRoll::NotRolled,
             shiny_effect_pending: false,
             shiny_effect_delay_timer: -1,
             evolution_completion_frame: -1,
@@ -296,28 +262,16 @@ impl PlayerEvolutionState {
         self.is_evolving = false;
         self.evolution_timer = 0;
         self.linking_cord_active = false;
-        self.blink_phase = BlinkPhase::Open;
-        self.blink_timer = unsafe { random_module::rand_range_i32(120, 300) };
+        self.expressions.reset();
         self.evo_attempt_delay_damage_taken_penalty = 0.0;
         self.evo_attempt_delay_hits_penalty = 0;
 
-        self.linking_cord_visual_icon_active = false; self.linking_cord_visual_icon_timer = 0;
-        self.up_taunt_press_count_linking_cord = 0; self.last_up_taunt_input_frame_linking_cord = 0;
         self.manual_linking_cord_evo_attempted_this_frame = false;
         self.linking_cord_consumed_everstone_this_frame = false;
-        self.linking_cord_evo_attempt_icon_timer = 0;
-        self.linking_cord_evo_attempt_icon_is_pos_sensitive = false;
         self.everstone_effect_active = false;
-        self.everstone_icon_active = false; self.everstone_icon_timer = 0;
-        self.everstone_x_icon_active = false; self.everstone_x_icon_timer = 0;
-        self.special_press_count_everstone = 0; self.last_special_press_frame_everstone = 0;
-        self.down_taunt_cancel_press_count = 0; self.last_down_taunt_cancel_input_frame = 0;
-        self.gengarite_icon_display_active = false; self.gengarite_icon_display_timer = 0;
-        self.dynamax_icon_display_active = false; self.dynamax_icon_display_timer = 0;
-        self.gengarite_input_sequence.clear(); self.last_gengarite_input_frame = 0;
-        self.s_taunt_r_press_count_dynamax = 0; self.last_s_taunt_r_input_frame_dynamax = 0;
+        self.icons.clear();
+        self.command_matcher.reset(); self.command_events.clear(); self.input_history.clear();
         self.is_in_final_smash_form = false;
-        self.is_any_pos_sensitive_icon_active = false;
 
         // Reset completion flags
         self.evolution_just_completed_this_frame = false;
@@ -349,6 +303,7 @@ impl PlayerEvolutionState {
 
     pub unsafe fn full_reset_on_respawn(&mut self, boma: *mut BattleObjectModuleAccessor) {
         self.reset_for_new_stage(EvolutionStage::Gastly, boma);
+        self.linking_cord_usage = LinkingCordUsage::default();
         self.mega_gengar_form_active = false;
         self.giga_gengar_form_active = false;
         self.previous_total_damage = 0.0;
@@ -387,61 +342,6 @@ impl PlayerEvolutionState {
         self.dmg_se_icon_is_locked_out = false;
     }
 
-    pub fn advance_blink_phase(&mut self) {
-        match self.blink_phase {
-            BlinkPhase::Open => { self.blink_phase = BlinkPhase::HalfClose; self.blink_timer = 3; }
-            BlinkPhase::HalfClose => { self.blink_phase = BlinkPhase::FullClose; self.blink_timer = 5; }
-            BlinkPhase::FullClose => { self.blink_phase = BlinkPhase::HalfOpen; self.blink_timer = 3; }
-            BlinkPhase::HalfOpen => { self.blink_phase = BlinkPhase::Open; self.blink_timer = unsafe { random_module::rand_range_i32(120, 300) }; }
-        }
-    }
-
-    pub unsafe fn find_recently_hit_enemy(&self, my_boma: *mut BattleObjectModuleAccessor) -> Option<(u32, Vector3f)> {
-        let my_entry_id = WorkModule::get_int(my_boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as u32;
-        let my_pos = {
-            let mut pos = Vector3f { x: 0.0, y: 0.0, z: 0.0 };
-            ModelModule::joint_global_position(my_boma, Hash40::new("top"), &mut pos, true);
-            pos
-        };
-        
-        let mut closest_enemy: Option<(u32, Vector3f, f32)> = None; // (id, pos, distance)
-        
-        for enemy_id in 0..8u32 {
-            if enemy_id == my_entry_id { continue; }
-            
-            let enemy_boma = smash::app::sv_battle_object::module_accessor(enemy_id);
-            if enemy_boma.is_null() { continue; }
-            
-            // Check if enemy exists and has taken damage recently
-            let enemy_kind = utility::get_kind(&mut *enemy_boma);
-            if enemy_kind < *FIGHTER_KIND_MARIO || enemy_kind > *FIGHTER_KIND_PICKEL { continue; }
-            
-            // Get enemy hip position
-            let mut enemy_hip_pos = Vector3f { x: 0.0, y: 0.0, z: 0.0 };
-            ModelModule::joint_global_position(enemy_boma, Hash40::new("hip"), &mut enemy_hip_pos, true);
-            
-            // Calculate distance to find closest enemy
-            let dx = enemy_hip_pos.x - my_pos.x;
-            let dy = enemy_hip_pos.y - my_pos.y;
-            let dz = enemy_hip_pos.z - my_pos.z;
-            let distance = (dx*dx + dy*dy + dz*dz).sqrt();
-            
-            // Only consider enemies within reasonable range (10 units)
-            if distance > 10.0 { continue; }
-            
-            match closest_enemy {
-                None => closest_enemy = Some((enemy_id, enemy_hip_pos, distance)),
-                Some((_, _, closest_dist)) => {
-                    if distance < closest_dist {
-                        closest_enemy = Some((enemy_id, enemy_hip_pos, distance));
-                    }
-                }
-            }
-        }
-        
-        closest_enemy.map(|(id, pos, _)| (id, pos))
-    }
-    
     // Helper to check if we're actively hitting someone with shadowball
     pub unsafe fn is_shadowball_hitting_enemy(&self, boma: *mut BattleObjectModuleAccessor) -> bool {
         let current_status = StatusModule::status_kind(boma);
@@ -520,6 +420,14 @@ impl PlayerEvolutionState {
         None
     }
 
+    // Eye a motion rule dictates (expression_map.rs)
+    pub unsafe fn detect_scripted_expression(&mut self, boma: *mut BattleObjectModuleAccessor) -> Option<Hash40> {
+        if !self.vanilla_expression_tracking || boma.is_null() {
+            return None;
+        }
+        crate::gastly::animation_hooks::detect_motion_based_expression(boma, self)
+    }
+
     pub unsafe fn detect_vanilla_expression(&mut self, boma: *mut BattleObjectModuleAccessor) -> Option<Hash40> {
         if !self.vanilla_expression_tracking {
             return None;
@@ -532,104 +440,28 @@ impl PlayerEvolutionState {
             return Some(expression);
         }
 
-        if let Some(expression) = crate::gastly::animation_hooks::detect_expression_from_game_state(boma, self) {
+        if let Some(expression) = crate::gastly::animation_hooks::detect_expression_from_game_state(self) {
             return Some(expression);
         }
 
-        self.check_vanilla_mesh_visibility(boma)
+        self.check_vanilla_mesh_visibility()
     }
 
-    unsafe fn check_vanilla_mesh_visibility(&mut self, boma: *mut BattleObjectModuleAccessor) -> Option<Hash40> {
+    fn check_vanilla_mesh_visibility(&mut self) -> Option<Hash40> {
         if self.current_frame % 2 != 0 {
             return None;
         }
-        if boma.is_null() {
-            return None;
-        }
 
-        let vanilla_expressions = [
-            (*PURIN_VANILLA_EYE_N, "normal"),
-            (*PURIN_VANILLA_EYE_BLINK, "blink"),
-            (*PURIN_VANILLA_EYE_HALFBLINK1, "halfblink"),
-            (*PURIN_VANILLA_EYE_ATTACK, "attack"),
-            (*PURIN_VANILLA_EYE_CAPTURE, "capture"),
-            (*PURIN_VANILLA_EYE_OUCH, "ouch"),
-            (*PURIN_VANILLA_EYE_DOWN, "down"),
-            (*PURIN_VANILLA_EYE_HEAVYATTACK, "heavyattack"),
-        ];
-
-        for (vanilla_mesh, expression_type) in vanilla_expressions.iter() {
-            if vanilla_mesh.hash != 0 {
-                let is_visible = VisibilityModule::is_visible_mesh(boma, vanilla_mesh.hash as c_uint);
-
-                if is_visible {
-                    let custom_expression = self.map_expression_type_to_custom(*expression_type);
-                    if let Some(custom_expr) = custom_expression {
-                        if self.last_vanilla_expression.hash != custom_expr.hash {
-                            self.vanilla_expression_changed = true;
-                            self.last_vanilla_expression = custom_expr;
-                        }
-                        return Some(custom_expr);
-                    }
-                }
-            }
+        let custom_expr = self.visible_vanilla_eye.and_then(|vanilla_eye| self.map_vanilla_to_custom_expression(vanilla_eye))?;
+        if self.last_vanilla_expression.hash != custom_expr.hash {
+            self.vanilla_expression_changed = true;
+            self.last_vanilla_expression = custom_expr;
         }
-        None
+        Some(custom_expr)
     }
 
     pub fn map_vanilla_to_custom_expression(&self, vanilla_eye: Hash40) -> Option<Hash40> {
-        let vanilla_expression_type = if vanilla_eye.hash == PURIN_VANILLA_EYE_N.hash {
-            "normal"
-        } else if vanilla_eye.hash == PURIN_VANILLA_EYE_BLINK.hash {
-            "blink"
-        } else if vanilla_eye.hash == PURIN_VANILLA_EYE_HALFBLINK1.hash {
-            "halfblink"
-        } else if vanilla_eye.hash == PURIN_VANILLA_EYE_ATTACK.hash {
-            "attack"
-        } else if vanilla_eye.hash == PURIN_VANILLA_EYE_CAPTURE.hash {
-            "capture"
-        } else if vanilla_eye.hash == PURIN_VANILLA_EYE_OUCH.hash {
-            "ouch"
-        } else if vanilla_eye.hash == PURIN_VANILLA_EYE_DOWN.hash {
-            "down"
-        } else if vanilla_eye.hash == PURIN_VANILLA_EYE_HEAVYATTACK.hash {
-            "heavyattack"
-        } else {
-            return None;
-        };
-        self.map_expression_type_to_custom(vanilla_expression_type)
-    }
-
-    fn map_expression_type_to_custom(&self, expression_type: &str) -> Option<Hash40> {
-        match (expression_type, self.stage) {
-            ("normal", EvolutionStage::Gastly) => Some(*GASTLY_EYE_N),
-            ("blink", EvolutionStage::Gastly) => Some(*GASTLY_EYE_BLINK),
-            ("halfblink", EvolutionStage::Gastly) => Some(*GASTLY_EYE_HALFBLINK1),
-            ("attack", EvolutionStage::Gastly) => Some(*GASTLY_EYE_ATTACK),
-            ("capture", EvolutionStage::Gastly) => Some(*GASTLY_EYE_CAPTURE),
-            ("ouch", EvolutionStage::Gastly) => Some(*GASTLY_EYE_OUCH),
-            ("down", EvolutionStage::Gastly) => Some(*GASTLY_EYE_DOWN),
-            ("heavyattack", EvolutionStage::Gastly) => Some(*GASTLY_EYE_HEAVYATTACK),
-
-            ("normal", EvolutionStage::Haunter) => Some(*HAUNTER_EYE_N),
-            ("blink", EvolutionStage::Haunter) => Some(*HAUNTER_EYE_BLINK),
-            ("halfblink", EvolutionStage::Haunter) => Some(*HAUNTER_EYE_HALFBLINK1),
-            ("attack", EvolutionStage::Haunter) => Some(*HAUNTER_EYE_ATTACK),
-            ("capture", EvolutionStage::Haunter) => Some(*HAUNTER_EYE_CAPTURE),
-            ("ouch", EvolutionStage::Haunter) => Some(*HAUNTER_EYE_OUCH),
-            ("down", EvolutionStage::Haunter) => Some(*HAUNTER_EYE_DOWN),
-            ("heavyattack", EvolutionStage::Haunter) => Some(*HAUNTER_EYE_HEAVYATTACK),
-
-            ("normal", EvolutionStage::Gengar) => Some(*GENGAR_EYE_N),
-            ("blink", EvolutionStage::Gengar) => Some(*GENGAR_EYE_BLINK),
-            ("halfblink", EvolutionStage::Gengar) => Some(*GENGAR_EYE_HALFBLINK1),
-            ("attack", EvolutionStage::Gengar) => Some(*GENGAR_EYE_ATTACK),
-            ("capture", EvolutionStage::Gengar) => Some(*GENGAR_EYE_CAPTURE),
-            ("ouch", EvolutionStage::Gengar) => Some(*GENGAR_EYE_OUCH),
-            ("down", EvolutionStage::Gengar) => Some(*GENGAR_EYE_DOWN),
-            ("heavyattack", EvolutionStage::Gengar) => Some(*GENGAR_EYE_HEAVYATTACK),
-            _ => None,
-        }
+        expression_for_vanilla_eye(vanilla_eye).map(|expression| expression_mesh(self.stage, expression))
     }
 
     pub fn set_vanilla_expression_tracking(&mut self, enabled: bool) {
@@ -645,6 +477,19 @@ impl PlayerEvolutionState {
         self.vanilla_expression_changed = false;
     }
 
+    // Progress towards the next stage (0-1): the lower of the damage taken and hits landed ratios
+    pub fn evolution_progress(&self) -> f32 {
+        let (required_dmg_received, required_hits) = match self.stage {
+            EvolutionStage::Gastly => (GASTLY_EVO_DMG_RECEIVED_THRESHOLD, GASTLY_EVO_HITS_THRESHOLD),
+            EvolutionStage::Haunter => (HAUNTER_EVO_DMG_RECEIVED_THRESHOLD, HAUNTER_EVO_HITS_THRESHOLD),
+            EvolutionStage::Gengar => return 1.0,
+        };
+        // Divisors clamped to 1 so a zero threshold can't produce NaN/inf
+        let dmg_ratio = self.damage_received_this_stage / (required_dmg_received + self.evo_attempt_delay_damage_taken_penalty).max(1.0);
+        let hits_ratio = self.hits_landed_this_stage as f32 / (required_hits + self.evo_attempt_delay_hits_penalty).max(1) as f32;
+        dmg_ratio.min(hits_ratio).clamp(0.0, 1.0)
+    }
+
     pub fn start_evolution_process(&mut self, target_stage: EvolutionStage, fighter: &mut L2CFighterCommon, is_manual_trigger: bool) {
         let boma = fighter.module_accessor;
         if self.is_evolving {
@@ -654,7 +499,8 @@ impl PlayerEvolutionState {
             return;
         }
 
-        if !is_manual_trigger && self.everstone_effect_active {
+        let everstone_holds = crate::gastly::item_rules::item_rule_for_stage(self.stage).map_or(false, |rule| rule.everstone);
+        if !is_manual_trigger && self.everstone_effect_active && everstone_holds {
             return;
         }
 
@@ -687,12 +533,8 @@ impl PlayerEvolutionState {
         self.evolution_timer = 0;
 
         // Handle manual evolution icon
-        if is_manual_trigger && self.stage == EvolutionStage::Haunter && target_stage == EvolutionStage::Gengar {
-            unsafe { 
-                crate::gastly::icon_management::enforce_icon_exclusivity(self, Some(*LINKING_CORD_ICON)); 
-            }
-            self.linking_cord_evo_attempt_icon_timer = MANUAL_EVO_HAUNTER_ICON_DURATION;
-            self.linking_cord_evo_attempt_icon_is_pos_sensitive = true;
+        if is_manual_trigger {
+            self.icons.request(OverheadIcon::LinkingCordEvolving);
         }
         
     }
@@ -711,8 +553,7 @@ impl PlayerEvolutionState {
         let target_stage = self.evolution_target_stage;
         self.is_evolving = false;
         self.linking_cord_active = false;
-        self.linking_cord_evo_attempt_icon_timer = 0;
-        self.linking_cord_evo_attempt_icon_is_pos_sensitive = false;
+        self.icons.cancel(OverheadIcon::LinkingCordEvolving);
 
         unsafe { 
             self.reset_for_new_stage(target_stage, boma); 
@@ -724,7 +565,6 @@ impl PlayerEvolutionState {
             macros::COL_NORMAL(fighter);
             crate::gastly::visuals::update_body_and_unique_parts_visibility(boma, self.stage);
             crate::gastly::visuals::set_active_eye_mesh(boma, self, None);
-            crate::gastly::icon_management::update_is_any_pos_sensitive_icon_active_flag(self);
         }
 
         // Set the flag AFTER reset_for_new_stage so it doesn't get cleared
@@ -736,14 +576,13 @@ impl PlayerEvolutionState {
         
         self.is_evolving = false;
         self.linking_cord_active = false;
-        self.linking_cord_evo_attempt_icon_timer = 0;
-        self.linking_cord_evo_attempt_icon_is_pos_sensitive = false;
+        self.icons.cancel(OverheadIcon::LinkingCordEvolving);
         self.evolution_timer = 0;
 
         // Simple 15% damage penalty for cancellation
         self.evo_attempt_delay_damage_taken_penalty += 15.0;
         
-        self.down_taunt_cancel_press_count = 0;
+        self.command_matcher.reset_command(EvolutionCommand::CancelEvolution);
         self.last_evolution_confirmation_frame = -1;
         
         // Reset evolution progress for the current stage
@@ -757,6 +596,8 @@ impl PlayerEvolutionState {
         self.evolution_just_cancelled_this_frame = true;
 
         unsafe {
+            crate::gastly::match_stats::record_evolution_cancel(crate::gastly::get_instance_key(boma));
+
             // Play cancel evolution sound
             use smash::app::lua_bind::SoundModule;
             use smash::phx::Hash40;
@@ -765,13 +606,12 @@ impl PlayerEvolutionState {
             macros::COL_NORMAL(fighter);
             crate::gastly::visuals::update_body_and_unique_parts_visibility(boma, self.stage);
             crate::gastly::visuals::set_active_eye_mesh(boma, self, None);
-            crate::gastly::icon_management::update_is_any_pos_sensitive_icon_active_flag(self);
             
             // Explicitly hide all readiness icon meshes
-            ModelModule::set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
-            ModelModule::set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
-            ModelModule::set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
-            ModelModule::set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
+            set_mesh_visibility(boma, *STG1_DMG_T_ICON, false);
+            set_mesh_visibility(boma, *STG1_DMG_D_ICON, false);
+            set_mesh_visibility(boma, *STG2_DMG_SS_ICON, false);
+            set_mesh_visibility(boma, *STG2_DMG_SE_ICON, false);
         }
     }
 
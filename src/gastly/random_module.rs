@@ -22,4 +22,11 @@ pub unsafe fn rand_range_i32(min: i32, max: i32) -> i32 {
     let type_hash_u64 = hash40("fighter_gastly_blink_random_seed_v45"); // Keep your unique seed
     
     min + sv_math::rand(type_hash_u64, range_size) as i32
-}
\ No newline at end of file
+}
+
+// Uniform value in [0, n) from a named sv_math::rand stream (synced between clients).
+pub unsafe fn rand_below_u32(stream: &str, n: u32) -> u32 {
+    if n <= 1 { return 0; }
+    let range_size = n.min(i32::MAX as u32) as c_int;
+    sv_math::rand(hash40(stream), range_size) as u32
+}
@@ -0,0 +1,128 @@
+// src/gastly/shiny_odds.rs
+
+// "Wild encounter" shiny odds. When enabled, every regular marked slot rolls once at match start
+// (first ENTRY frame) and becomes shiny for that match on a win:
+//
+//   [shiny_odds]
+//   enabled = true
+//   one_in = 4096      # 1 in N chance (lower = boosted)
+//
+// on_start isn't used: it also runs for the results-screen fighter, which would re-roll and replace
+// the match's result. The results fighter never enters ENTRY, so it keeps the roll.
+// The roll uses the game's synced RNG (sv_math::rand) so every client agrees online. The result is
+// kept per player here (read by the aura/palette code without touching FIGHTER_STATES) and mirrored
+// to PlayerEvolutionState::shiny_roll for overlays.
+
+use smash::app::BattleObjectModuleAccessor;
+use smash::app::lua_bind::WorkModule;
+use smash::lib::lua_const::*;
+
+use crate::gastly::config::IniDocument;
+use crate::gastly::markers::MarkerVariant;
+use parking_lot::RwLock;
+
+pub const DEFAULT_SHINY_ONE_IN: u32 = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShinyOddsConfig {
+    pub enabled: bool,
+    pub one_in: u32,
+}
+
+impl ShinyOddsConfig {
+    pub const DISABLED: Self = Self { enabled: false, one_in: DEFAULT_SHINY_ONE_IN };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShinyRoll {
+    NotRolled, // Odds mode off, shiny slot, or non-regular slot
+    Missed,
+    Won,       // Regular slot turned shiny for this match
+}
+
+impl ShinyRoll {
+    pub fn is_won(self) -> bool {
+        self == ShinyRoll::Won
+    }
+}
+
+pub fn shiny_odds_from_config(config: &IniDocument) -> ShinyOddsConfig {
+    let section = match config.section("shiny_odds") {
+        Some(section) => section,
+        None => return ShinyOddsConfig::DISABLED,
+    };
+    ShinyOddsConfig {
+        enabled: section.get_bool("enabled").unwrap_or(false),
+        one_in: section.get_u32("one_in").filter(|n| *n > 0).unwrap_or(DEFAULT_SHINY_ONE_IN),
+    }
+}
+
+// `roll` is a uniform draw from 0..one_in
+pub fn roll_outcome(roll: u32, one_in: u32) -> ShinyRoll {
+    if one_in <= 1 || roll == 0 { ShinyRoll::Won } else { ShinyRoll::Missed }
+}
+
+pub static SHINY_ODDS: RwLock<ShinyOddsConfig> = parking_lot::const_rwlock(ShinyOddsConfig::DISABLED);
+
+pub fn load_shiny_odds() {
+    let config = crate::gastly::config::GASTLY_CONFIG.read();
+    *SHINY_ODDS.write() = shiny_odds_from_config(&config);
+}
+
+// Roll result per player (instance key)
+static mut SHINY_ROLLS: [ShinyRoll; 256] = [ShinyRoll::NotRolled; 256];
+
+// Roll for this player's match. Only regular marked slots are eligible.
+pub unsafe fn roll_for_match(boma: *mut BattleObjectModuleAccessor) -> ShinyRoll {
+    let instance_key = crate::gastly::get_instance_key(boma) as usize;
+    let odds = *SHINY_ODDS.read();
+    let eligible = odds.enabled
+        && crate::is_marked_gastly_costume(boma)
+        && !crate::is_shiny_gastly_costume(boma)
+        && crate::gastly::markers::marker_for(boma).variant == MarkerVariant::Regular;
+
+    let outcome = if eligible {
+        roll_outcome(crate::gastly::random_module::rand_below_u32("fighter_gastly_shiny_odds", odds.one_in), odds.one_in)
+    } else {
+        ShinyRoll::NotRolled
+    };
+
+    if instance_key < 256 {
+        SHINY_ROLLS[instance_key] = outcome;
+    }
+    outcome
+}
+
+// Set on the first ENTRY frame, cleared once the fighter leaves ENTRY (instance key)
+static mut ENTRY_ROLLED: [bool; 256] = [false; 256];
+
+// Called every frame; rolls once per match, on its first ENTRY frame
+pub unsafe fn roll_on_match_entry(boma: *mut BattleObjectModuleAccessor, status: i32) -> Option<ShinyRoll> {
+    let instance_key = crate::gastly::get_instance_key(boma) as usize;
+    if instance_key >= 256 {
+        return None;
+    }
+    if status != *FIGHTER_STATUS_KIND_ENTRY {
+        ENTRY_ROLLED[instance_key] = false;
+        return None;
+    }
+    if ENTRY_ROLLED[instance_key] {
+        return None;
+    }
+    ENTRY_ROLLED[instance_key] = true;
+    Some(roll_for_match(boma))
+}
+
+pub unsafe fn shiny_roll_for(boma: *mut BattleObjectModuleAccessor) -> ShinyRoll {
+    shiny_roll_for_instance(crate::gastly::get_instance_key(boma))
+}
+
+pub fn shiny_roll_for_instance(instance_key: u32) -> ShinyRoll {
+    let index = instance_key as usize;
+    if index < 256 { unsafe { SHINY_ROLLS[index] } } else { ShinyRoll::NotRolled }
+}
+
+// Slot shiny or rolled shiny this match
+pub unsafe fn is_shiny_this_match(boma: *mut BattleObjectModuleAccessor) -> bool {
+    crate::is_shiny_gastly_costume(boma) || shiny_roll_for(boma).is_won()
+}
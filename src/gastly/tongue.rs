@@ -0,0 +1,143 @@
+// src/gastly/tongue.rs
+
+// Which tongue mesh an animation shows, and which copy of it to use for the fighter's facing.
+//
+// The tongue meshes are modelled facing right. Facing left, flip.prc mirrors the fighter, which
+// leaves the long tongue (grab/pummel) and the down air tongue pointing the wrong way. Slots can
+// ship mirrored copies (`gastly_tongue_l`, `gengar_tongue_long_l`, `gengar_tongue_normal_l`) that
+// are swapped in while PostureModule::lr is negative; slots without them keep the regular mesh.
+
+use smash::hash40;
+use smash::phx::Hash40;
+
+use crate::gastly::constants::*;
+use crate::gastly::player_state::EvolutionStage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TongueMesh {
+    Gastly,
+    GengarLong,
+    GengarNormal,
+}
+
+impl TongueMesh {
+    pub fn mesh(self) -> Hash40 {
+        match self {
+            TongueMesh::Gastly => *GASTLY_TONGUE,
+            TongueMesh::GengarLong => *GENGAR_TONGUE_LONG,
+            TongueMesh::GengarNormal => *GENGAR_TONGUE_NORMAL,
+        }
+    }
+
+    pub fn mirrored_mesh(self) -> Hash40 {
+        match self {
+            TongueMesh::Gastly => *GASTLY_TONGUE_L,
+            TongueMesh::GengarLong => *GENGAR_TONGUE_LONG_L,
+            TongueMesh::GengarNormal => *GENGAR_TONGUE_NORMAL_L,
+        }
+    }
+}
+
+// Gengar (without a Gengarite/Dynamax form selected) shows gengar_tongue_normal during these
+const GENGAR_TONGUE_NORMAL_MOTIONS: &[&str] = &[
+    "final", "final_air",
+    "special_hi", "special_air_hi", "special_air_s", "special_s",
+    "attack_dash", "attack_s3_hi", "attack_s3_lw", "attack_s3_s", "attack_s4_s",
+    "attack_hi4", "attack_lw4", "attack_lw4_hold", "attack_hi3", "attack_lw3",
+    "attack_air_hi", "attack_air_f", "attack_air_n",
+    "dash", "turn_dash",
+    "damage_hi_1", "damage_hi_2", "damage_hi_3",
+    "damage_lw_1", "damage_lw_2", "damage_lw_3",
+    "damage_n_1", "damage_n_2", "damage_n_3",
+    "damage_fly_hi", "damage_fly_lw", "damage_fly_n",
+];
+
+// Down air shows the tongue on these frames
+const ATTACK_AIR_LW_TONGUE_FRAMES: (f32, f32) = (7.0, 49.0);
+
+pub fn select_tongue(stage: EvolutionStage, final_smash_form_selected: bool, motion: u64, frame: f32) -> Option<TongueMesh> {
+    if stage == EvolutionStage::Gengar && !final_smash_form_selected &&
+       GENGAR_TONGUE_NORMAL_MOTIONS.iter().any(|name| hash40(name) == motion) {
+        return Some(TongueMesh::GengarNormal);
+    }
+
+    if motion == hash40("attack_air_lw") {
+        let (first, last) = ATTACK_AIR_LW_TONGUE_FRAMES;
+        if frame >= first && frame <= last {
+            return match stage {
+                EvolutionStage::Gastly => Some(TongueMesh::Gastly),
+                EvolutionStage::Gengar => Some(TongueMesh::GengarNormal),
+                EvolutionStage::Haunter => None, // Haunter doesn't have a tongue for this animation
+            };
+        }
+        return None;
+    }
+
+    if motion == hash40("catch_attack") || motion == hash40("catch_wait") {
+        return match stage {
+            EvolutionStage::Gastly => Some(TongueMesh::Gastly),
+            EvolutionStage::Gengar => Some(TongueMesh::GengarLong),
+            EvolutionStage::Haunter => None, // Haunter doesn't have a tongue for catch animations
+        };
+    }
+
+    None
+}
+
+// The mesh to show for `tongue`: the mirrored copy when facing left and the slot has it
+pub fn tongue_mesh_for_facing(tongue: TongueMesh, lr: f32, has_mirrored_copy: bool) -> Hash40 {
+    if lr < 0.0 && has_mirrored_copy {
+        tongue.mirrored_mesh()
+    } else {
+        tongue.mesh()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tongue(stage: EvolutionStage, motion: &str, frame: f32) -> Option<TongueMesh> {
+        select_tongue(stage, false, hash40(motion), frame)
+    }
+
+    #[test]
+    fn grab_and_pummel_show_the_long_tongue() {
+        for motion in ["catch_wait", "catch_attack"] {
+            assert_eq!(tongue(EvolutionStage::Gastly, motion, 1.0), Some(TongueMesh::Gastly));
+            assert_eq!(tongue(EvolutionStage::Haunter, motion, 1.0), None);
+            assert_eq!(tongue(EvolutionStage::Gengar, motion, 1.0), Some(TongueMesh::GengarLong));
+        }
+    }
+
+    #[test]
+    fn down_air_shows_the_tongue_from_frame_7_to_49() {
+        for stage in [EvolutionStage::Gastly, EvolutionStage::Haunter, EvolutionStage::Gengar] {
+            assert_eq!(tongue(stage, "attack_air_lw", 6.0), None);
+            assert_eq!(tongue(stage, "attack_air_lw", 50.0), None);
+        }
+        for frame in [7.0, 30.0, 49.0] {
+            assert_eq!(tongue(EvolutionStage::Gastly, "attack_air_lw", frame), Some(TongueMesh::Gastly));
+            assert_eq!(tongue(EvolutionStage::Haunter, "attack_air_lw", frame), None);
+            assert_eq!(tongue(EvolutionStage::Gengar, "attack_air_lw", frame), Some(TongueMesh::GengarNormal));
+        }
+    }
+
+    #[test]
+    fn gengar_normal_tongue_motions_yield_to_a_final_smash_form() {
+        assert_eq!(tongue(EvolutionStage::Gengar, "attack_air_n", 1.0), Some(TongueMesh::GengarNormal));
+        assert_eq!(select_tongue(EvolutionStage::Gengar, true, hash40("attack_air_n"), 1.0), None);
+        assert_eq!(tongue(EvolutionStage::Gastly, "attack_air_n", 1.0), None);
+        assert_eq!(tongue(EvolutionStage::Gengar, "wait_1", 1.0), None);
+    }
+
+    #[test]
+    fn facing_left_uses_the_mirrored_copy_when_the_slot_has_it() {
+        for tongue in [TongueMesh::Gastly, TongueMesh::GengarLong, TongueMesh::GengarNormal] {
+            assert_eq!(tongue_mesh_for_facing(tongue, 1.0, true).hash, tongue.mesh().hash);
+            assert_eq!(tongue_mesh_for_facing(tongue, -1.0, true).hash, tongue.mirrored_mesh().hash);
+            assert_eq!(tongue_mesh_for_facing(tongue, -1.0, false).hash, tongue.mesh().hash);
+            assert_eq!(tongue_mesh_for_facing(tongue, 1.0, false).hash, tongue.mesh().hash);
+        }
+    }
+}
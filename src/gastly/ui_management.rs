@@ -1,4 +1,4 @@
-// src/gastly/ui_management.rs - New module for UI swapping and cutin effects
+// src/gastly/ui_management.rs - New module for UI swapping and cutin triggers
 
 use smash::app::lua_bind::{WorkModule, StatusModule, SoundModule};
 use smash::app::BattleObjectModuleAccessor;
@@ -13,13 +13,14 @@ use smash::hash40;
 
 use crate::gastly::player_state::{PlayerEvolutionState, EvolutionStage};
 use crate::gastly::FIGHTER_STATES;
+use crate::gastly::cutins::{CutinKind, queue_cutin, clear_cutins_for_entry, update_cutins};
 use crate::gastly::constants::{FIGHTER_PURIN_INSTANCE_WORK_ID_FLAG_HAUNTER_CUTIN_READY, FIGHTER_PURIN_INSTANCE_WORK_ID_FLAG_GENGAR_CUTIN_READY, ENABLE_EVOLUTION_CUTINS};
 
 // Track original UI for restoration if needed
 static mut ORIGINAL_UI_CHARA_HASH: [u64; 256] = [0x0; 256];
 
 // Track last known evolution stage to detect changes
-static mut LAST_EVOLUTION_STAGE: [u8; 256] = [0; 256]; // 0=Gastly, 1=Haunter, 2=Gengar
+static mut LAST_EVOLUTION_STAGE: [u8; 256] = [0; 256]; // StageUiForm as u8 (0=Gastly, 1=Haunter, 2=Gengar, 3=Mega, 4=Giga)
 
 // Track cutin state to prevent spam
 static mut CUTIN_PLAYED_THIS_EVOLUTION: [bool; 256] = [false; 256];
@@ -36,7 +37,117 @@ static mut UI_DEATH_RESET_COOLDOWN: [i32; 256] = [0; 256];
 // Track death frame to enforce stage reset for longer period
 static mut DEATH_RESET_FRAME: [i32; 256] = [-1; 256];
 
-// Changes the battle portrait (chara_4) UI based on current evolution stage
+// ui_chara each entry had when its fighter started, captured for every fighter kind
+// (echoes, DLC and CSK-added characters included) so it can be restored exactly
+// (fighter kind it was captured for, ui_chara hash)
+static mut MATCH_START_UI_CHARA: [(i32, u64); 8] = [(-1, 0x0); 8];
+
+// Portraits this mod swaps in - never a valid "original"
+fn is_gastly_override_ui(ui_chara: u64) -> bool {
+    [
+        "ui_chara_evolving", "ui_chara_haunter", "ui_chara_gengar", "ui_chara_mega_gengar", "ui_chara_giga_gengar",
+        "ui_chara_gastly_shiny", "ui_chara_haunter_shiny", "ui_chara_gengar_shiny", "ui_chara_mega_gengar_shiny", "ui_chara_giga_gengar_shiny",
+    ].iter().any(|name| hash40(name) == ui_chara)
+}
+
+/// Store the entry's ui_chara at fighter start (called from the all-fighters on_start agent).
+/// A training-mode character switch can start a fighter while our portrait is still applied;
+/// that value is ignored and the previous capture / kind fallback is used instead.
+pub unsafe fn capture_original_ui_chara(boma: *mut BattleObjectModuleAccessor) {
+    let entry_id = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as usize;
+    if entry_id >= MATCH_START_UI_CHARA.len() { return; }
+
+    let fighter_kind = utility::get_kind(&mut *boma);
+    let ui_chara = the_csk_collection_api::get_ui_chara_from_entry_id(entry_id as u32);
+    if ui_chara != 0x0 && !is_gastly_override_ui(ui_chara) {
+        MATCH_START_UI_CHARA[entry_id] = (fighter_kind, ui_chara);
+    } else if MATCH_START_UI_CHARA[entry_id].0 != fighter_kind {
+        // A stale capture from a previous character must not be restored onto a different one
+        MATCH_START_UI_CHARA[entry_id] = (-1, 0x0);
+    }
+}
+
+// Last-resort table when nothing valid was captured
+fn ui_chara_for_kind(fighter_kind: i32) -> Option<u64> {
+    let name = match fighter_kind {
+        k if k == *FIGHTER_KIND_PURIN => "ui_chara_purin",
+        k if k == *FIGHTER_KIND_FOX => "ui_chara_fox",
+        k if k == *FIGHTER_KIND_FALCO => "ui_chara_falco",
+        k if k == *FIGHTER_KIND_MARIO => "ui_chara_mario",
+        k if k == *FIGHTER_KIND_DONKEY => "ui_chara_donkey",
+        k if k == *FIGHTER_KIND_LINK => "ui_chara_link",
+        k if k == *FIGHTER_KIND_SAMUS => "ui_chara_samus",
+        k if k == *FIGHTER_KIND_YOSHI => "ui_chara_yoshi",
+        k if k == *FIGHTER_KIND_KIRBY => "ui_chara_kirby",
+        k if k == *FIGHTER_KIND_PIKACHU => "ui_chara_pikachu",
+        k if k == *FIGHTER_KIND_LUIGI => "ui_chara_luigi",
+        k if k == *FIGHTER_KIND_NESS => "ui_chara_ness",
+        k if k == *FIGHTER_KIND_CAPTAIN => "ui_chara_captain",
+        k if k == *FIGHTER_KIND_KOOPA => "ui_chara_koopa",
+        k if k == *FIGHTER_KIND_PEACH => "ui_chara_peach",
+        k if k == *FIGHTER_KIND_ZELDA => "ui_chara_zelda",
+        k if k == *FIGHTER_KIND_SHEIK => "ui_chara_sheik",
+        k if k == *FIGHTER_KIND_MARTH => "ui_chara_marth",
+        k if k == *FIGHTER_KIND_GAMEWATCH => "ui_chara_gamewatch",
+        _ => return None,
+    };
+    Some(hash40(name))
+}
+
+/// Original ui_chara for an entry: match-start capture, else the kind table, else the current UI
+pub unsafe fn original_ui_chara_for_entry(entry_id: u32, fighter_kind: i32) -> u64 {
+    let (captured_kind, captured) = MATCH_START_UI_CHARA.get(entry_id as usize).copied().unwrap_or((-1, 0x0));
+    if captured != 0x0 && captured_kind == fighter_kind {
+        return captured;
+    }
+    ui_chara_for_kind(fighter_kind)
+        .unwrap_or_else(|| the_csk_collection_api::get_ui_chara_from_entry_id(entry_id))
+}
+
+// What the battle UI (chara_4 portrait and chara_2 stock icons) shows for a player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageUiForm {
+    Gastly = 0,
+    Haunter = 1,
+    Gengar = 2,
+    MegaGengar = 3,
+    GigaGengar = 4,
+}
+
+pub fn stage_ui_form(player_state: &PlayerEvolutionState) -> StageUiForm {
+    if player_state.is_in_final_smash_form && player_state.giga_gengar_form_active {
+        return StageUiForm::GigaGengar;
+    }
+    if player_state.is_in_final_smash_form && player_state.mega_gengar_form_active {
+        return StageUiForm::MegaGengar;
+    }
+    match player_state.stage {
+        EvolutionStage::Gastly => StageUiForm::Gastly,
+        EvolutionStage::Haunter => StageUiForm::Haunter,
+        EvolutionStage::Gengar => StageUiForm::Gengar,
+    }
+}
+
+/// ui_chara for a form (its chara_2 files are the stock icons). None = the player's original UI
+/// (Gastly on its own slot, whose chara_2 is already the slot's regular/shiny icon).
+/// `rolled_shiny` is a regular slot that won the shiny odds roll and needs the shiny Gastly entry.
+pub fn stage_ui_chara(form: StageUiForm, is_shiny: bool, rolled_shiny: bool) -> Option<u64> {
+    let name = match (form, is_shiny) {
+        (StageUiForm::Gastly, _) if rolled_shiny => "ui_chara_gastly_shiny",
+        (StageUiForm::Gastly, _) => return None,
+        (StageUiForm::Haunter, false) => "ui_chara_haunter",
+        (StageUiForm::Haunter, true) => "ui_chara_haunter_shiny",
+        (StageUiForm::Gengar, false) => "ui_chara_gengar",
+        (StageUiForm::Gengar, true) => "ui_chara_gengar_shiny",
+        (StageUiForm::MegaGengar, false) => "ui_chara_mega_gengar",
+        (StageUiForm::MegaGengar, true) => "ui_chara_mega_gengar_shiny",
+        (StageUiForm::GigaGengar, false) => "ui_chara_giga_gengar",
+        (StageUiForm::GigaGengar, true) => "ui_chara_giga_gengar_shiny",
+    };
+    Some(hash40(name))
+}
+
+// Changes the battle portrait (chara_4) and stock icon (chara_2) UI based on current evolution stage / form
 pub unsafe fn update_battle_portrait_ui(
     boma: *mut BattleObjectModuleAccessor,
     player_state: &PlayerEvolutionState
@@ -64,30 +175,7 @@ pub unsafe fn update_battle_portrait_ui(
     if !is_purin {
         if ORIGINAL_UI_CHARA_HASH[instance_key] != 0x0 {
             // Character switched away from Purin - restore the proper UI for current character
-            let proper_ui_hash = match current_fighter_kind {
-                k if k == *FIGHTER_KIND_FOX => hash40("ui_chara_fox"),
-                k if k == *FIGHTER_KIND_FALCO => hash40("ui_chara_falco"),
-                k if k == *FIGHTER_KIND_MARIO => hash40("ui_chara_mario"),
-                k if k == *FIGHTER_KIND_DONKEY => hash40("ui_chara_donkey"),
-                k if k == *FIGHTER_KIND_LINK => hash40("ui_chara_link"),
-                k if k == *FIGHTER_KIND_SAMUS => hash40("ui_chara_samus"),
-                k if k == *FIGHTER_KIND_YOSHI => hash40("ui_chara_yoshi"),
-                k if k == *FIGHTER_KIND_KIRBY => hash40("ui_chara_kirby"),
-                k if k == *FIGHTER_KIND_PIKACHU => hash40("ui_chara_pikachu"),
-                k if k == *FIGHTER_KIND_LUIGI => hash40("ui_chara_luigi"),
-                k if k == *FIGHTER_KIND_NESS => hash40("ui_chara_ness"),
-                k if k == *FIGHTER_KIND_CAPTAIN => hash40("ui_chara_captain"),
-                k if k == *FIGHTER_KIND_KOOPA => hash40("ui_chara_koopa"),
-                k if k == *FIGHTER_KIND_PEACH => hash40("ui_chara_peach"),
-                k if k == *FIGHTER_KIND_ZELDA => hash40("ui_chara_zelda"),
-                k if k == *FIGHTER_KIND_SHEIK => hash40("ui_chara_sheik"),
-                k if k == *FIGHTER_KIND_MARTH => hash40("ui_chara_marth"),
-                k if k == *FIGHTER_KIND_GAMEWATCH => hash40("ui_chara_gamewatch"),
-                _ => {
-                    // For unknown characters, try to get their current UI hash
-                    the_csk_collection_api::get_ui_chara_from_entry_id(entry_id)
-                }
-            };
+            let proper_ui_hash = original_ui_chara_for_entry(entry_id, current_fighter_kind);
             
             // Force restore proper UI for current character
             let owner_color = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR);
@@ -103,44 +191,41 @@ pub unsafe fn update_battle_portrait_ui(
         return; // Exit early since we're not Purin anymore
     }
     
-    // ROBUST ORIGINAL UI STORAGE - Only store once and protect from corruption
+    // ROBUST ORIGINAL UI STORAGE - the ui_chara captured at match start (ui_chara_purin if unknown)
+    let match_start_ui = original_ui_chara_for_entry(entry_id, current_fighter_kind);
     if is_purin && ORIGINAL_UI_CHARA_HASH[instance_key] == 0x0 {
-        // Always force ui_chara_purin as the original UI for all Purin instances
-        // This ensures consistent behavior and prevents name text loss
-        ORIGINAL_UI_CHARA_HASH[instance_key] = hash40("ui_chara_purin");
+        ORIGINAL_UI_CHARA_HASH[instance_key] = match_start_ui;
     }
     
     // PROTECTION: Prevent original UI from being overwritten during evolution
     // This is the key fix for name text disappearing - the original UI reference must never change
-    if is_purin && ORIGINAL_UI_CHARA_HASH[instance_key] != 0x0 && ORIGINAL_UI_CHARA_HASH[instance_key] != hash40("ui_chara_purin") {
-        // If original UI got corrupted somehow, restore it to ui_chara_purin
-        ORIGINAL_UI_CHARA_HASH[instance_key] = hash40("ui_chara_purin");
+    if is_purin && ORIGINAL_UI_CHARA_HASH[instance_key] != 0x0 && ORIGINAL_UI_CHARA_HASH[instance_key] != match_start_ui {
+        // If original UI got corrupted somehow, restore the captured one
+        ORIGINAL_UI_CHARA_HASH[instance_key] = match_start_ui;
     }
     
     // Determine which UI to use based on evolution stage and status
+    let stage_form = stage_ui_form(player_state);
     let target_ui_hash = if player_state.is_evolving {
         // ★ Show "Who's that Pokemon?" UI during evolution
         hash40("ui_chara_evolving")
     } else {
-        // Normal stage-based UI
-        match player_state.stage {
-            EvolutionStage::Gastly => {
+        // Normal stage/form-based UI (stock icons follow, incl. Mega/Gigantamax and shiny variants)
+        match stage_ui_chara(stage_form, player_state.is_shiny, player_state.shiny_roll.is_won()) {
+            Some(form_ui) => form_ui,
+            None => {
                 // Fallback to "ui_chara_purin" if original UI wasn't stored or is corrupted
                 if ORIGINAL_UI_CHARA_HASH[instance_key] != 0x0 &&
-                   ORIGINAL_UI_CHARA_HASH[instance_key] != hash40("ui_chara_evolving") &&
-                   ORIGINAL_UI_CHARA_HASH[instance_key] != hash40("ui_chara_haunter") &&
-                   ORIGINAL_UI_CHARA_HASH[instance_key] != hash40("ui_chara_gengar") {
+                   !is_gastly_override_ui(ORIGINAL_UI_CHARA_HASH[instance_key]) {
                     ORIGINAL_UI_CHARA_HASH[instance_key]
                 } else {
-                    // PROTECTION: If original UI is corrupted or not stored, use Purin default
+                    // PROTECTION: If original UI is corrupted or not stored, use the captured one
                     // and update the stored hash for future use
-                    let fallback_ui = hash40("ui_chara_purin");
+                    let fallback_ui = match_start_ui;
                     ORIGINAL_UI_CHARA_HASH[instance_key] = fallback_ui;
                     fallback_ui
                 }
-            },
-            EvolutionStage::Haunter => hash40("ui_chara_haunter"),
-            EvolutionStage::Gengar => hash40("ui_chara_gengar")
+            }
         }
     };
     
@@ -155,12 +240,9 @@ pub unsafe fn update_battle_portrait_ui(
         false
     };
 
-    // BULLETPROOF STAGE CHANGE DETECTION: Force UI refresh when evolution stage changes
-    let current_stage_value = match player_state.stage {
-        EvolutionStage::Gastly => 0,
-        EvolutionStage::Haunter => 1,
-        EvolutionStage::Gengar => 2,
-    };
+    // BULLETPROOF STAGE CHANGE DETECTION: Force UI refresh when evolution stage / final smash form changes
+    // (death resets LAST_EVOLUTION_STAGE, so the icons revert along with the stage)
+    let current_stage_value = stage_form as u8;
     
     let last_stage = LAST_EVOLUTION_STAGE[instance_key];
     let stage_changed = last_stage != current_stage_value;
@@ -171,16 +253,15 @@ pub unsafe fn update_battle_portrait_ui(
         
         // Force immediate UI update by ensuring target differs from current
         let current_ui_hash = the_csk_collection_api::get_ui_chara_from_entry_id(entry_id);
-        let target_ui_hash = match player_state.stage {
-            EvolutionStage::Gastly => {
+        let target_ui_hash = match stage_ui_chara(stage_form, player_state.is_shiny, player_state.shiny_roll.is_won()) {
+            Some(form_ui) => form_ui,
+            None => {
                 if ORIGINAL_UI_CHARA_HASH[instance_key] != 0x0 {
                     ORIGINAL_UI_CHARA_HASH[instance_key]
                 } else {
-                    hash40("ui_chara_purin")
+                    match_start_ui
                 }
-            },
-            EvolutionStage::Haunter => hash40("ui_chara_haunter"),
-            EvolutionStage::Gengar => hash40("ui_chara_gengar"),
+            }
         };
         
         // Force the UI change immediately on stage change
@@ -219,7 +300,7 @@ pub unsafe fn update_battle_portrait_ui(
     }
 }
 
-/// Handles cutin effects with appropriate chara_6 UI for different forms
+/// Queues the evolution and final smash cutins (cutins.rs)
 pub unsafe fn handle_cutin_effects(
     boma: *mut BattleObjectModuleAccessor,
     player_state: &PlayerEvolutionState,
@@ -246,6 +327,9 @@ pub unsafe fn handle_cutin_effects(
     // Check for final smash form cutins
     check_for_final_smash_cutins(boma, player_state, fighter, instance_key);
     
+    // Draw/advance the shared cutin queue
+    update_cutins();
+    
     // Check for immediate evolution cutin flags
     if WorkModule::is_flag(boma, FIGHTER_PURIN_INSTANCE_WORK_ID_FLAG_HAUNTER_CUTIN_READY) {
         WorkModule::set_flag(boma, false, FIGHTER_PURIN_INSTANCE_WORK_ID_FLAG_HAUNTER_CUTIN_READY);
@@ -345,70 +429,32 @@ unsafe fn check_for_final_smash_cutins(
     }
 }
 
-/// Triggers cutin effect for evolution with appropriate chara_6 UI
+/// Queues the evolution cutin (drawn by cutins.rs, no chara_6 UI swap)
 unsafe fn trigger_evolution_cutin(
     fighter: &mut L2CFighterCommon,
     evolution_name: &str,
     entry_id: usize
 ) {
-    // Temporarily change to appropriate chara_6 UI for cutin
-    let cutin_ui_hash = match evolution_name {
-        "Haunter" => hash40("ui_chara_haunter_00"),
-        "Gengar" => hash40("ui_chara_gengar_00"),
+    let kind = match evolution_name {
+        "Haunter" => CutinKind::Haunter,
+        "Gengar" => CutinKind::Gengar,
         _ => return,
     };
-    
-    // Change to cutin UI temporarily
-    let boma = fighter.module_accessor;
-    let owner_color = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR);
-    let entry_id_u32 = entry_id as u32;
-    
-    the_csk_collection_api::change_entry_chara_ui(
-        entry_id_u32,
-        cutin_ui_hash,
-        owner_color as _,
-    );
-    
-    // Trigger cutin effect
-    macros::FT_START_CUTIN(fighter);
-    
-    
-    // Schedule restoration of chara_4 UI after cutin (will happen in next frame via update_battle_portrait_ui)
+    queue_cutin(entry_id as u32, kind);
 }
 
-/// Triggers cutin effect for final smash forms with mega/giga chara_6 UI
+/// Queues the final smash form cutin (drawn by cutins.rs, no chara_6 UI swap)
 unsafe fn trigger_final_smash_cutin(
     fighter: &mut L2CFighterCommon,
     form_name: &str,
     entry_id: usize
 ) {
-    // Temporarily change to appropriate chara_6 UI for cutin
-    let cutin_ui_hash = match form_name {
-        "Mega Gengar" => hash40("ui_chara_mega_gengar_00"),
-        "Gigantamax Gengar" => hash40("ui_chara_giga_gengar_00"),
+    let kind = match form_name {
+        "Mega Gengar" => CutinKind::MegaGengar,
+        "Gigantamax Gengar" => CutinKind::GigaGengar,
         _ => return,
     };
-    
-    // Change to cutin UI temporarily
-    let boma = fighter.module_accessor;
-    let owner_color = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR);
-    let entry_id_u32 = entry_id as u32;
-    
-    the_csk_collection_api::change_entry_chara_ui(
-        entry_id_u32,
-        cutin_ui_hash,
-        owner_color as _,
-    );
-    
-    // Trigger cutin effect
-    macros::FT_START_CUTIN(fighter);
-
-    // Delay restoration to let cutin display - use entry_id for this timer since it's for UI API
-    static mut CUTIN_RESTORE_TIMER_FINAL_SMASH: [i32; 8] = [0; 8];
-    if entry_id < 8 {
-        CUTIN_RESTORE_TIMER_FINAL_SMASH[entry_id] = 120; // 2 second delay
-    }
-    
+    queue_cutin(entry_id as u32, kind);
 }
 
 /// Tracks cry sound playback for cutin timing
@@ -441,7 +487,8 @@ pub unsafe fn reset_ui_state_on_death(entry_id: u32) {
     let instance_key = crate::gastly::get_instance_key(boma) as usize;
     if instance_key >= 256 { return; }
     
-    // Reset cutin flags
+    // Reset cutin flags and drop this player's queued cutins
+    clear_cutins_for_entry(entry_id);
     CUTIN_PLAYED_THIS_EVOLUTION[instance_key] = false;
     CUTIN_PLAYED_MEGA[instance_key] = false;
     CUTIN_PLAYED_GIGA[instance_key] = false;
@@ -502,32 +549,4 @@ pub unsafe fn handle_ui_management(
     // Handle cutin effects for evolutions and final smash forms
     handle_cutin_effects(boma, player_state, fighter);
 
-    /// Direct cutin trigger for evolution (bypasses timing checks)
-    pub unsafe fn trigger_evolution_cutin_direct(
-        fighter: &mut L2CFighterCommon,
-        evolution_name: &str,
-        entry_id: usize
-    ) {
-        let cutin_ui_hash = match evolution_name {
-            "Haunter" => hash40("ui_chara_haunter_00"),
-            "Gengar" => hash40("ui_chara_gengar_00"),
-            _ => return,
-        };
-        
-        let boma = fighter.module_accessor;
-        let owner_color = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_COLOR);
-        let entry_id_u32 = entry_id as u32;
-        
-        // Change to cutin UI
-        the_csk_collection_api::change_entry_chara_ui(
-            entry_id_u32,
-            cutin_ui_hash,
-            owner_color as _,
-        );
-        
-        
-        // Trigger cutin effect  
-        macros::FT_START_CUTIN(fighter);
-        
-    }
 }
\ No newline at end of file
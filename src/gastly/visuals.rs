@@ -7,7 +7,15 @@ use smash::app::utility;
 // Import constants (mesh names, motion hashes)
 use crate::gastly::constants::*;
 // Import PlayerEvolutionState and related enums
-use crate::gastly::player_state::{PlayerEvolutionState, EvolutionStage, BlinkPhase};
+use crate::gastly::player_state::{PlayerEvolutionState, EvolutionStage};
+use crate::gastly::expression_engine::eye_choice_mesh;
+use crate::gastly::mesh_manifest::is_mesh_available;
+use crate::gastly::tongue::{select_tongue, tongue_mesh_for_facing};
+use crate::gastly::mesh_visibility::{
+    MeshLayerStack, apply_mesh_layers, set_mesh_visibility,
+    LAYER_BASE, LAYER_BODY, LAYER_EYES, LAYER_TONGUE, LAYER_ANIMATION, LAYER_EVOLVING,
+    LAYER_SHADOWBALL, LAYER_ROLLOUT, LAYER_FINAL_SMASH
+};
 
 // Weakened effect work IDs (use WorkModule instead of static arrays)
 pub const WEAKENED_EFFECT_HANDLE_WORK_ID: i32 = 0x50000021;
@@ -30,21 +38,6 @@ pub enum ShadowballState {
     AirToGroundRolloutWithHitbox,  // Air-to-ground rollout with hitbox
 }
 
-#[derive(Debug, Copy, Clone)]
-struct SimpleHitState {
-    was_hitting: bool,
-    last_bomb_frame: i32,
-}
-
-impl SimpleHitState {
-    const fn new() -> Self {
-        Self {
-            was_hitting: false,
-            last_bomb_frame: -100,
-        }
-    }
-}
-
 //  Centralized function to check if Gastly body should be hidden
 unsafe fn should_force_hide_gastly_body(boma: *mut BattleObjectModuleAccessor, player_state: &PlayerEvolutionState) -> bool {
     // Only hide for Haunter and Gengar stages during final smash
@@ -70,133 +63,19 @@ unsafe fn should_force_hide_gastly_body(boma: *mut BattleObjectModuleAccessor, p
 //  Force hide Gastly body if conditions are met (call this after any mesh visibility setting)
 unsafe fn enforce_gastly_body_hiding(boma: *mut BattleObjectModuleAccessor, player_state: &PlayerEvolutionState) {
     if should_force_hide_gastly_body(boma, player_state) {
-        ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
-    }
-}
-
-//  Check for animations that should show gengar_tongue_normal for Gengar (non-FS modes)
-unsafe fn should_show_gengar_tongue_normal(boma: *mut BattleObjectModuleAccessor, player_state: &PlayerEvolutionState) -> bool {
-    // Only for Gengar stage and not in final smash forms
-    if player_state.stage != EvolutionStage::Gengar || 
-       player_state.giga_gengar_form_active || 
-       player_state.mega_gengar_form_active {
-        return false;
-    }
-    
-    let current_motion = MotionModule::motion_kind(boma);
-    let motion_hash = Hash40 { hash: current_motion };
-    
-    // List of animations that should show gengar_tongue_normal
-    let tongue_normal_animations = [
-        smash::hash40("final"),
-        smash::hash40("final_air"),
-        smash::hash40("special_hi"),          
-        smash::hash40("special_air_hi"),     
-        smash::hash40("special_air_s"),
-        smash::hash40("attack_dash"),
-        smash::hash40("attack_s3_hi"),     
-        smash::hash40("attack_s3_lw"),    
-        smash::hash40("attack_s3_s"),    
-        smash::hash40("attack_s4_s"),
-        smash::hash40("attack_hi4"),
-        smash::hash40("attack_lw4"),
-        smash::hash40("attack_air_hi"),    
-        smash::hash40("attack_air_f"),      
-        smash::hash40("attack_air_n"),        
-        smash::hash40("dash"),
-        smash::hash40("turn_dash"),
-        smash::hash40("attack_hi3"),
-        smash::hash40("attack_lw3"),
-        smash::hash40("attack_lw4_hold"),
-        smash::hash40("special_s"),
-        smash::hash40("damage_hi_1"),
-        smash::hash40("damage_hi_2"),
-        smash::hash40("damage_hi_3"),
-        smash::hash40("damage_lw_1"),
-        smash::hash40("damage_lw_2"),
-        smash::hash40("damage_lw_3"),
-        smash::hash40("damage_n_1"),
-        smash::hash40("damage_n_2"),
-        smash::hash40("damage_n_3"),
-        smash::hash40("damage_fly_hi"),
-        smash::hash40("damage_fly_lw"),
-        smash::hash40("damage_fly_n"),
-    ];
-    
-    tongue_normal_animations.contains(&motion_hash.hash)
-}
-
-//  Check for attack_air_lw animation with frame-specific tongue visibility
-unsafe fn should_show_tongue_for_attack_air_lw(boma: *mut BattleObjectModuleAccessor, _player_state: &PlayerEvolutionState) -> bool {
-    let current_motion = MotionModule::motion_kind(boma);
-    let motion_hash = Hash40 { hash: current_motion };
-    
-    if motion_hash.hash == smash::hash40("attack_air_lw") {
-        let motion_frame = MotionModule::frame(boma);
-        
-        // Show tongue during frames 7-49
-        if motion_frame >= 7.0 && motion_frame <= 49.0 {
-            return true;
-        }
+        set_mesh_visibility(boma, *GASTLY_BODY, false);
     }
-    
-    false
 }
 
 unsafe fn hide_all_evolving_meshes(boma: *mut BattleObjectModuleAccessor) {
     // Hide all evolving meshes
-    ModelModule::set_mesh_visibility(boma, *GASTLY_EVOLVING, false);
-    ModelModule::set_mesh_visibility(boma, *GASTLY_EVOLVING_FLOORSHADOW, false);
-    ModelModule::set_mesh_visibility(boma, *GASTLY_EVOLVING_SHADOWBALL, false);
-    ModelModule::set_mesh_visibility(boma, *GASTLY_EVOLVING_TONGUE, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_EVOLVING, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_EVOLVING_FLOORSHADOW, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_EVOLVING_SHADOWBALL, false);
-}
-
-// Gastly Evolving Tongue Swap
-
-unsafe fn handle_evolving_tongue_visibility(
-    boma: *mut BattleObjectModuleAccessor,
-    player_state: &PlayerEvolutionState
-) -> bool {
-    // Only during Gastly → Haunter evolution
-    if !player_state.is_evolving || 
-       player_state.stage != EvolutionStage::Gastly || 
-       player_state.evolution_target_stage != EvolutionStage::Haunter {
-        return false;
-    }
-    
-    // Check for attack_air_lw animation with frame-specific tongue visibility
-    let current_motion = MotionModule::motion_kind(boma);
-    let motion_hash = Hash40 { hash: current_motion };
-    
-    if motion_hash.hash == smash::hash40("attack_air_lw") {
-        let motion_frame = MotionModule::frame(boma);
-        
-        // Show evolving tongue during frames 7-49 (same as normal tongue)
-        if motion_frame >= 7.0 && motion_frame <= 49.0 {
-            ModelModule::set_mesh_visibility(boma, *GASTLY_EVOLVING_TONGUE, true);
-                        return true;
-        }
-    }
-    
-    // Check for catch animations (catch_attack, catch_wait) 
-    if motion_hash.hash == smash::hash40("catch_attack") || motion_hash.hash == smash::hash40("catch_wait") {
-        ModelModule::set_mesh_visibility(boma, *GASTLY_EVOLVING_TONGUE, true);
-                return true;
-    }
-    
-    false
-}
-
-// Check for catch animations that should show long tongue
-unsafe fn should_show_tongue_for_catch_animations(boma: *mut BattleObjectModuleAccessor) -> bool {
-    let current_motion = MotionModule::motion_kind(boma);
-    let motion_hash = Hash40 { hash: current_motion };
-    
-    // Show tongue during catch_attack and catch_wait
-    motion_hash.hash == smash::hash40("catch_attack") || motion_hash.hash == smash::hash40("catch_wait")
+    set_mesh_visibility(boma, *GASTLY_EVOLVING, false);
+    set_mesh_visibility(boma, *GASTLY_EVOLVING_FLOORSHADOW, false);
+    set_mesh_visibility(boma, *GASTLY_EVOLVING_SHADOWBALL, false);
+    set_mesh_visibility(boma, *GASTLY_EVOLVING_TONGUE, false);
+    set_mesh_visibility(boma, *HAUNTER_EVOLVING, false);
+    set_mesh_visibility(boma, *HAUNTER_EVOLVING_FLOORSHADOW, false);
+    set_mesh_visibility(boma, *HAUNTER_EVOLVING_SHADOWBALL, false);
 }
 
 // Also update the update_body_and_unique_parts_visibility function to not interfere
@@ -222,23 +101,26 @@ pub unsafe fn update_body_and_unique_parts_visibility(boma: *mut BattleObjectMod
     }
         
     // Always hide all meshes first (both normal and special)
-    ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *GASTLY_TONGUE, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_HANDS, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
-    ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
+    set_mesh_visibility(boma, *GASTLY_BODY, false);
+    set_mesh_visibility(boma, *GASTLY_TONGUE, false);
+    set_mesh_visibility(boma, *HAUNTER_BODY, false);
+    set_mesh_visibility(boma, *HAUNTER_HANDS, false);
+    set_mesh_visibility(boma, *GENGAR_BODY, false);
+    set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
+    set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
+    for tongue in MIRRORED_TONGUES.iter() {
+        set_mesh_visibility(boma, *tongue, false);
+    }
+    set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
+    set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
 
     // Hide all eye expressions
-    for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-    for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-    for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
+    for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() { set_mesh_visibility(boma, *eye_hash, false); }
+    for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() { set_mesh_visibility(boma, *eye_hash, false); }
+    for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { set_mesh_visibility(boma, *eye_hash, false); }
     
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, false);
+    set_mesh_visibility(boma, *HAUNTER_IRIS, false);
+    set_mesh_visibility(boma, *GENGAR_IRIS, false);
     
     // Hide all animation-specific meshes
     hide_all_animation_specific_meshes(boma);
@@ -246,16 +128,16 @@ pub unsafe fn update_body_and_unique_parts_visibility(boma: *mut BattleObjectMod
     // Show appropriate body parts based on stage (only normal body parts, not eyes)
     match active_stage {
         EvolutionStage::Gastly => { 
-            ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, true); 
+            set_mesh_visibility(boma, *GASTLY_BODY, true); 
         }
         EvolutionStage::Haunter => {
-            ModelModule::set_mesh_visibility(boma, *HAUNTER_BODY, true);
-            ModelModule::set_mesh_visibility(boma, *HAUNTER_HANDS, true);
-            ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, true);
+            set_mesh_visibility(boma, *HAUNTER_BODY, true);
+            set_mesh_visibility(boma, *HAUNTER_HANDS, true);
+            set_mesh_visibility(boma, *HAUNTER_IRIS, true);
         }
         EvolutionStage::Gengar => {
-            ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, true);
-            ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, true);
+            set_mesh_visibility(boma, *GENGAR_BODY, true);
+            set_mesh_visibility(boma, *GENGAR_IRIS, true);
         }
     }
 }
@@ -448,6 +330,50 @@ unsafe fn handle_shadowball_roll_transition(player_state: &mut PlayerEvolutionSt
 }
 
 // In visuals.rs - Updated set_active_eye_mesh function
+// Normal body parts, tongues, irises and eyes of every stage
+fn normal_meshes_and_eyes() -> Vec<Hash40> {
+    let mut meshes = vec![
+        *GASTLY_BODY, *GASTLY_TONGUE,
+        *HAUNTER_BODY, *HAUNTER_HANDS,
+        *GENGAR_BODY, *GENGAR_TONGUE_LONG, *GENGAR_TONGUE_NORMAL,
+        *HAUNTER_IRIS, *GENGAR_IRIS,
+    ];
+    meshes.extend(MIRRORED_TONGUES.iter());
+    meshes.extend(GASTLY_EYE_EXPRESSIONS.iter());
+    meshes.extend(HAUNTER_EYELID_EXPRESSIONS.iter());
+    meshes.extend(GENGAR_EYELID_EXPRESSIONS.iter());
+    meshes
+}
+
+fn evolving_meshes() -> Vec<Hash40> {
+    vec![
+        *GASTLY_EVOLVING, *GASTLY_EVOLVING_FLOORSHADOW, *GASTLY_EVOLVING_SHADOWBALL, *GASTLY_EVOLVING_TONGUE,
+        *HAUNTER_EVOLVING, *HAUNTER_EVOLVING_FLOORSHADOW, *HAUNTER_EVOLVING_SHADOWBALL,
+    ]
+}
+
+// Floor shadows, shadow balls, ghost, Gengar run, tongues and evolving meshes
+fn animation_specific_meshes() -> Vec<Hash40> {
+    let mut meshes = vec![
+        *GASTLY_FLOORSHADOW, *HAUNTER_FLOORSHADOW, *GENGAR_FLOORSHADOW,
+        *GASTLY_SHADOWBALL, *HAUNTER_SHADOWBALL, *GENGAR_SHADOWBALL,
+        *GHOST, *GENGAR_RUN,
+        *GASTLY_TONGUE, *GENGAR_TONGUE_NORMAL, *GENGAR_TONGUE_LONG,
+    ];
+    meshes.extend(MIRRORED_TONGUES.iter());
+    meshes.extend(evolving_meshes());
+    meshes
+}
+
+// Everything hide_all_meshes touches
+fn all_character_meshes() -> Vec<Hash40> {
+    let mut meshes = normal_meshes_and_eyes();
+    meshes.extend([*MEGA_GENGAR_BODY, *GIGA_GENGAR_BODY]);
+    meshes.extend(PURIN_VANILLA_EYES_TO_HIDE.iter());
+    meshes.extend(animation_specific_meshes());
+    meshes
+}
+
 pub unsafe fn set_active_eye_mesh(
     boma: *mut BattleObjectModuleAccessor, 
     player_state: &mut PlayerEvolutionState, 
@@ -456,353 +382,217 @@ pub unsafe fn set_active_eye_mesh(
     let current_status = StatusModule::status_kind(boma);
     let current_motion = MotionModule::motion_kind(boma);
     
-    // First, always ensure all animation-specific meshes are hidden
-    hide_all_animation_specific_meshes(boma);
-    hide_all_evolving_meshes(boma); // Also hide evolving meshes initially
-    
-    // PRIORITY 1: Enhanced shadowball detection (highest priority - can override evolution)
     let shadowball_state = detect_shadowball_hitbox_state(boma, player_state);
-    
-    // Handle shadowball states FIRST (even during evolution)
-    match shadowball_state {
-        ShadowballState::ActiveWithHitbox | ShadowballState::ActiveFrameBased => {
-            let is_hold_status = current_status == 0x1E1 || current_status == 0x1E2;
-            
-            if is_hold_status {
-                // PRIORITY: During hold/charge status, ALWAYS show shadowball mesh (never evolving mesh)
-                // This fixes the issue where evolving mesh shows during special n hold when shadow ball effect is active
-                let shadow_ball_mesh = if player_state.is_evolving {
-                    // Show evolving shadowball mesh during evolution hold status
-                    match (player_state.stage, player_state.evolution_target_stage) {
-                        (EvolutionStage::Gastly, EvolutionStage::Haunter) => *GASTLY_EVOLVING_SHADOWBALL,
-                        (EvolutionStage::Haunter, EvolutionStage::Gengar) => *HAUNTER_EVOLVING_SHADOWBALL,
-                        _ => match player_state.stage {
-                            EvolutionStage::Gastly => *GASTLY_SHADOWBALL,
-                            EvolutionStage::Haunter => *HAUNTER_SHADOWBALL,
-                            EvolutionStage::Gengar => *GENGAR_SHADOWBALL,
-                        }
-                    }
-                } else {
-                    // Not evolving - normal shadowball mesh logic
-                    match player_state.stage {
-                        EvolutionStage::Gastly => *GASTLY_SHADOWBALL,
-                        EvolutionStage::Haunter => *HAUNTER_SHADOWBALL,
-                        EvolutionStage::Gengar => *GENGAR_SHADOWBALL,
-                    }
-                };
-                
-                ModelModule::set_mesh_visibility(boma, shadow_ball_mesh, true);
-                hide_all_normal_meshes_and_eyes(boma);
-                return;
-            }
-        },
-        
-        // ANY rollout with active hitbox = completely invisible (no evolving mesh)
-        ShadowballState::ChargedRolloutWithHitbox | 
-        ShadowballState::RegularRolloutWithHitbox | 
-        ShadowballState::AirToGroundRolloutWithHitbox => {
-            hide_all_meshes(boma);
-            return;
-        },
-        
-        // Rollout without hitbox OR turn status = visible (evolving if evolving, normal if not)
-        ShadowballState::ChargedRollout |
-        ShadowballState::RegularRollout |
-        ShadowballState::AirToGroundRollout => {
-            // SPECIAL CASE: TURN status should show model (evolving if evolving)
-            if current_status == PURIN_SPECIAL_N_TURN {
-                if player_state.is_evolving {
-                    // Show evolving mesh during turn status
-                    let evolving_main = match (player_state.stage, player_state.evolution_target_stage) {
-                        (EvolutionStage::Gastly, EvolutionStage::Haunter) => *GASTLY_EVOLVING,
-                        (EvolutionStage::Haunter, EvolutionStage::Gengar) => *HAUNTER_EVOLVING,
-                        _ => {
-                            restore_normal_body_parts(boma, player_state);
-                            enforce_gastly_body_hiding(boma, player_state);
-                            handle_animation_specific_tongue_visibility(boma, player_state);
-                            show_appropriate_eye_expression(boma, player_state, game_state_expression_override);
-                            return;
-                        }
-                    };
-                    ModelModule::set_mesh_visibility(boma, evolving_main, true);
-                    
-                    // Hide all eyes during evolution turn
-                    for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() { 
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false); 
-                    }
-                    for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() { 
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false); 
-                    }
-                    for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { 
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false); 
-                    }
-                    ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, false);
-                    ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, false);
-                    return;
-                } else {
-                    // Normal turn status - show normal model
-                    restore_normal_body_parts(boma, player_state);
-                    enforce_gastly_body_hiding(boma, player_state);
-                    handle_animation_specific_tongue_visibility(boma, player_state);
-                    show_appropriate_eye_expression(boma, player_state, game_state_expression_override);
-                    return;
-                }
-            } else {
-                // Regular rollout without hitbox - check if evolving
-                if player_state.is_evolving {
-                    // Show evolving mesh during ground rollout when evolving (fixes air-to-ground transition)
-                    let evolving_main = match (player_state.stage, player_state.evolution_target_stage) {
-                        (EvolutionStage::Gastly, EvolutionStage::Haunter) => *GASTLY_EVOLVING,
-                        (EvolutionStage::Haunter, EvolutionStage::Gengar) => *HAUNTER_EVOLVING,
-                        _ => {
-                            restore_normal_body_parts(boma, player_state);
-                            enforce_gastly_body_hiding(boma, player_state);
-                            handle_animation_specific_tongue_visibility(boma, player_state);
-                            show_appropriate_eye_expression(boma, player_state, game_state_expression_override);
-                            return;
-                        }
-                    };
-                    ModelModule::set_mesh_visibility(boma, evolving_main, true);
-                    
-                    // Hide all eyes during evolution rollout
-                    for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() { 
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false); 
-                    }
-                    for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() { 
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false); 
-                    }
-                    for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { 
-                        ModelModule::set_mesh_visibility(boma, *eye_hash, false); 
-                    }
-                    ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, false);
-                    ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, false);
-                    return;
-                } else {
-                    // Not evolving - normal rollout visibility logic
-                    restore_normal_body_parts(boma, player_state);
-                    enforce_gastly_body_hiding(boma, player_state);
-                    handle_animation_specific_tongue_visibility(boma, player_state);
-                    show_appropriate_eye_expression(boma, player_state, game_state_expression_override);
-                    return;
-                }
-            }
-        },
-        
-        ShadowballState::ChargingBelowThreshold |
-        ShadowballState::TransitionKeepModel => {
-            // Continue to normal processing
-        },
-        
-        ShadowballState::NotActive => {
-            // Reset shadowball tracking if completely out of shadowball statuses
-            if player_state.is_in_shadowball_status || player_state.shadowball_status_frames > 0 {
-                player_state.is_in_shadowball_status = false;
-                player_state.shadowball_status_frames = 0;
-                player_state.last_shadowball_status = -1;
-                player_state.shadowball_previous_status = -1;
-                player_state.shadowball_air_charge_count = 0;
-            }
+    if shadowball_state == ShadowballState::NotActive {
+        // Reset shadowball tracking if completely out of shadowball statuses
+        if player_state.is_in_shadowball_status || player_state.shadowball_status_frames > 0 {
+            player_state.is_in_shadowball_status = false;
+            player_state.shadowball_status_frames = 0;
+            player_state.last_shadowball_status = -1;
+            player_state.shadowball_previous_status = -1;
+            player_state.shadowball_air_charge_count = 0;
         }
     }
-    
-    // PRIORITY 2: Check if we should show evolving meshes (only if not handled by shadowball logic above)
-    if player_state.is_evolving {
-        if show_evolving_meshes_for_animation(boma, player_state, current_motion) {
-            // Hide all normal meshes when showing evolving meshes
-            hide_all_normal_meshes_and_eyes(boma);
-            
-            // Hide ALL eye expressions during evolution - no blinking!
-            for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() { 
-                ModelModule::set_mesh_visibility(boma, *eye_hash, false); 
-            }
-            for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() { 
-                ModelModule::set_mesh_visibility(boma, *eye_hash, false); 
-            }
-            for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { 
-                ModelModule::set_mesh_visibility(boma, *eye_hash, false); 
-            }
-            for eye_hash in PURIN_VANILLA_EYES_TO_HIDE.iter() {
-                ModelModule::set_mesh_visibility(boma, *eye_hash, false);
-            }
-            
-            // Hide iris as well during evolution
-            ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, false);
-            ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, false);
-            
-            // Hide all body parts (they'll be replaced by evolving mesh)
-            ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
-            ModelModule::set_mesh_visibility(boma, *HAUNTER_BODY, false);
-            ModelModule::set_mesh_visibility(boma, *HAUNTER_HANDS, false);
-            ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, false);
-            ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
-            ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
-            
-            return; // Exit early - ONLY evolving mesh should be visible, nothing else
-        }
+
+    // Each subsystem adds its requests; priorities decide overrides (see mesh_visibility.rs)
+    let mut layers = MeshLayerStack::new();
+    layers.hide(LAYER_BASE, &animation_specific_meshes());
+    layers.hide(LAYER_BASE, &normal_meshes_and_eyes());
+
+    add_body_layer(&mut layers, player_state);
+    add_tongue_layer(&mut layers, boma, player_state);
+    add_animation_mesh_layer(&mut layers, boma, player_state, current_motion);
+    add_evolving_layer(&mut layers, boma, player_state, current_status, current_motion);
+    add_shadowball_layer(&mut layers, boma, player_state, current_status, &shadowball_state);
+
+    if should_force_hide_gastly_body(boma, player_state) {
+        layers.hide(LAYER_FINAL_SMASH, &[*GASTLY_BODY]);
     }
-    
-    // PRIORITY 3: Check for other special animation meshes (non-shadowball)
-    let using_special_mesh = check_and_show_other_animation_meshes(boma, player_state);
-    
-    if using_special_mesh {
-        hide_all_normal_meshes_and_eyes(boma);
-        return;
+
+    // Eyes go last so the weakened state only runs when the eye would actually be visible
+    add_eye_layer(&mut layers, boma, player_state, game_state_expression_override);
+
+    apply_mesh_layers(boma, &layers);
+}
+
+//  Stage body parts
+fn add_body_layer(layers: &mut MeshLayerStack, player_state: &PlayerEvolutionState) {
+    match player_state.stage {
+        EvolutionStage::Gastly => layers.show(LAYER_BODY, &[*GASTLY_BODY]),
+        EvolutionStage::Haunter => layers.show(LAYER_BODY, &[*HAUNTER_BODY, *HAUNTER_HANDS, *HAUNTER_IRIS]),
+        EvolutionStage::Gengar => layers.show(LAYER_BODY, &[*GENGAR_BODY, *GENGAR_IRIS]),
+    }
+}
+
+//  Animation-specific tongue visibility (mirrored copy when facing left, see tongue.rs)
+unsafe fn add_tongue_layer(layers: &mut MeshLayerStack, boma: *mut BattleObjectModuleAccessor, player_state: &PlayerEvolutionState) {
+    let final_smash_form_selected = player_state.mega_gengar_form_active || player_state.giga_gengar_form_active;
+    let tongue = select_tongue(player_state.stage, final_smash_form_selected, MotionModule::motion_kind(boma), MotionModule::frame(boma));
+    if let Some(tongue) = tongue {
+        let has_mirrored_copy = is_mesh_available(boma, tongue.mirrored_mesh());
+        layers.show(LAYER_TONGUE, &[tongue_mesh_for_facing(tongue, PostureModule::lr(boma), has_mirrored_copy)]);
+    }
+}
+
+//  Non-shadowball animation meshes replace the normal body and eyes
+unsafe fn add_animation_mesh_layer(layers: &mut MeshLayerStack, boma: *mut BattleObjectModuleAccessor, player_state: &PlayerEvolutionState, current_motion: u64) {
+    let animation_mesh = if current_motion == SQUAT_WAIT_MOTION.hash {
+        // Floor shadow
+        Some(match player_state.stage {
+            EvolutionStage::Gastly => *GASTLY_FLOORSHADOW,
+            EvolutionStage::Haunter => *HAUNTER_FLOORSHADOW,
+            EvolutionStage::Gengar => *GENGAR_FLOORSHADOW,
+        })
+    } else if current_motion == APPEAL_LW_L_MOTION.hash || current_motion == APPEAL_LW_R_MOTION.hash {
+        // Ghost
+        Some(*GHOST)
+    } else if current_motion == RUN_MOTION.hash && player_state.stage == EvolutionStage::Gengar {
+        // Run (Gengar only)
+        Some(*GENGAR_RUN)
+    } else {
+        None
+    };
+
+    // Slots whose model lacks the mesh keep the normal body (see mesh_manifest.rs)
+    if let Some(mesh) = animation_mesh.filter(|mesh| is_mesh_available(boma, *mesh)) {
+        layers.hide(LAYER_ANIMATION, &normal_meshes_and_eyes());
+        layers.show(LAYER_ANIMATION, &[mesh]);
     }
-    
-    // PRIORITY 4: Default - Restore normal body parts and show eyes
-    restore_normal_body_parts(boma, player_state);
-    enforce_gastly_body_hiding(boma, player_state);
-    handle_animation_specific_tongue_visibility(boma, player_state);
-    show_appropriate_eye_expression(boma, player_state, game_state_expression_override);
 }
 
-//  Handle animation-specific tongue visibility
-unsafe fn handle_animation_specific_tongue_visibility(boma: *mut BattleObjectModuleAccessor, player_state: &PlayerEvolutionState) {
-    // Check for gengar_tongue_normal animations (Gengar only, non-FS)
-    if should_show_gengar_tongue_normal(boma, player_state) {
-        ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, true);
+//  Evolving meshes replace every normal and animation mesh
+unsafe fn add_evolving_layer(
+    layers: &mut MeshLayerStack,
+    boma: *mut BattleObjectModuleAccessor,
+    player_state: &PlayerEvolutionState,
+    current_status: i32,
+    current_motion: u64
+) {
+    if !player_state.is_evolving {
         return;
     }
-    
-    // Check for attack_air_lw frame-specific tongue (frames 7-49)
-    if should_show_tongue_for_attack_air_lw(boma, player_state) {
-        match player_state.stage {
-            EvolutionStage::Gastly => {
-                ModelModule::set_mesh_visibility(boma, *GASTLY_TONGUE, true);
-            }
-            EvolutionStage::Gengar => {
-                ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, true);
-            }
-            _ => {} // Haunter doesn't have tongue for this animation
-        }
+
+    let is_gastly_to_haunter = player_state.stage == EvolutionStage::Gastly && player_state.evolution_target_stage == EvolutionStage::Haunter;
+    let (evolving_main, evolving_floorshadow) = match (player_state.stage, player_state.evolution_target_stage) {
+        (EvolutionStage::Gastly, EvolutionStage::Haunter) => (*GASTLY_EVOLVING, *GASTLY_EVOLVING_FLOORSHADOW),
+        (EvolutionStage::Haunter, EvolutionStage::Gengar) => (*HAUNTER_EVOLVING, *HAUNTER_EVOLVING_FLOORSHADOW),
+        _ => return, // Invalid evolution combination - normal meshes stay
+    };
+    if !is_mesh_available(boma, evolving_main) {
         return;
     }
-    
-    // Check for catch animations (catch_attack, catch_wait)
-    if should_show_tongue_for_catch_animations(boma) {
-        match player_state.stage {
-            EvolutionStage::Gastly => {
-                ModelModule::set_mesh_visibility(boma, *GASTLY_TONGUE, true);
-            }
-            EvolutionStage::Gengar => {
-                ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, true);
-            }
-            _ => {} // Haunter doesn't have tongue for catch animations
+
+    let is_rollout = current_status == PURIN_SPECIAL_N_ROLL ||
+                     current_status == PURIN_SPECIAL_N_ROLL_AIR ||
+                     current_status == PURIN_SPECIAL_N_TURN;
+
+    // SPECIAL CASE: During evolution with hold statuses, an active hitbox means the shadow ball shows instead
+    if current_status == PURIN_SPECIAL_N_HOLD || current_status == PURIN_SPECIAL_N_HOLD_MAX {
+        let has_active_hitbox = AttackModule::is_attack(boma, 0, false) ||
+                               AttackModule::is_attack(boma, 1, false) ||
+                               AttackModule::is_attack(boma, 2, false) ||
+                               AttackModule::is_infliction_status(boma, 0) ||
+                               AttackModule::is_attack_occur(boma);
+        if has_active_hitbox {
+            return;
         }
     }
-}
 
-//  Separate function to hide all normal meshes and eyes
-unsafe fn hide_all_normal_meshes_and_eyes(boma: *mut BattleObjectModuleAccessor) {
-    // Hide all normal body meshes
-    ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *GASTLY_TONGUE, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_HANDS, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, false);
-    
-    // Hide all eyes
-    for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-    for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-    for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
+    let motion_frame = MotionModule::frame(boma);
+    let shows_evolving_tongue = is_gastly_to_haunter && !is_rollout && (
+        (current_motion == smash::hash40("attack_air_lw") && motion_frame >= 7.0 && motion_frame <= 49.0) ||
+        current_motion == smash::hash40("catch_attack") ||
+        current_motion == smash::hash40("catch_wait")
+    );
+
+    // Hide ALL eye expressions and body parts during evolution - no blinking!
+    layers.hide(LAYER_EVOLVING, &normal_meshes_and_eyes());
+    layers.hide(LAYER_EVOLVING, &animation_specific_meshes());
+    layers.hide(LAYER_EVOLVING, &PURIN_VANILLA_EYES_TO_HIDE[..]);
+    layers.hide(LAYER_EVOLVING, &[*MEGA_GENGAR_BODY, *GIGA_GENGAR_BODY]);
+
+    if current_motion == SQUAT_WAIT_MOTION.hash && !is_rollout && is_mesh_available(boma, evolving_floorshadow) {
+        layers.show(LAYER_EVOLVING, &[evolving_floorshadow]);
+    } else if shows_evolving_tongue {
+        layers.show(LAYER_EVOLVING, &[evolving_main, *GASTLY_EVOLVING_TONGUE]);
+    } else {
+        layers.show(LAYER_EVOLVING, &[evolving_main]);
+    }
 }
 
-//  Restore normal body parts based on current stage
-unsafe fn restore_normal_body_parts(boma: *mut BattleObjectModuleAccessor, player_state: &PlayerEvolutionState) {
-    match player_state.stage {
-        EvolutionStage::Gastly => { 
-            ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, true);
-        }
-        EvolutionStage::Haunter => {
-            ModelModule::set_mesh_visibility(boma, *HAUNTER_BODY, true);
-            ModelModule::set_mesh_visibility(boma, *HAUNTER_HANDS, true);
-            ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, true);
-        }
-        EvolutionStage::Gengar => {
-            ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, true);
-            ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, true);
-        }
+//  Shadow ball hold mesh and invisible rollout
+unsafe fn add_shadowball_layer(
+    layers: &mut MeshLayerStack,
+    boma: *mut BattleObjectModuleAccessor,
+    player_state: &PlayerEvolutionState,
+    current_status: i32,
+    shadowball_state: &ShadowballState
+) {
+    match shadowball_state {
+        ShadowballState::ActiveWithHitbox | ShadowballState::ActiveFrameBased => {
+            let is_hold_status = current_status == 0x1E1 || current_status == 0x1E2;
+            if !is_hold_status {
+                return;
+            }
+
+            // During hold/charge status, ALWAYS show shadowball mesh (evolving shadowball while evolving)
+            let shadow_ball_mesh = match (player_state.is_evolving, player_state.stage, player_state.evolution_target_stage) {
+                (true, EvolutionStage::Gastly, EvolutionStage::Haunter) => *GASTLY_EVOLVING_SHADOWBALL,
+                (true, EvolutionStage::Haunter, EvolutionStage::Gengar) => *HAUNTER_EVOLVING_SHADOWBALL,
+                (_, EvolutionStage::Gastly, _) => *GASTLY_SHADOWBALL,
+                (_, EvolutionStage::Haunter, _) => *HAUNTER_SHADOWBALL,
+                (_, EvolutionStage::Gengar, _) => *GENGAR_SHADOWBALL,
+            };
+
+            if !is_mesh_available(boma, shadow_ball_mesh) {
+                return;
+            }
+
+            layers.hide(LAYER_SHADOWBALL, &normal_meshes_and_eyes());
+            layers.hide(LAYER_SHADOWBALL, &evolving_meshes());
+            layers.show(LAYER_SHADOWBALL, &[shadow_ball_mesh]);
+        },
+
+        // ANY rollout with active hitbox = completely invisible (no evolving mesh)
+        ShadowballState::ChargedRolloutWithHitbox |
+        ShadowballState::RegularRolloutWithHitbox |
+        ShadowballState::AirToGroundRolloutWithHitbox => {
+            layers.hide(LAYER_ROLLOUT, &all_character_meshes());
+        },
+
+        _ => {}
     }
 }
 
-//  Show appropriate eye expression (extracted from original logic)
-unsafe fn show_appropriate_eye_expression(
+//  Eye expression for the current stage (expression engine or game state override)
+unsafe fn add_eye_layer(
+    layers: &mut MeshLayerStack,
     boma: *mut BattleObjectModuleAccessor, 
     player_state: &PlayerEvolutionState, 
     game_state_expression_override: Option<Hash40>
 ) {
-    let (current_stage_expressions, default_mesh_to_use, half_mesh_to_use, full_mesh_to_use) = match player_state.stage {
-        EvolutionStage::Gastly => {
-            for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-            for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-            (GASTLY_EYE_EXPRESSIONS.as_slice(), *GASTLY_EYE_N, *GASTLY_EYE_HALFBLINK1, *GASTLY_EYE_BLINK)
-        }
-        EvolutionStage::Haunter => {
-            for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-            for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-            (HAUNTER_EYELID_EXPRESSIONS.as_slice(), *HAUNTER_EYE_N, *HAUNTER_EYE_HALFBLINK1, *HAUNTER_EYE_BLINK)
-        }
-        EvolutionStage::Gengar => {
-            for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-            for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-            (GENGAR_EYELID_EXPRESSIONS.as_slice(), *GENGAR_EYE_N, *GENGAR_EYE_HALFBLINK1, *GENGAR_EYE_BLINK)
-        }
+    let default_mesh_to_use = match player_state.stage {
+        EvolutionStage::Gastly => *GASTLY_EYE_N,
+        EvolutionStage::Haunter => *HAUNTER_EYE_N,
+        EvolutionStage::Gengar => *GENGAR_EYE_N,
     };
-
-    for eye_hash in current_stage_expressions.iter() {
-        ModelModule::set_mesh_visibility(boma, *eye_hash, false);
-    }
     
     let eye_to_show = if let Some(override_eye_hash) = game_state_expression_override {
         override_eye_hash
     } else {
-        match player_state.blink_phase {
-            BlinkPhase::Open => default_mesh_to_use,
-            BlinkPhase::HalfClose | BlinkPhase::HalfOpen => half_mesh_to_use,
-            BlinkPhase::FullClose => full_mesh_to_use,
-        }
+        eye_choice_mesh(player_state.stage, player_state.expressions.current(), &|mesh| is_mesh_available(boma, mesh))
     };
 
-    // Apply weakened state system here
-    let final_eye_to_show = handle_weakened_state_isolated(boma, player_state, eye_to_show, player_state.current_frame);
-
-    ModelModule::set_mesh_visibility(boma, final_eye_to_show, true);
-}
-
+    // Expressions missing from the slot's model fall back to the open eye
+    let eye_to_show = if is_mesh_available(boma, eye_to_show) { eye_to_show } else { default_mesh_to_use };
 
-//  Check for non-shadowball animation meshes (refactored from original)
-unsafe fn check_and_show_other_animation_meshes(boma: *mut BattleObjectModuleAccessor, player_state: &PlayerEvolutionState) -> bool {
-    let current_motion = MotionModule::motion_kind(boma);
-    
-    // Check for squat_wait animation (floor shadow)
-    if current_motion == SQUAT_WAIT_MOTION.hash {
-        let floor_shadow_mesh = match player_state.stage {
-            EvolutionStage::Gastly => *GASTLY_FLOORSHADOW,
-            EvolutionStage::Haunter => *HAUNTER_FLOORSHADOW,
-            EvolutionStage::Gengar => *GENGAR_FLOORSHADOW,
-        };
-        ModelModule::set_mesh_visibility(boma, floor_shadow_mesh, true);
-        return true;
-    }
-    
-    // Check for appeal_lw animations (ghost)
-    if current_motion == APPEAL_LW_L_MOTION.hash ||
-       current_motion == APPEAL_LW_R_MOTION.hash {
-        ModelModule::set_mesh_visibility(boma, *GHOST, true);
-        return true;
-    }
-    
-    // Check for run animation (gengar only)
-    if current_motion == RUN_MOTION.hash && player_state.stage == EvolutionStage::Gengar {
-        ModelModule::set_mesh_visibility(boma, *GENGAR_RUN, true);
-        return true;
+    if !layers.would_show_at(eye_to_show.hash, LAYER_EYES) {
+        return;
     }
-    
-    false
+
+    // Apply weakened state system here
+    let final_eye_to_show = handle_weakened_state_isolated(boma, player_state, eye_to_show, player_state.current_frame);
+    layers.show(LAYER_EYES, &[final_eye_to_show]);
 }
 
 pub unsafe fn handle_final_smash_model_swap(boma: *mut BattleObjectModuleAccessor, player_state: &mut PlayerEvolutionState) {
@@ -814,51 +604,51 @@ pub unsafe fn handle_final_smash_model_swap(boma: *mut BattleObjectModuleAccesso
                                     current_motion == FINAL_SMASH_START_R_MOTION_HASH.hash;
     
     // DEBUG: Log final smash state
-    static mut LAST_FS_STATE: [bool; 8] = [false; 8];
-    static mut LAST_MOTION: [u64; 8] = [0; 8];
-    static mut LAST_FS_FORM: [bool; 8] = [false; 8];
-    let entry_id = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as usize;
-    
-    if entry_id < 8 {
-        if LAST_FS_STATE[entry_id] != is_final_smash_active_flag || 
-           LAST_MOTION[entry_id] != current_motion || 
-           LAST_FS_FORM[entry_id] != player_state.is_in_final_smash_form {
+    static mut LAST_FS_STATE: [bool; 256] = [false; 256];
+    static mut LAST_MOTION: [u64; 256] = [0; 256];
+    static mut LAST_FS_FORM: [bool; 256] = [false; 256];
+    let instance_key = crate::gastly::get_instance_key(boma) as usize;
+    
+    if instance_key < 256 {
+        if LAST_FS_STATE[instance_key] != is_final_smash_active_flag || 
+           LAST_MOTION[instance_key] != current_motion || 
+           LAST_FS_FORM[instance_key] != player_state.is_in_final_smash_form {
             
             // AGGRESSIVE: Force cleanup on motion change if in final smash form and not in attack animation
-            if player_state.is_in_final_smash_form && !is_in_fs_attack_animation && LAST_MOTION[entry_id] != current_motion {
+            if player_state.is_in_final_smash_form && !is_in_fs_attack_animation && LAST_MOTION[instance_key] != current_motion {
                                 
                 // Force cleanup immediately
                 EffectModule::kill_kind(boma, Hash40::new("sys_final_aura2"), false, true);
-                ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
-                ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
-                ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
+                set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
+                set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
+                set_mesh_visibility(boma, *GASTLY_BODY, false);
                 
                 player_state.is_in_final_smash_form = false;
                 
                 // Force normal visibility for current stage
                 match player_state.stage {
                     EvolutionStage::Gastly => {
-                        ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, true);
-                        ModelModule::set_mesh_visibility(boma, *GASTLY_EYE_N, true);
+                        set_mesh_visibility(boma, *GASTLY_BODY, true);
+                        set_mesh_visibility(boma, *GASTLY_EYE_N, true);
                     },
                     EvolutionStage::Haunter => {
-                        ModelModule::set_mesh_visibility(boma, *HAUNTER_BODY, true);
-                        ModelModule::set_mesh_visibility(boma, *HAUNTER_HANDS, true);
-                        ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, true);
-                        ModelModule::set_mesh_visibility(boma, *HAUNTER_EYE_N, true);
+                        set_mesh_visibility(boma, *HAUNTER_BODY, true);
+                        set_mesh_visibility(boma, *HAUNTER_HANDS, true);
+                        set_mesh_visibility(boma, *HAUNTER_IRIS, true);
+                        set_mesh_visibility(boma, *HAUNTER_EYE_N, true);
                     },
                     EvolutionStage::Gengar => {
-                        ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, true);
-                        ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, true);
-                        ModelModule::set_mesh_visibility(boma, *GENGAR_EYE_N, true);
+                        set_mesh_visibility(boma, *GENGAR_BODY, true);
+                        set_mesh_visibility(boma, *GENGAR_IRIS, true);
+                        set_mesh_visibility(boma, *GENGAR_EYE_N, true);
                     }
                 }
                 
                             }
             
-            LAST_FS_STATE[entry_id] = is_final_smash_active_flag;
-            LAST_MOTION[entry_id] = current_motion;
-            LAST_FS_FORM[entry_id] = player_state.is_in_final_smash_form;
+            LAST_FS_STATE[instance_key] = is_final_smash_active_flag;
+            LAST_MOTION[instance_key] = current_motion;
+            LAST_FS_FORM[instance_key] = player_state.is_in_final_smash_form;
         }
     }
     
@@ -882,21 +672,22 @@ pub unsafe fn handle_final_smash_model_swap(boma: *mut BattleObjectModuleAccesso
             // Hide all animation-specific meshes during FS
             hide_all_animation_specific_meshes(boma);
             
-            if player_state.mega_gengar_form_active {
+            // Forms missing from the slot's model keep normal Gengar
+            if player_state.mega_gengar_form_active && is_mesh_available(boma, *MEGA_GENGAR_BODY) {
                 // Hide normal Gengar parts
-                ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, false); 
-                for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-                ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, false);
+                set_mesh_visibility(boma, *GENGAR_BODY, false); 
+                for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { set_mesh_visibility(boma, *eye_hash, false); }
+                set_mesh_visibility(boma, *GENGAR_IRIS, false);
                 // Show Mega Gengar
-                ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, true);
+                set_mesh_visibility(boma, *MEGA_GENGAR_BODY, true);
                 player_state.is_in_final_smash_form = true;
-                            } else if player_state.giga_gengar_form_active {
+                            } else if player_state.giga_gengar_form_active && is_mesh_available(boma, *GIGA_GENGAR_BODY) {
                 // Hide normal Gengar parts
-                ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, false); 
-                for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-                ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, false);
+                set_mesh_visibility(boma, *GENGAR_BODY, false); 
+                for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { set_mesh_visibility(boma, *eye_hash, false); }
+                set_mesh_visibility(boma, *GENGAR_IRIS, false);
                 // Show Giga Gengar
-                ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, true);
+                set_mesh_visibility(boma, *GIGA_GENGAR_BODY, true);
                 player_state.is_in_final_smash_form = true;
                             }
         }
@@ -909,27 +700,30 @@ pub unsafe fn handle_final_smash_model_swap(boma: *mut BattleObjectModuleAccesso
         EffectModule::kill_kind(boma, Hash40::new("sys_final_aura2"), false, true);
         
         // STEP 2: AGGRESSIVELY hide ALL possible meshes that could be visible
-        ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
-        ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
-        ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
-        ModelModule::set_mesh_visibility(boma, *GASTLY_TONGUE, false);
-        ModelModule::set_mesh_visibility(boma, *HAUNTER_BODY, false);
-        ModelModule::set_mesh_visibility(boma, *HAUNTER_HANDS, false);
-        ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, false);
-        ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, false);
-        ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, false);
-        ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
-        ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
+        set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
+        set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
+        set_mesh_visibility(boma, *GASTLY_BODY, false);
+        set_mesh_visibility(boma, *GASTLY_TONGUE, false);
+        set_mesh_visibility(boma, *HAUNTER_BODY, false);
+        set_mesh_visibility(boma, *HAUNTER_HANDS, false);
+        set_mesh_visibility(boma, *HAUNTER_IRIS, false);
+        set_mesh_visibility(boma, *GENGAR_BODY, false);
+        set_mesh_visibility(boma, *GENGAR_IRIS, false);
+        set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
+        set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
+        for tongue in MIRRORED_TONGUES.iter() {
+            set_mesh_visibility(boma, *tongue, false);
+        }
         
         // STEP 3: Hide ALL eye expressions from ALL stages
         for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() {
-            ModelModule::set_mesh_visibility(boma, *eye_hash, false);
+            set_mesh_visibility(boma, *eye_hash, false);
         }
         for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() {
-            ModelModule::set_mesh_visibility(boma, *eye_hash, false);
+            set_mesh_visibility(boma, *eye_hash, false);
         }
         for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() {
-            ModelModule::set_mesh_visibility(boma, *eye_hash, false);
+            set_mesh_visibility(boma, *eye_hash, false);
         }
         
         // STEP 4: Hide all animation-specific meshes
@@ -942,238 +736,48 @@ pub unsafe fn handle_final_smash_model_swap(boma: *mut BattleObjectModuleAccesso
         update_body_and_unique_parts_visibility(boma, player_state.stage);
         
         // STEP 7: Set the correct eye expression manually as backup
-        let eye_to_show = match player_state.stage {
-            EvolutionStage::Gastly => {
-                match player_state.blink_phase {
-                    crate::gastly::player_state::BlinkPhase::Open => *GASTLY_EYE_N,
-                    crate::gastly::player_state::BlinkPhase::HalfClose | 
-                    crate::gastly::player_state::BlinkPhase::HalfOpen => *GASTLY_EYE_HALFBLINK1,
-                    crate::gastly::player_state::BlinkPhase::FullClose => *GASTLY_EYE_BLINK,
-                }
-            },
-            EvolutionStage::Haunter => {
-                match player_state.blink_phase {
-                    crate::gastly::player_state::BlinkPhase::Open => *HAUNTER_EYE_N,
-                    crate::gastly::player_state::BlinkPhase::HalfClose | 
-                    crate::gastly::player_state::BlinkPhase::HalfOpen => *HAUNTER_EYE_HALFBLINK1,
-                    crate::gastly::player_state::BlinkPhase::FullClose => *HAUNTER_EYE_BLINK,
-                }
-            },
-            EvolutionStage::Gengar => {
-                match player_state.blink_phase {
-                    crate::gastly::player_state::BlinkPhase::Open => *GENGAR_EYE_N,
-                    crate::gastly::player_state::BlinkPhase::HalfClose | 
-                    crate::gastly::player_state::BlinkPhase::HalfOpen => *GENGAR_EYE_HALFBLINK1,
-                    crate::gastly::player_state::BlinkPhase::FullClose => *GENGAR_EYE_BLINK,
-                }
-            }
-        };
+        let eye_to_show = eye_choice_mesh(player_state.stage, player_state.expressions.current(), &|mesh| is_mesh_available(boma, mesh));
         
-        ModelModule::set_mesh_visibility(boma, eye_to_show, true);
+        set_mesh_visibility(boma, eye_to_show, true);
         
             }
 }
 
 pub unsafe fn hide_all_animation_specific_meshes(boma: *mut BattleObjectModuleAccessor) {
     // Hide shadow floor meshes
-    ModelModule::set_mesh_visibility(boma, *GASTLY_FLOORSHADOW, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_FLOORSHADOW, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_FLOORSHADOW, false);
+    set_mesh_visibility(boma, *GASTLY_FLOORSHADOW, false);
+    set_mesh_visibility(boma, *HAUNTER_FLOORSHADOW, false);
+    set_mesh_visibility(boma, *GENGAR_FLOORSHADOW, false);
     
     // Hide shadow ball meshes
-    ModelModule::set_mesh_visibility(boma, *GASTLY_SHADOWBALL, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_SHADOWBALL, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_SHADOWBALL, false);
+    set_mesh_visibility(boma, *GASTLY_SHADOWBALL, false);
+    set_mesh_visibility(boma, *HAUNTER_SHADOWBALL, false);
+    set_mesh_visibility(boma, *GENGAR_SHADOWBALL, false);
     
     // Hide ghost mesh
-    ModelModule::set_mesh_visibility(boma, *GHOST, false);
+    set_mesh_visibility(boma, *GHOST, false);
     
     // Hide gengar run mesh
-    ModelModule::set_mesh_visibility(boma, *GENGAR_RUN, false);
+    set_mesh_visibility(boma, *GENGAR_RUN, false);
     
     // Hide all tongue meshes (they'll be shown by animation-specific logic if needed)
-    ModelModule::set_mesh_visibility(boma, *GASTLY_TONGUE, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
+    set_mesh_visibility(boma, *GASTLY_TONGUE, false);
+    set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
+    set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
+    for tongue in MIRRORED_TONGUES.iter() {
+        set_mesh_visibility(boma, *tongue, false);
+    }
     
     //  Hide all evolving meshes
     hide_all_evolving_meshes(boma);
 }
 
-// Enhanced mesh management for evolving state
-unsafe fn show_evolving_meshes_for_animation(
-    boma: *mut BattleObjectModuleAccessor,
-    player_state: &PlayerEvolutionState,
-    current_motion: u64
-) -> bool {
-    if !player_state.is_evolving {
-        return false;
-    }
-
-    let evolution_stage = player_state.stage;
-    let target_stage = player_state.evolution_target_stage;
-    
-    // Determine which evolving meshes to use based on current evolution
-    let (evolving_main, evolving_floorshadow, evolving_tongue) = match (evolution_stage, target_stage) {
-        (EvolutionStage::Gastly, EvolutionStage::Haunter) => {
-            (*GASTLY_EVOLVING, *GASTLY_EVOLVING_FLOORSHADOW, *GASTLY_EVOLVING_TONGUE)
-        },
-        (EvolutionStage::Haunter, EvolutionStage::Gengar) => {
-            // Haunter doesn't have tongue, so use a dummy value that won't be shown
-            (*HAUNTER_EVOLVING, *HAUNTER_EVOLVING_FLOORSHADOW, *GASTLY_TONGUE) // Dummy tongue
-        },
-        _ => return false, // Invalid evolution combination
-    };
-
-    // Check for special animation cases
-    let motion_hash = Hash40 { hash: current_motion };
-    let current_status = StatusModule::status_kind(boma);
-    
-    // Don't show evolving meshes during rollout statuses 
-    // (shadowball logic in set_active_eye_mesh handles those cases)
-    if current_status == PURIN_SPECIAL_N_ROLL || current_status == PURIN_SPECIAL_N_ROLL_AIR {
-        return false; // Let shadowball logic handle rollout visibility
-    }
-    
-    // SPECIAL CASE: During evolution with hold statuses, check if hitbox is active
-    if current_status == PURIN_SPECIAL_N_HOLD || current_status == PURIN_SPECIAL_N_HOLD_MAX {
-        // If evolving and no hitbox is active, show evolving mesh instead of shadowball
-        let has_active_hitbox = AttackModule::is_attack(boma, 0, false) ||
-                               AttackModule::is_attack(boma, 1, false) ||
-                               AttackModule::is_attack(boma, 2, false) ||
-                               AttackModule::is_infliction_status(boma, 0) ||
-                               AttackModule::is_attack_occur(boma);
-        
-        if has_active_hitbox {
-            return false; // Let shadowball logic handle this (hitbox is active)
-        }
-        // If no hitbox, continue to show evolving mesh below
-    }
-    
-    // Check for squat_wait animation (floor shadow)
-    if motion_hash.hash == SQUAT_WAIT_MOTION.hash {
-        ModelModule::set_mesh_visibility(boma, evolving_floorshadow, true);
-                return true;
-    }
-    
-    // Check for tongue animations (only for Gastly → Haunter evolution)
-    if evolution_stage == EvolutionStage::Gastly && target_stage == EvolutionStage::Haunter {
-        // Check for attack_air_lw animation with frame-specific tongue visibility
-        if motion_hash.hash == smash::hash40("attack_air_lw") {
-            let motion_frame = MotionModule::frame(boma);
-            
-            // Show evolving tongue + main mesh during frames 7-49
-            if motion_frame >= 7.0 && motion_frame <= 49.0 {
-                ModelModule::set_mesh_visibility(boma, evolving_main, true);
-                ModelModule::set_mesh_visibility(boma, evolving_tongue, true);
-                                return true;
-            }
-        }
-        
-        // Check for catch animations (catch_attack, catch_wait) 
-        if motion_hash.hash == smash::hash40("catch_attack") || motion_hash.hash == smash::hash40("catch_wait") {
-            ModelModule::set_mesh_visibility(boma, evolving_main, true);
-            ModelModule::set_mesh_visibility(boma, evolving_tongue, true);
-                        return true;
-        }
-    }
-    
-    // Default case - show main evolving mesh for all other animations
-    ModelModule::set_mesh_visibility(boma, evolving_main, true);
-        true
-}
-
-// DEPRECATED: Old function kept for compatibility, but now uses new detection
-pub unsafe fn check_and_show_animation_specific_meshes(boma: *mut BattleObjectModuleAccessor, player_state: &mut PlayerEvolutionState) -> bool {
-    // This function is now handled by the new shadowball detection system
-    // Keep for any external calls, but redirect to new logic
-    let shadowball_state = detect_shadowball_hitbox_state(boma, player_state);
-    
-    match shadowball_state {
-        ShadowballState::ActiveWithHitbox | ShadowballState::ActiveFrameBased => true,
-        ShadowballState::RegularRollout => true,
-        _ => check_and_show_other_animation_meshes(boma, player_state)
-    }
-}
-
 // Check if player is in rollout status
 pub unsafe fn is_in_rollout_status(boma: *mut BattleObjectModuleAccessor) -> bool {
     let current_status = StatusModule::status_kind(boma);
     current_status == 0x1E3 || current_status == 0x1E4 || current_status == 0x1E5   // SPECIAL_N_ROLL | SPECIAL_N_ROLL_AIR | SPECIAL_N_TURN
 }
 
-// Bomb Detection
-unsafe fn handle_bomb_detection_in_visuals(
-    boma: *mut BattleObjectModuleAccessor,
-    player_state: &mut PlayerEvolutionState
-) {
-    static mut BOMB_STATE: [SimpleHitState; 8] = [SimpleHitState::new(); 8];
-    
-    let entry_id = WorkModule::get_int(boma, *FIGHTER_INSTANCE_WORK_ID_INT_ENTRY_ID) as usize;
-    if entry_id >= 8 { return; }
-    
-    let hit_state = &mut BOMB_STATE[entry_id];
-    
-    // Simple hit detection
-    let currently_hitting = AttackModule::is_infliction_status(boma, 0) ||
-                           AttackModule::is_attack_occur(boma) ||
-                           AttackModule::is_attack(boma, 0, false);
-    
-    // Fresh hit with cooldown
-    let fresh_hit = currently_hitting && 
-                   !hit_state.was_hitting && 
-                   (player_state.current_frame - hit_state.last_bomb_frame >= 15);
-    
-    if fresh_hit {
-                
-        // Get enemy position (simplified)
-        let mut enemy_pos = Vector3f { x: 0.0, y: 0.0, z: 0.0 };
-        ModelModule::joint_global_position(boma, Hash40::new("top"), &mut enemy_pos, true);
-        
-        // Offset forward for enemy location
-        let facing = PostureModule::lr(boma);
-        enemy_pos.x += facing * 3.0;
-        enemy_pos.y += 1.5;
-        
-        // Clean up old bombs
-        EffectModule::kill_kind(boma, Hash40::new("mewtwo_shadowball_bomb"), false, true);
-        EffectModule::kill_kind(boma, Hash40::new("sys_bomb_a"), false, true);
-        
-        // Spawn bomb
-        let bomb_rot = Vector3f { x: 0.0, y: 0.0, z: 0.0 };
-        let bomb_handle = EffectModule::req(
-            boma,
-            Hash40::new("mewtwo_shadowball_bomb").into(),
-            &enemy_pos,
-            &bomb_rot,
-            2.5,
-            60, // Lifetime frames
-            -1,
-            false,
-            0
-        );
-        
-        // Backup effect
-        let backup_handle = EffectModule::req(
-            boma,
-            Hash40::new("sys_bomb_a").into(),
-            &enemy_pos,
-            &bomb_rot,
-            1.8,
-            60,
-            -1,
-            false,
-            0
-        );
-        
-        if bomb_handle != u64::MAX || backup_handle != u64::MAX {
-            hit_state.last_bomb_frame = player_state.current_frame;
-                    }
-    }
-    
-    hit_state.was_hitting = currently_hitting;
-}
-
 // SINGLE function that handles everything - no external calls can interfere
 pub unsafe fn handle_weakened_state_isolated(
     boma: *mut BattleObjectModuleAccessor,
@@ -1295,24 +899,27 @@ pub unsafe fn emergency_cleanup_weakened(entry_id: u32) {
 // Hide all meshes (for rollout)
 pub unsafe fn hide_all_meshes(boma: *mut BattleObjectModuleAccessor) {
     // Hide all normal body meshes
-    ModelModule::set_mesh_visibility(boma, *GASTLY_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *GASTLY_TONGUE, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_HANDS, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
-    ModelModule::set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
-    ModelModule::set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
+    set_mesh_visibility(boma, *GASTLY_BODY, false);
+    set_mesh_visibility(boma, *GASTLY_TONGUE, false);
+    set_mesh_visibility(boma, *HAUNTER_BODY, false);
+    set_mesh_visibility(boma, *HAUNTER_HANDS, false);
+    set_mesh_visibility(boma, *GENGAR_BODY, false);
+    set_mesh_visibility(boma, *GENGAR_TONGUE_LONG, false);
+    set_mesh_visibility(boma, *GENGAR_TONGUE_NORMAL, false);
+    for tongue in MIRRORED_TONGUES.iter() {
+        set_mesh_visibility(boma, *tongue, false);
+    }
+    set_mesh_visibility(boma, *MEGA_GENGAR_BODY, false);
+    set_mesh_visibility(boma, *GIGA_GENGAR_BODY, false);
 
     // Hide all eye expressions
-    for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-    for eye_hash in PURIN_VANILLA_EYES_TO_HIDE.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-    for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
-    for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { ModelModule::set_mesh_visibility(boma, *eye_hash, false); }
+    for eye_hash in GASTLY_EYE_EXPRESSIONS.iter() { set_mesh_visibility(boma, *eye_hash, false); }
+    for eye_hash in PURIN_VANILLA_EYES_TO_HIDE.iter() { set_mesh_visibility(boma, *eye_hash, false); }
+    for eye_hash in HAUNTER_EYELID_EXPRESSIONS.iter() { set_mesh_visibility(boma, *eye_hash, false); }
+    for eye_hash in GENGAR_EYELID_EXPRESSIONS.iter() { set_mesh_visibility(boma, *eye_hash, false); }
     
-    ModelModule::set_mesh_visibility(boma, *HAUNTER_IRIS, false);
-    ModelModule::set_mesh_visibility(boma, *GENGAR_IRIS, false);
+    set_mesh_visibility(boma, *HAUNTER_IRIS, false);
+    set_mesh_visibility(boma, *GENGAR_IRIS, false);
     
     // Hide all animation-specific meshes
     hide_all_animation_specific_meshes(boma);
@@ -72,54 +72,16 @@ pub fn check_deps() -> bool {
 
 extern "C" fn mods_mounted(_ev: arcropolis_api::Event) {
     const FIGHTER_NAME: &str = "purin";
-    const MARKER_FILE: &str = "gastly.marker";
-    let mut lowest_color: i32 = -1;
-    let mut marked_slots: Vec<i32> = vec![];
-    
-    // Regular Marker check loop
-    for x in 0..256 {
-        if let Ok(_) = std::fs::read(format!(
-            "mods:/fighter/{}/model/body/c{:02}/{}",
-            FIGHTER_NAME, x, MARKER_FILE
-        )) {
-            unsafe {
-                marked_slots.push(x as _);
-                MARKED_COLORS[x as usize] = true;
-                if lowest_color == -1 {
-                    lowest_color = x as _;
-                }
-            }
-        }
-    }
-
-    // Shiny Marker check loop
-    for x in 0..256 {
-        if let Ok(_) = std::fs::read(format!(
-            "mods:/fighter/{}/model/body/c{:02}/shiny.marker",
-            FIGHTER_NAME, x
-        )) {
-            unsafe {
-                SHINY_COLORS[x as usize] = true;
-            }
-            // Shiny slot detected
-        }
-    }
+    // Marker files (optionally with per-slot metadata, see gastly/markers.rs); slots need not be contiguous
+    crate::gastly::markers::load_slot_markers(FIGHTER_NAME);
 
+    // Unmark slots whose model is missing required meshes (report in sd:/ultimate/gastly/mesh_report.txt)
+    crate::gastly::mesh_manifest::validate_marked_slots(FIGHTER_NAME);
 
-    if lowest_color == -1 {
+    if unsafe { !(0..256).any(|slot| MARKED_COLORS[slot]) } {
         return;
     }
 
-    let color_num = {
-        unsafe {
-            let mut index = lowest_color;
-            while index < 256 && MARKED_COLORS[index as usize] {
-                index += 1;
-            }
-            index - lowest_color
-        }
-    };
-
     // Add character database entries for different evolution stages
     
     // Evolving "Who's that Pokemon?" chara_4 UI entry
@@ -188,7 +150,7 @@ extern "C" fn mods_mounted(_ev: arcropolis_api::Event) {
         },
     );
 
-    // Mega Gengar chara_6 UI entry (for cutin)
+    // Mega Gengar UI entry
     the_csk_collection_api::add_chara_db_entry_info(
         the_csk_collection_api::CharacterDatabaseEntry {
             ui_chara_id: hash40("ui_chara_mega_gengar"),
@@ -201,59 +163,48 @@ extern "C" fn mods_mounted(_ev: arcropolis_api::Event) {
         },
     );
 
-    // ===== CHARA_6 UI ENTRIES FOR CUTINS =====
-
-    // Haunter chara_6 (cutin) entry
-    the_csk_collection_api::add_chara_db_entry_info(
-        the_csk_collection_api::CharacterDatabaseEntry {
-            ui_chara_id: hash40("ui_chara_haunter_00"),
-            clone_from_ui_chara_id: Some(hash40("ui_chara_purin_00")),
-            name_id: the_csk_collection_api::StringType::Overwrite(
-                the_csk_collection_api::CStrCSK::new("haunter_cutin"),
-            ),
-            disp_order: the_csk_collection_api::SignedByteType::Overwrite(-1),
-            ..Default::default()
-        },
-    );
-
-    // Gengar chara_6 (cutin) entry
-    the_csk_collection_api::add_chara_db_entry_info(
-        the_csk_collection_api::CharacterDatabaseEntry {
-            ui_chara_id: hash40("ui_chara_gengar_00"),
-            clone_from_ui_chara_id: Some(hash40("ui_chara_purin_00")),
-            name_id: the_csk_collection_api::StringType::Overwrite(
-                the_csk_collection_api::CStrCSK::new("gengar_cutin"),
-            ),
-            disp_order: the_csk_collection_api::SignedByteType::Overwrite(-1),
-            ..Default::default()
-        },
-    );
-
-    // Mega Gengar chara_6 (cutin) entry
-    the_csk_collection_api::add_chara_db_entry_info(
-        the_csk_collection_api::CharacterDatabaseEntry {
-            ui_chara_id: hash40("ui_chara_mega_gengar_00"),
-            clone_from_ui_chara_id: Some(hash40("ui_chara_purin_00")),
-            name_id: the_csk_collection_api::StringType::Overwrite(
-                the_csk_collection_api::CStrCSK::new("mega_gengar_cutin"),
-            ),
-            disp_order: the_csk_collection_api::SignedByteType::Overwrite(-1),
+    // Mega Gengar layout so its portrait / stock icons can be shown during the final smash
+    the_csk_collection_api::add_chara_layout_db_entry_info(
+        the_csk_collection_api::CharacterLayoutDatabaseEntry {
+            ui_layout_id: hash40("ui_chara_mega_gengar_00"),
+            clone_from_ui_layout_id: Some(hash40("ui_chara_purin_00")),
+            ui_chara_id: the_csk_collection_api::Hash40Type::Overwrite(hash40("ui_chara_mega_gengar")),
             ..Default::default()
         },
     );
 
-    // Giga Gengar chara_6 (cutin) entry
-    the_csk_collection_api::add_chara_db_entry_info(
-        the_csk_collection_api::CharacterDatabaseEntry {
-            ui_chara_id: hash40("ui_chara_giga_gengar_00"),
-            clone_from_ui_chara_id: Some(hash40("ui_chara_purin_00")),
-            name_id: the_csk_collection_api::StringType::Overwrite(
-                the_csk_collection_api::CStrCSK::new("giga_gengar_cutin"),
-            ),
-            disp_order: the_csk_collection_api::SignedByteType::Overwrite(-1),
-            ..Default::default()
-        },
-    );
+    // Gigantamax Gengar and shiny stage entries (chara_2 stock icons / chara_4 portraits per form)
+    for (ui_chara, name) in [
+        ("ui_chara_giga_gengar", "giga_gengar"),
+        ("ui_chara_gastly_shiny", "gastly_shiny"),
+        ("ui_chara_haunter_shiny", "haunter_shiny"),
+        ("ui_chara_gengar_shiny", "gengar_shiny"),
+        ("ui_chara_mega_gengar_shiny", "mega_gengar_shiny"),
+        ("ui_chara_giga_gengar_shiny", "giga_gengar_shiny"),
+    ] {
+        the_csk_collection_api::add_chara_db_entry_info(
+            the_csk_collection_api::CharacterDatabaseEntry {
+                ui_chara_id: hash40(ui_chara),
+                clone_from_ui_chara_id: Some(hash40("ui_chara_purin")),
+                name_id: the_csk_collection_api::StringType::Overwrite(
+                    the_csk_collection_api::CStrCSK::new(name),
+                ),
+                disp_order: the_csk_collection_api::SignedByteType::Overwrite(-1),
+                ..Default::default()
+            },
+        );
+
+        the_csk_collection_api::add_chara_layout_db_entry_info(
+            the_csk_collection_api::CharacterLayoutDatabaseEntry {
+                ui_layout_id: hash40(&format!("{}_00", ui_chara)),
+                clone_from_ui_layout_id: Some(hash40("ui_chara_purin_00")),
+                ui_chara_id: the_csk_collection_api::Hash40Type::Overwrite(hash40(ui_chara)),
+                ..Default::default()
+            },
+        );
+
+        the_csk_collection_api::allow_ui_chara_hash_online(hash40(ui_chara));
+    }
 
     // Allow all custom UIs for online play
     the_csk_collection_api::allow_ui_chara_hash_online(hash40("ui_chara_evolving"));
@@ -262,12 +213,6 @@ extern "C" fn mods_mounted(_ev: arcropolis_api::Event) {
     the_csk_collection_api::allow_ui_chara_hash_online(hash40("ui_chara_mega_gengar"));
     the_csk_collection_api::allow_ui_chara_hash_online(hash40("ui_chara_giga_gengar"));
 
-    // Allow chara_6 cutins for online play
-    the_csk_collection_api::allow_ui_chara_hash_online(hash40("ui_chara_haunter_00"));
-    the_csk_collection_api::allow_ui_chara_hash_online(hash40("ui_chara_gengar_00"));
-    the_csk_collection_api::allow_ui_chara_hash_online(hash40("ui_chara_mega_gengar_00"));
-    the_csk_collection_api::allow_ui_chara_hash_online(hash40("ui_chara_giga_gengar_00"));
-
     // Gastly mod initialization with detected colors
     
     // Install systems exactly as they were when hook comment-out worked